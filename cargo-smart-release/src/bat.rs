@@ -1,47 +1,528 @@
-use std::{io, path::Path, process::Command};
+use std::{
+    ffi::OsString,
+    io::{self, IsTerminal, Read, Write},
+    path::Path,
+    process::{Child, Command, ExitStatus, Stdio},
+    time::{Duration, Instant},
+};
 
 use crate::utils::Program;
 
+/// A program to display previews with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Previewer {
+    /// [`bat`](https://github.com/sharkdp/bat), which can highlight many languages.
+    Bat,
+    /// [`delta`](https://github.com/dandavison/delta), a pager for diffs.
+    Delta,
+}
+
 pub struct Support {
     bat: Program,
+    delta: Program,
+    /// The programs to try for previewing diffs, in order of preference. Other languages are always shown with `bat`.
+    pub diff_previewers: Vec<Previewer>,
+    /// If set, previewers that don't finish within the given time are killed, which is an error.
+    ///
+    /// It's set to [`NON_INTERACTIVE_TIMEOUT`] if stdout isn't a terminal, as nobody could quit a hanging pager then.
+    pub timeout: Option<Duration>,
+    /// The program and its arguments to page with if `bat` isn't available.
+    fallback_pager: Option<Vec<String>>,
+    /// If `true`, never page, just print to stdout.
+    no_pager: bool,
+    /// If `true`, print without colors.
+    no_color: bool,
 }
 
 impl Default for Support {
     fn default() -> Self {
-        Self::new()
+        Self::new(false, false)
     }
 }
 
 impl Support {
-    pub fn new() -> Self {
+    /// Create a new instance which doesn't page if `no_pager` is set or if stdout isn't a terminal,
+    /// and which doesn't use colors if `no_color` is set or if the environment asks for it with `NO_COLOR` or `CLICOLOR=0`.
+    pub fn new(no_pager: bool, no_color: bool) -> Self {
         Support {
             bat: Program::named("bat"),
+            delta: Program::named("delta"),
+            diff_previewers: vec![Previewer::Delta, Previewer::Bat],
+            fallback_pager: fallback_pager(),
+            timeout: (!io::stdout().is_terminal()).then_some(NON_INTERACTIVE_TIMEOUT),
+            no_pager: no_pager || !io::stdout().is_terminal(),
+            no_color: no_color || colors_disabled_by(|name| std::env::var_os(name)),
+        }
+    }
+
+    /// Return the program to preview `language` with, or `None` if there is none.
+    fn previewer(&self, language: Option<&str>) -> Option<Previewer> {
+        let is_available = |previewer: &Previewer| match previewer {
+            Previewer::Bat => self.bat.found,
+            Previewer::Delta => self.delta.found && !self.no_color,
+        };
+        if language == Some("diff") {
+            self.diff_previewers.iter().copied().find(is_available)
+        } else {
+            Some(Previewer::Bat).filter(is_available)
         }
     }
 
+    /// Display the file at `path` with syntax highlighting for `language`, like `"toml"` or `"diff"`, or markdown if `None`.
+    ///
+    /// Diffs are shown with the first available of the [`diff_previewers`](Self::diff_previewers), everything else with `bat`.
+    /// Without any of these the file is shown without highlighting.
     pub fn display_to_tty(
         &self,
         path: &Path,
         path_for_title: &Path,
         additional_title: impl AsRef<str>,
+        language: Option<&str>,
     ) -> io::Result<()> {
-        if !self.bat.found {
-            log::warn!(
-                "Would want to use 'bat' for colored preview of '{}', but it wasn't available in the PATH.",
-                path.display()
-            );
-            return Ok(());
-        }
-        if Command::new("bat")
-            .args(["--paging=always", "-l=md", "--file-name"])
-            .arg(format!("{} ({})", path_for_title.display(), additional_title.as_ref()))
-            .arg(path)
-            .status()?
-            .success()
-        {
-            Ok(())
+        match self.previewer(language) {
+            None => self.display_without_highlighting(path, language),
+            Some(Previewer::Delta) => {
+                let mut cmd = Command::new("delta");
+                cmd.arg(paging_arg(!self.no_pager)).stdin(std::fs::File::open(path)?);
+                run(cmd, "delta", None, self.timeout)
+            }
+            Some(Previewer::Bat) => {
+                let mut cmd = Command::new("bat");
+                cmd.args(bat_args(
+                    language.unwrap_or(DEFAULT_LANGUAGE),
+                    !self.no_pager,
+                    !self.no_color,
+                ))
+                .arg("--file-name")
+                .arg(format!("{} ({})", path_for_title.display(), additional_title.as_ref()))
+                .arg(path);
+                run(cmd, "bat", None, self.timeout)
+            }
+        }
+    }
+
+    /// Display all `files`, each a pair of the path to read and the path to show in its separator, in a single pager session,
+    /// with `additional_title` used as title of the combined preview.
+    ///
+    /// Each file is preceded by a separator naming it, as produced by [`combined_preview()`]. A single file is displayed
+    /// like [`display_to_tty()`](Self::display_to_tty()) would, and nothing is displayed if there are no files.
+    pub fn display_all_to_tty(
+        &self,
+        files: &[(&Path, &Path)],
+        additional_title: impl AsRef<str>,
+        language: Option<&str>,
+    ) -> io::Result<()> {
+        match files {
+            [] => return Ok(()),
+            [(path, path_for_title)] => {
+                return self.display_to_tty(path, path_for_title, additional_title, language);
+            }
+            _ => {}
+        }
+        let content = combined_preview(files)?;
+        match self.previewer(language) {
+            None => {
+                for (path, _) in files {
+                    log::warn!(
+                        "Would want to use 'bat' for colored preview of '{}', but it wasn't available in the PATH.",
+                        path.display()
+                    );
+                }
+                if self.no_pager {
+                    return io::stdout().write_all(content.as_bytes());
+                }
+                let (program, args) = match self.fallback_pager.as_ref().and_then(|pager| pager.split_first()) {
+                    Some(pager) => pager,
+                    None => return Ok(()),
+                };
+                log::info!(
+                    "Falling back to '{}' for paging {} files without syntax highlighting.",
+                    self.fallback_pager.as_ref().expect("set").join(" "),
+                    files.len()
+                );
+                let mut cmd = Command::new(program);
+                cmd.args(args);
+                run(cmd, program, Some(content.as_bytes()), self.timeout)
+            }
+            Some(Previewer::Delta) => {
+                let mut cmd = Command::new("delta");
+                cmd.arg(paging_arg(!self.no_pager));
+                run(cmd, "delta", Some(content.as_bytes()), self.timeout)
+            }
+            Some(Previewer::Bat) => {
+                let mut cmd = Command::new("bat");
+                cmd.args(bat_args(
+                    language.unwrap_or(DEFAULT_LANGUAGE),
+                    !self.no_pager,
+                    !self.no_color,
+                ))
+                .arg("--file-name")
+                .arg(additional_title.as_ref())
+                .arg("-");
+                run(cmd, "bat", Some(content.as_bytes()), self.timeout)
+            }
+        }
+    }
+
+    fn display_without_highlighting(&self, path: &Path, language: Option<&str>) -> io::Result<()> {
+        log::warn!(
+            "Would want to use 'bat' for colored preview of '{}', but it wasn't available in the PATH.",
+            path.display()
+        );
+        if self.no_pager {
+            return io::stdout().write_all(self.render_to_string(path, language)?.as_bytes());
+        }
+        let (program, args) = match self.fallback_pager.as_ref().and_then(|pager| pager.split_first()) {
+            Some(pager) => pager,
+            None => return Ok(()),
+        };
+        log::info!(
+            "Falling back to '{}' for paging '{}' without syntax highlighting.",
+            self.fallback_pager.as_ref().expect("set").join(" "),
+            path.display()
+        );
+        let mut cmd = Command::new(program);
+        cmd.args(args).arg(path);
+        run(cmd, program, None, self.timeout)
+    }
+
+    /// Return the file at `path` highlighted for `language` (or markdown if `None`) like [`display_to_tty()`](Self::display_to_tty()),
+    /// but without involving a terminal.
+    ///
+    /// Without a program to highlight `language` the contents of the file are returned as is.
+    pub fn render_to_string(&self, path: &Path, language: Option<&str>) -> io::Result<String> {
+        let (program, output) = match self.previewer(language) {
+            None => return std::fs::read_to_string(path),
+            Some(Previewer::Delta) => (
+                "delta",
+                Command::new("delta")
+                    .arg(paging_arg(false))
+                    .stdin(std::fs::File::open(path)?)
+                    .output()?,
+            ),
+            Some(Previewer::Bat) => (
+                "bat",
+                Command::new("bat")
+                    .args(bat_args(language.unwrap_or(DEFAULT_LANGUAGE), false, !self.no_color))
+                    .args((!self.no_color).then_some("--color=always"))
+                    .arg(path)
+                    .output()?,
+            ),
+        };
+        if !output.status.success() {
+            return Err(failure(program, output.status, &output.stderr));
+        }
+        String::from_utf8(output.stdout).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// The time after which previewers are killed if stdout isn't a terminal.
+pub const NON_INTERACTIVE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Run `cmd` with its output going to the terminal and `input`, if set, fed to its standard input,
+/// but capture its stderr to produce a descriptive error mentioning `program` if it fails.
+///
+/// If `timeout` is set and `cmd` doesn't finish in time, it is killed and an error is returned.
+fn run(mut cmd: Command, program: &str, input: Option<&[u8]>, timeout: Option<Duration>) -> io::Result<()> {
+    if input.is_some() {
+        cmd.stdin(Stdio::piped());
+    }
+    let mut child = cmd.stderr(Stdio::piped()).spawn()?;
+    // Feed and drain the pipes on their own threads so a hanging program can't block us beyond the timeout.
+    let writer = input.map(|input| {
+        let mut stdin = child.stdin.take().expect("configured");
+        let input = input.to_owned();
+        std::thread::spawn(move || stdin.write_all(&input))
+    });
+    let mut stderr = child.stderr.take().expect("configured");
+    let reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        stderr.read_to_end(&mut buf).map(|_| buf)
+    });
+
+    let status = match timeout {
+        None => child.wait()?,
+        Some(timeout) => match wait_with_timeout(&mut child, timeout)? {
+            Some(status) => status,
+            None => {
+                child.kill().ok();
+                child.wait()?;
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("{program} didn't finish within {timeout:?} and was terminated"),
+                ));
+            }
+        },
+    };
+    let stderr = reader.join().expect("no panic")?;
+    if status.success() {
+        // A pager that is quit early closes its input, which isn't an error.
+        match writer.map(|writer| writer.join().expect("no panic")) {
+            Some(Err(err)) if err.kind() != io::ErrorKind::BrokenPipe => return Err(err),
+            _ => {}
+        }
+        io::stderr().write_all(&stderr)
+    } else {
+        Err(failure(program, status, &stderr))
+    }
+}
+
+/// Wait for `child` to exit for at most `timeout`, and return its exit status, or `None` if it is still running.
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Concatenate the contents of all `files`, pairs of the path to read and the path to show, into one preview.
+///
+/// Like `head` does with multiple files, each file is preceded by a `==> path (n / total) <==` separator line,
+/// and files are separated by an empty line.
+fn combined_preview(files: &[(&Path, &Path)]) -> io::Result<String> {
+    let mut out = String::new();
+    for (idx, (path, path_for_title)) in files.iter().enumerate() {
+        if idx != 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "==> {} ({} / {}) <==\n",
+            path_for_title.display(),
+            idx + 1,
+            files.len()
+        ));
+        let content = std::fs::read_to_string(path)?;
+        out.push_str(&content);
+        if !content.is_empty() && !content.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// Produce an error for `program` which exited with `status` and printed `stderr`.
+fn failure(program: &str, status: ExitStatus, stderr: &[u8]) -> io::Error {
+    let stderr = String::from_utf8_lossy(stderr);
+    let stderr = stderr.trim();
+    io::Error::new(
+        io::ErrorKind::Other,
+        if stderr.is_empty() {
+            format!("{program} failed with {status}")
         } else {
-            Err(io::Error::new(io::ErrorKind::Other, "bat exited with an error"))
+            format!("{program} failed with {status}: {stderr}")
+        },
+    )
+}
+
+/// The language to highlight if none is specified.
+const DEFAULT_LANGUAGE: &str = "md";
+
+/// Return the arguments to pass to `bat` to display `language`, with `paging` and `color` enabled or disabled.
+fn bat_args(language: &str, paging: bool, color: bool) -> Vec<String> {
+    let mut args = vec![paging_arg(paging).into(), format!("-l={language}")];
+    if !color {
+        args.push("--color=never".into());
+    }
+    args
+}
+
+/// Return the argument understood by `bat` and `delta` to enable or disable `paging`.
+fn paging_arg(paging: bool) -> &'static str {
+    if paging {
+        "--paging=always"
+    } else {
+        "--paging=never"
+    }
+}
+
+/// Return `true` if the environment variables obtained by `var` disable colors, following the conventions of
+/// [`NO_COLOR`](https://no-color.org) being set to a non-empty value and `CLICOLOR` being set to `0`.
+fn colors_disabled_by(var: impl Fn(&str) -> Option<OsString>) -> bool {
+    var("NO_COLOR").is_some_and(|value| !value.is_empty()) || var("CLICOLOR").is_some_and(|value| value == "0")
+}
+
+/// Use `$PAGER` if set, or `less -R` if it is available.
+fn fallback_pager() -> Option<Vec<String>> {
+    std::env::var("PAGER")
+        .ok()
+        .map(|pager| pager.split_whitespace().map(ToOwned::to_owned).collect::<Vec<_>>())
+        .filter(|pager| !pager.is_empty())
+        .or_else(|| Program::named("less").found.then(|| vec!["less".into(), "-R".into()]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{bat_args, colors_disabled_by, combined_preview, Previewer, Support, DEFAULT_LANGUAGE};
+    use crate::utils::Program;
+
+    fn support_with(bat: bool, delta: bool) -> Support {
+        Support {
+            bat: Program { found: bat },
+            delta: Program { found: delta },
+            diff_previewers: vec![Previewer::Delta, Previewer::Bat],
+            fallback_pager: None,
+            timeout: None,
+            no_pager: true,
+            no_color: false,
+        }
+    }
+
+    #[test]
+    fn paging_and_color_can_be_disabled() {
+        assert_eq!(bat_args(DEFAULT_LANGUAGE, true, true), ["--paging=always", "-l=md"]);
+        assert_eq!(bat_args(DEFAULT_LANGUAGE, false, true), ["--paging=never", "-l=md"]);
+        assert_eq!(
+            bat_args(DEFAULT_LANGUAGE, true, false),
+            ["--paging=always", "-l=md", "--color=never"]
+        );
+        assert_eq!(
+            bat_args(DEFAULT_LANGUAGE, false, false),
+            ["--paging=never", "-l=md", "--color=never"]
+        );
+    }
+
+    #[test]
+    fn language_is_configurable() {
+        assert_eq!(bat_args("toml", true, true), ["--paging=always", "-l=toml"]);
+        assert_eq!(bat_args("diff", false, true), ["--paging=never", "-l=diff"]);
+    }
+
+    #[test]
+    fn color_conventions_of_the_environment_are_respected() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find_map(|(var, value)| (*var == name).then(|| (*value).into()))
+            }
+        };
+        assert!(!colors_disabled_by(env(&[])));
+        assert!(colors_disabled_by(env(&[("NO_COLOR", "1")])));
+        assert!(
+            !colors_disabled_by(env(&[("NO_COLOR", "")])),
+            "an empty NO_COLOR is ignored"
+        );
+        assert!(colors_disabled_by(env(&[("CLICOLOR", "0")])));
+        assert!(!colors_disabled_by(env(&[("CLICOLOR", "1")])));
+    }
+
+    #[cfg(unix)]
+    mod run {
+        use std::{
+            io,
+            process::{Command, Stdio},
+            time::{Duration, Instant},
+        };
+
+        use super::super::run;
+
+        #[test]
+        fn failures_mention_exit_code_and_stderr() {
+            let mut cmd = Command::new("git");
+            cmd.arg("--no-such-argument");
+            let err = run(cmd, "git", None, None).unwrap_err().to_string();
+            assert!(err.starts_with("git failed with exit "), "{err}");
+            assert!(err.contains("129"), "the exit code is mentioned: {err}");
+            assert!(
+                err.contains("unknown option: --no-such-argument"),
+                "stderr is included: {err}"
+            );
         }
+
+        #[test]
+        fn hanging_programs_are_killed_after_the_timeout() {
+            let mut cmd = Command::new("sleep");
+            cmd.arg("10");
+            let start = Instant::now();
+            let err = run(cmd, "sleep", None, Some(Duration::from_millis(100))).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+            assert!(err.to_string().starts_with("sleep didn't finish within 100ms"), "{err}");
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "the program doesn't run to completion"
+            );
+
+            let mut cmd = Command::new("cat");
+            cmd.stdout(Stdio::null());
+            run(cmd, "cat", Some(b"input"), Some(Duration::from_secs(60)))
+                .expect("programs that finish in time succeed");
+        }
+    }
+
+    #[test]
+    fn render_to_string_without_bat_returns_the_file_as_is() {
+        let support = support_with(false, false);
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        assert_eq!(
+            support.render_to_string(&path, Some("toml")).unwrap(),
+            std::fs::read_to_string(&path).unwrap()
+        );
+    }
+
+    #[test]
+    fn combined_preview_separates_files_by_title() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let manifest = root.join("Cargo.toml");
+        let lib = root.join("src").join("lib.rs");
+        let preview = combined_preview(&[
+            (manifest.as_path(), Path::new("a/Cargo.toml")),
+            (lib.as_path(), Path::new("b/lib.rs")),
+        ])
+        .unwrap();
+        assert_eq!(
+            preview,
+            format!(
+                "==> a/Cargo.toml (1 / 2) <==\n{}\n==> b/lib.rs (2 / 2) <==\n{}",
+                std::fs::read_to_string(&manifest).unwrap(),
+                std::fs::read_to_string(&lib).unwrap()
+            )
+        );
+        assert_eq!(combined_preview(&[]).unwrap(), "", "nothing to preview");
+    }
+
+    #[test]
+    fn display_all_to_tty_without_files_does_nothing() {
+        let support = support_with(true, true);
+        support
+            .display_all_to_tty(&[], "title", None)
+            .expect("no program is run");
+    }
+
+    #[test]
+    fn delta_is_preferred_for_diffs_if_available() {
+        let mut support = support_with(true, true);
+        assert_eq!(support.previewer(Some("diff")), Some(Previewer::Delta));
+        assert_eq!(
+            support.previewer(None),
+            Some(Previewer::Bat),
+            "bat is used for markdown"
+        );
+        assert_eq!(support.previewer(Some("toml")), Some(Previewer::Bat));
+
+        support.diff_previewers = vec![Previewer::Bat, Previewer::Delta];
+        assert_eq!(
+            support.previewer(Some("diff")),
+            Some(Previewer::Bat),
+            "the order is configurable"
+        );
+
+        support.no_color = true;
+        support.diff_previewers.reverse();
+        assert_eq!(
+            support.previewer(Some("diff")),
+            Some(Previewer::Bat),
+            "delta can't be used without colors"
+        );
+
+        let support = support_with(false, true);
+        assert_eq!(support.previewer(Some("diff")), Some(Previewer::Delta));
+        assert_eq!(support.previewer(None), None, "delta is only for diffs");
     }
 }
@@ -18,6 +18,8 @@ fn main() -> anyhow::Result<()> {
             crates,
             no_dependencies,
             no_preview,
+            no_pager,
+            no_color,
             no_links,
             without,
             allow_dirty,
@@ -30,6 +32,8 @@ fn main() -> anyhow::Result<()> {
                     allow_dirty,
                     no_links,
                     preview: !no_preview,
+                    no_pager,
+                    no_color,
                     dependencies: !no_dependencies,
                     generator_segments: names_to_segment_selection(&without)?,
                     capitalize_commit,
@@ -58,6 +62,8 @@ fn main() -> anyhow::Result<()> {
             no_changelog,
             no_changelog_links,
             no_changelog_preview,
+            no_pager,
+            no_color,
             no_changelog_github_release,
             allow_fully_generated_changelogs,
             no_dependencies,
@@ -85,6 +91,8 @@ fn main() -> anyhow::Result<()> {
                     allow_auto_publish_of_stable_crates: auto_publish_of_stable_crates,
                     update_crates_index,
                     preview: !no_changelog_preview,
+                    no_pager,
+                    no_color,
                     generator_segments: names_to_segment_selection(&changelog_without)?,
                     allow_fully_generated_changelogs,
                     changelog_links: !no_changelog_links,
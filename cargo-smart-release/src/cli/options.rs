@@ -105,6 +105,14 @@ pub enum SubCommands {
         #[clap(long, help_heading = Some("CHANGELOG"))]
         no_changelog_preview: bool,
 
+        /// Print changelog previews without paging them, which is also the case if stdout isn't a terminal.
+        #[clap(long, help_heading = Some("CHANGELOG"))]
+        no_pager: bool,
+
+        /// Print changelog previews without colors, which is also the case if `NO_COLOR` is set or `CLICOLOR` is `0`.
+        #[clap(long, help_heading = Some("CHANGELOG"))]
+        no_color: bool,
+
         /// Allow publishes to take place on a dirty working tree. Really not recommended alongside --execute.
         #[clap(long, help_heading = Some("EXPERT"))]
         allow_dirty: bool,
@@ -202,6 +210,14 @@ pub enum SubCommands {
         #[clap(long, help_heading = Some("CUSTOMIZATION"))]
         no_preview: bool,
 
+        /// Print the preview without paging it, which is also the case if stdout isn't a terminal.
+        #[clap(long, help_heading = Some("CUSTOMIZATION"))]
+        no_pager: bool,
+
+        /// Print the preview without colors, which is also the case if `NO_COLOR` is set or `CLICOLOR` is `0`.
+        #[clap(long, help_heading = Some("CUSTOMIZATION"))]
+        no_color: bool,
+
         /// Do not generate links to commits and issues when writing the changelogs. This currently only works for GitHub.
         #[clap(long, help_heading = Some("CUSTOMIZATION"))]
         no_links: bool,
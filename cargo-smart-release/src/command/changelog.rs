@@ -18,6 +18,8 @@ pub fn changelog(opts: Options, crates: Vec<String>) -> anyhow::Result<()> {
         dry_run,
         preview,
         no_links,
+        no_pager,
+        no_color,
         capitalize_commit,
         ..
     } = opts;
@@ -60,7 +62,7 @@ pub fn changelog(opts: Options, crates: Vec<String>) -> anyhow::Result<()> {
         Some(history) => history,
     };
 
-    let bat = (dry_run && preview).then(bat::Support::new);
+    let bat = (dry_run && preview).then(|| bat::Support::new(no_pager, no_color));
 
     let mut pending_changes = Vec::new();
     let linkables = if dry_run || no_links {
@@ -71,7 +73,8 @@ pub fn changelog(opts: Options, crates: Vec<String>) -> anyhow::Result<()> {
         })
     };
     let mut num_crates = 0;
-    for (idx, package) in crates.iter().enumerate() {
+    let mut previews = Vec::new();
+    for package in &crates {
         num_crates += 1;
         let crate::changelog::init::Outcome {
             log, mut lock, state, ..
@@ -101,18 +104,29 @@ pub fn changelog(opts: Options, crates: Vec<String>) -> anyhow::Result<()> {
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
             file.write_all(buf.as_bytes())
         })?;
-        if let Some(bat) = bat.as_ref() {
-            bat.display_to_tty(
-                lock.lock_path(),
-                lock.resource_path().strip_prefix(&ctx.root.to_path_buf())?,
-                format!("PREVIEW {} / {}, press Ctrl+C to cancel", idx + 1, crates.len()),
-            )?;
-        }
-        if !dry_run {
+        if bat.is_some() {
+            previews.push(lock);
+        } else if !dry_run {
             pending_changes.push(lock);
         }
     }
 
+    if let Some(bat) = bat.as_ref() {
+        let files = previews
+            .iter()
+            .map(|lock| -> anyhow::Result<_> { Ok((lock.lock_path(), lock.resource_path().strip_prefix(&ctx.root)?)) })
+            .collect::<Result<Vec<_>, _>>()?;
+        bat.display_all_to_tty(
+            &files,
+            format!(
+                "PREVIEW of {} changelog{}, press Ctrl+C to cancel",
+                files.len(),
+                if files.len() == 1 { "" } else { "s" }
+            ),
+            None,
+        )?;
+    }
+
     if num_crates == 0 {
         anyhow::bail!(
             "The given crate{} {} didn't change and no changelog could be generated.",
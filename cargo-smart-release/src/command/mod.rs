@@ -21,6 +21,10 @@ pub mod release {
         pub isolate_dependencies_from_breaking_changes: bool,
         pub changelog: bool,
         pub preview: bool,
+        /// Print changelog previews without paging them.
+        pub no_pager: bool,
+        /// Print changelog previews without colors.
+        pub no_color: bool,
         pub generator_segments: segment::Selection,
         pub allow_fully_generated_changelogs: bool,
         pub changelog_links: bool,
@@ -41,6 +45,10 @@ pub mod changelog {
         pub dependencies: bool,
         pub allow_dirty: bool,
         pub preview: bool,
+        /// Print changelog previews without paging them.
+        pub no_pager: bool,
+        /// Print changelog previews without colors.
+        pub no_color: bool,
         // All the segments to generate
         pub generator_segments: segment::Selection,
         pub no_links: bool,
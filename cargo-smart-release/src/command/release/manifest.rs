@@ -255,7 +255,13 @@ fn commit_locks_and_generate_bail_message(
 fn preview_changelogs(
     ctx: &Context,
     pending_changelogs: &[(&Package, bool, File)],
-    Options { dry_run, preview, .. }: Options,
+    Options {
+        dry_run,
+        preview,
+        no_pager,
+        no_color,
+        ..
+    }: Options,
 ) -> anyhow::Result<()> {
     if !pending_changelogs.is_empty() && preview && !dry_run {
         let additional_info =
@@ -270,21 +276,24 @@ fn preview_changelogs(
             additional_info
         );
 
-        let bat = crate::bat::Support::new();
-        for (idx, lock) in changelogs_with_changes.iter().enumerate() {
-            let additional_info = format!(
-                "PREVIEW {} / {}, {}{}",
-                idx + 1,
-                changelogs_with_changes.len(),
+        let bat = crate::bat::Support::new(no_pager, no_color);
+        let files = changelogs_with_changes
+            .iter()
+            .map(|lock| -> anyhow::Result<_> {
+                Ok((lock.lock_path(), lock.resource_path().strip_prefix(&ctx.base.root)?))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        bat.display_all_to_tty(
+            &files,
+            format!(
+                "PREVIEW of {} changelog{}, {}{}",
+                files.len(),
+                if files.len() == 1 { "" } else { "s" },
                 if dry_run { "simplified, " } else { "" },
                 additional_info
-            );
-            bat.display_to_tty(
-                lock.lock_path(),
-                lock.resource_path().strip_prefix(&ctx.base.root.to_path_buf())?,
-                additional_info,
-            )?;
-        }
+            ),
+            None,
+        )?;
     } else if !pending_changelogs.is_empty() && preview {
         log::info!(
             "Up to {} changelog{} would be previewed if the --execute is set and --no-changelog-preview is unset.",
@@ -0,0 +1,70 @@
+pub(crate) mod function {
+    use bstr::ByteSlice;
+
+    use super::{Error, Role, Signatures};
+    use crate::signature;
+
+    /// Find and decode the `author` and `committer` lines in the header of the raw commit object in `data`,
+    /// which may also be just the header or only these two lines.
+    ///
+    /// Only the header is searched, which ends at the first empty line, and the first line of each kind is used like `git` does.
+    pub fn commit_signatures(data: &[u8]) -> Result<Signatures<'_>, Error> {
+        let (mut author, mut committer) = (None, None);
+        for line in data.lines() {
+            if line.is_empty() {
+                break;
+            }
+            let (role, slot) = if let Some(value) = line.strip_prefix(b"author ") {
+                (Role::Author, author.is_none().then_some((&mut author, value)))
+            } else if let Some(value) = line.strip_prefix(b"committer ") {
+                (Role::Committer, committer.is_none().then_some((&mut committer, value)))
+            } else {
+                continue;
+            };
+            if let Some((slot, value)) = slot {
+                *slot = Some(signature::decode_exact(value).map_err(|source| Error::Decode { role, source })?);
+            }
+        }
+        Ok(Signatures {
+            author: author.ok_or(Error::Missing { role: Role::Author })?,
+            committer: committer.ok_or(Error::Missing { role: Role::Committer })?,
+        })
+    }
+}
+
+/// The signatures of a commit, as returned by [`commit_signatures()`](crate::commit_signatures()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signatures<'a> {
+    /// The signature of the one who wrote the change.
+    pub author: crate::SignatureRef<'a>,
+    /// The signature of the one who created the commit.
+    pub committer: crate::SignatureRef<'a>,
+}
+
+/// The role of a signature in a commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// The `author` line.
+    Author,
+    /// The `committer` line.
+    Committer,
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Role::Author => "author",
+            Role::Committer => "committer",
+        })
+    }
+}
+
+/// The error returned by [`commit_signatures()`](crate::commit_signatures()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The commit header has no {role} line")]
+    Missing { role: Role },
+    #[error("Could not decode the {role} line of the commit header")]
+    Decode { role: Role, source: crate::decode::Error },
+}
@@ -0,0 +1,73 @@
+pub(crate) mod function {
+    use nom::error::{VerboseError, VerboseErrorKind};
+
+    use super::{Error, Field};
+    use crate::{signature, SignatureRef};
+
+    /// Decode a signature from `data` like `name <email> 1528473343 +0230`, ignoring all data after it.
+    ///
+    /// As opposed to [`signature::decode()`], this doesn't require knowledge of `nom`.
+    pub fn decode(data: &[u8]) -> Result<SignatureRef<'_>, Error> {
+        decode_with_rest(data).map(|(_rest, signature)| signature)
+    }
+
+    /// Like [`decode()`], but also returns the data after the signature.
+    pub(crate) fn decode_with_rest(data: &[u8]) -> Result<(&[u8], SignatureRef<'_>), Error> {
+        signature::decode::<VerboseError<&[u8]>>(data).map_err(|err| Error::Field {
+            field: match err {
+                nom::Err::Error(err) | nom::Err::Failure(err) => failed_field(&err),
+                nom::Err::Incomplete(_) => Field::Timestamp,
+            },
+            input: data.into(),
+        })
+    }
+
+    /// Find the innermost field whose parser failed, or assume the timestamp is missing if only the separator
+    /// after the email couldn't be parsed.
+    fn failed_field(err: &VerboseError<&[u8]>) -> Field {
+        err.errors
+            .iter()
+            .find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context("<name>") => Some(Field::Name),
+                VerboseErrorKind::Context("<email>") => Some(Field::Email),
+                VerboseErrorKind::Context("<timestamp>") => Some(Field::Timestamp),
+                VerboseErrorKind::Context("+|-" | "HH" | "MM") => Some(Field::Offset),
+                _ => None,
+            })
+            .unwrap_or(Field::Timestamp)
+    }
+}
+
+/// The part of a signature that couldn't be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    /// The name, which is terminated by ` <`.
+    Name,
+    /// The email, which is terminated by `>`.
+    Email,
+    /// The seconds since the unix epoch.
+    Timestamp,
+    /// The timezone offset, like `+0230`.
+    Offset,
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Field::Name => "name",
+            Field::Email => "email",
+            Field::Timestamp => "timestamp",
+            Field::Offset => "timezone offset",
+        })
+    }
+}
+
+/// The error returned by [`decode()`](crate::decode()) and [`signature::decode_exact()`](crate::signature::decode_exact()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not parse the {field} of a signature from {input:?}")]
+    Field { field: Field, input: bstr::BString },
+    #[error("Unexpected data after the signature: {trailing:?}")]
+    TrailingData { trailing: bstr::BString },
+}
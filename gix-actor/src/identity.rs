@@ -1,4 +1,4 @@
-use bstr::ByteSlice;
+use bstr::{BStr, ByteSlice};
 
 use crate::{signature::decode, Identity, IdentityRef};
 
@@ -26,6 +26,13 @@ impl<'a> IdentityRef<'a> {
             email: self.email.trim().as_bstr(),
         }
     }
+
+    /// Split the name on whitespace into its tokens, which can be used to obtain initials or to sort by the last token.
+    ///
+    /// Note that there are no assumptions about what the tokens mean, so the last one isn't necessarily a family name.
+    pub fn name_tokens(&self) -> Vec<&'a BStr> {
+        self.name.fields().map(ByteSlice::as_bstr).collect()
+    }
 }
 
 mod write {
@@ -24,6 +24,14 @@ mod identity;
 ///
 pub mod signature;
 
+///
+pub mod decode;
+pub use decode::function::decode;
+
+///
+pub mod commit_signatures;
+pub use commit_signatures::function::commit_signatures;
+
 /// A person with name and email.
 #[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1,29 +1,61 @@
 pub(crate) mod function {
+    use super::{Anomaly, Mode};
+    use crate::{IdentityRef, SignatureRef};
     use bstr::ByteSlice;
     use btoi::btoi;
     use gix_date::{time::Sign, OffsetInSeconds, SecondsSinceUnixEpoch, Time};
-    use nom::multi::many1_count;
     use nom::{
-        branch::alt,
-        bytes::complete::{tag, take, take_until, take_while_m_n},
+        bytes::complete::{tag, take, take_until, take_while1, take_while_m_n},
         character::is_digit,
         error::{context, ContextError, ParseError},
         sequence::{terminated, tuple},
         IResult,
     };
-    use std::cell::RefCell;
-
-    use crate::{IdentityRef, SignatureRef};
 
     const SPACE: &[u8] = b" ";
+    /// 13-digit timestamps, which are between 2001 and 2286 if read as milliseconds, but after the year 33000 as seconds.
+    const MILLISECOND_TIMESTAMPS: std::ops::Range<SecondsSinceUnixEpoch> = 1_000_000_000_000..10_000_000_000_000;
 
     /// Parse a signature from the bytes input `i` using `nom`.
     pub fn decode<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
         i: &'a [u8],
     ) -> IResult<&'a [u8], SignatureRef<'a>, E> {
-        use nom::Parser;
-        let tzsign = RefCell::new(b'-'); // TODO: there should be no need for this.
-        let (i, (identity, _, time, _tzsign_count, hours, minutes)) = context(
+        decode_with_warnings(i).map(|(i, (signature, _anomalies))| (i, signature))
+    }
+
+    /// Parse a signature from the bytes input `i`, which must not contain anything but the signature itself
+    /// and an optional trailing newline.
+    ///
+    /// This is the inverse of [`SignatureRef::to_bstring()`], see there for the signatures that round-trip losslessly.
+    pub fn decode_exact(i: &[u8]) -> Result<SignatureRef<'_>, crate::decode::Error> {
+        let (rest, signature) = crate::decode::function::decode_with_rest(i)?;
+        if !rest.is_empty() && rest != b"\n" {
+            return Err(crate::decode::Error::TrailingData { trailing: rest.into() });
+        }
+        Ok(signature)
+    }
+
+    /// Parse a signature from the bytes input `i` using `nom` like [`decode()`], but interpret timestamps which are
+    /// [obviously given in milliseconds](Anomaly::MillisecondTimestamp) as such and convert them to seconds.
+    ///
+    /// This is opt-in as it changes the meaning of otherwise valid, if unusually large, timestamps.
+    pub fn decode_with_millisecond_timestamps<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        i: &'a [u8],
+    ) -> IResult<&'a [u8], SignatureRef<'a>, E> {
+        decode_with_warnings(i).map(|(i, (mut signature, anomalies))| {
+            if anomalies.contains(&Anomaly::MillisecondTimestamp) {
+                signature.time.seconds /= 1000;
+            }
+            (i, signature)
+        })
+    }
+
+    /// Parse a signature from the bytes input `i` using `nom`, along with all [anomalies](Anomaly) that were tolerated
+    /// while doing so.
+    pub fn decode_with_warnings<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        i: &'a [u8],
+    ) -> IResult<&'a [u8], (SignatureRef<'a>, Vec<Anomaly>), E> {
+        let (i, (identity, _, time, (tzsign, tzsign_count), hours, (minutes, minutes_len))) = context(
             "<name> <<email>> <timestamp> <+|-><HHMM>",
             tuple((
                 identity,
@@ -35,13 +67,7 @@ pub(crate) mod function {
                             .map_err(|_| nom::Err::Error(E::from_error_kind(i, nom::error::ErrorKind::MapRes)))
                     })
                 }),
-                context(
-                    "+|-",
-                    alt((
-                        many1_count(tag(b"-")).map(|_| *tzsign.borrow_mut() = b'-'), // TODO: this should be a non-allocating consumer of consecutive tags
-                        many1_count(tag(b"+")).map(|_| *tzsign.borrow_mut() = b'+'),
-                    )),
-                ),
+                context("+|-", timezone_sign),
                 context("HH", |i| {
                     take_while_m_n(2usize, 2, is_digit)(i).and_then(|(i, v)| {
                         btoi::<OffsetInSeconds>(v)
@@ -52,32 +78,165 @@ pub(crate) mod function {
                 context("MM", |i| {
                     take_while_m_n(1usize, 2, is_digit)(i).and_then(|(i, v)| {
                         btoi::<OffsetInSeconds>(v)
-                            .map(|v| (i, v))
+                            .map(|n| (i, (n, v.len())))
                             .map_err(|_| nom::Err::Error(E::from_error_kind(i, nom::error::ErrorKind::MapRes)))
                     })
                 }),
             )),
         )(i)?;
 
-        let tzsign = tzsign.into_inner();
-        debug_assert!(tzsign == b'-' || tzsign == b'+', "parser assure it's +|- only");
-        let sign = if tzsign == b'-' { Sign::Minus } else { Sign::Plus }; //
+        let sign = if tzsign == b'-' { Sign::Minus } else { Sign::Plus };
         let offset = (hours * 3600 + minutes * 60) * if sign == Sign::Minus { -1 } else { 1 };
 
+        let mut anomalies = Vec::new();
+        if tzsign_count > 1 {
+            anomalies.push(match sign {
+                Sign::Minus => Anomaly::DoubleDashSign,
+                Sign::Plus => Anomaly::DoublePlusSign,
+            });
+        }
+        if minutes_len < 2 {
+            anomalies.push(Anomaly::SingleDigitMinutes);
+        }
+        if MILLISECOND_TIMESTAMPS.contains(&time) {
+            anomalies.push(Anomaly::MillisecondTimestamp);
+        }
+
         Ok((
             i,
-            SignatureRef {
-                name: identity.name,
-                email: identity.email,
-                time: Time {
-                    seconds: time,
-                    offset,
-                    sign,
+            (
+                SignatureRef {
+                    name: identity.name,
+                    email: identity.email,
+                    time: Time {
+                        seconds: time,
+                        offset,
+                        sign,
+                    },
                 },
-            },
+                anomalies,
+            ),
         ))
     }
 
+    /// Parse a signature from the bytes input `i` using `nom` with the tolerances of `mode`, along with all
+    /// [anomalies](Anomaly) that were tolerated while doing so.
+    ///
+    /// With [`Mode::Strict`], this is exactly [`decode_with_warnings()`].
+    pub fn decode_with_mode<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        i: &'a [u8],
+        mode: Mode,
+    ) -> IResult<&'a [u8], (SignatureRef<'a>, Vec<Anomaly>), E> {
+        match mode {
+            Mode::Strict => decode_with_warnings(i),
+            Mode::Lenient => {
+                let (i, (mut signature, anomalies)) = match decode_with_warnings::<E>(i) {
+                    Ok(res) => res,
+                    Err(err) => match decode_with_unusual_offset::<E>(i) {
+                        Ok(res) => res,
+                        Err(_) => return Err(err),
+                    },
+                };
+                if anomalies.contains(&Anomaly::MillisecondTimestamp) {
+                    signature.time.seconds /= 1000;
+                }
+                Ok((i, (signature, anomalies)))
+            }
+        }
+    }
+
+    /// Parse signatures that [`decode_with_warnings()`] rejects for their timezone offset, which is either missing
+    /// at the end of the input or line, has a colon between hours and minutes, like `+02:30`, or is a named zone like `GMT`.
+    fn decode_with_unusual_offset<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        i: &'a [u8],
+    ) -> IResult<&'a [u8], (SignatureRef<'a>, Vec<Anomaly>), E> {
+        let map_err = |_| nom::Err::Error(E::from_error_kind(i, nom::error::ErrorKind::MapRes));
+        let (i, (identity, _, time)) = tuple((identity, tag(b" "), take_while1(is_digit)))(i)?;
+        let time = btoi::<SecondsSinceUnixEpoch>(time).map_err(map_err)?;
+
+        let mut anomalies = Vec::new();
+        let (i, sign, offset) = if i.is_empty() || i[0] == b'\n' {
+            anomalies.push(Anomaly::MissingOffset);
+            (i, Sign::Plus, 0)
+        } else if i.len() > 1 && i[0] == b' ' && i[1].is_ascii_alphabetic() {
+            let (rest, zone) = take_while1(|b: u8| b.is_ascii_alphabetic())(&i[1..])?;
+            let offset = named_timezone_offset(zone)
+                .ok_or_else(|| nom::Err::Error(E::from_error_kind(i, nom::error::ErrorKind::Verify)))?;
+            anomalies.push(Anomaly::NamedTimezone);
+            (rest, if offset < 0 { Sign::Minus } else { Sign::Plus }, offset)
+        } else {
+            let (i, (_, (tzsign, tzsign_count), hours, _, minutes)) = tuple((
+                tag(b" "),
+                timezone_sign,
+                take_while_m_n(2usize, 2, is_digit),
+                tag(b":"),
+                take_while_m_n(2usize, 2, is_digit),
+            ))(i)?;
+            let hours = btoi::<OffsetInSeconds>(hours).map_err(map_err)?;
+            let minutes = btoi::<OffsetInSeconds>(minutes).map_err(map_err)?;
+            let sign = if tzsign == b'-' { Sign::Minus } else { Sign::Plus };
+            if tzsign_count > 1 {
+                anomalies.push(match sign {
+                    Sign::Minus => Anomaly::DoubleDashSign,
+                    Sign::Plus => Anomaly::DoublePlusSign,
+                });
+            }
+            anomalies.push(Anomaly::ColonInOffset);
+            let offset = (hours * 3600 + minutes * 60) * if sign == Sign::Minus { -1 } else { 1 };
+            (i, sign, offset)
+        };
+        if MILLISECOND_TIMESTAMPS.contains(&time) {
+            anomalies.push(Anomaly::MillisecondTimestamp);
+        }
+
+        Ok((
+            i,
+            (
+                SignatureRef {
+                    name: identity.name,
+                    email: identity.email,
+                    time: Time {
+                        seconds: time,
+                        offset,
+                        sign,
+                    },
+                },
+                anomalies,
+            ),
+        ))
+    }
+
+    /// Return the offset of the timezone abbreviation `zone`, for the zones that RFC 2822 defines as obsolete
+    /// alternatives to numeric offsets, or `None` if it is unknown.
+    ///
+    /// Other abbreviations aren't supported as they are ambiguous, like `CST` which is also used for China Standard Time.
+    fn named_timezone_offset(zone: &[u8]) -> Option<OffsetInSeconds> {
+        let hours = match zone {
+            b"UT" | b"UTC" | b"GMT" | b"Z" => 0,
+            b"EDT" => -4,
+            b"EST" | b"CDT" => -5,
+            b"CST" | b"MDT" => -6,
+            b"MST" | b"PDT" => -7,
+            b"PST" => -8,
+            _ => return None,
+        };
+        Some(hours * 3600)
+    }
+
+    /// Parse the sign of the timezone offset, returning the sign character along with the amount of times it was given.
+    ///
+    /// The sign is a single `-` or `+`. Repeating it, like in `--0700` or `---0700`, is malformed but tolerated as git
+    /// produced such signatures, and the repetitions don't change its meaning. Mixing signs like in `+-0700` is rejected
+    /// as it's unclear which one is meant.
+    fn timezone_sign<'a, E: ParseError<&'a [u8]>>(i: &'a [u8]) -> IResult<&'a [u8], (u8, usize), E> {
+        let (rest, signs) = take_while1(|b| b == b'-' || b == b'+')(i)?;
+        let sign = signs[0];
+        if signs.iter().any(|b| *b != sign) {
+            return Err(nom::Err::Error(E::from_error_kind(i, nom::error::ErrorKind::Verify)));
+        }
+        Ok((rest, (sign, signs.len())))
+    }
+
     /// Parse an identity from the bytes input `i` (like `name <email>`) using `nom`.
     pub fn identity<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
         i: &'a [u8],
@@ -101,6 +260,62 @@ pub(crate) mod function {
 }
 pub use function::identity;
 
+/// An irregularity in a signature that was tolerated when decoding it with [`decode_with_warnings()`](function::decode_with_warnings()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Anomaly {
+    /// The `-` sign of the timezone offset was repeated, like in `--0700`.
+    DoubleDashSign,
+    /// The `+` sign of the timezone offset was repeated, like in `++0700`.
+    DoublePlusSign,
+    /// The minutes of the timezone offset were given with only one digit, like in `-070` which is read as 70 hours and 0 minutes.
+    SingleDigitMinutes,
+    /// The timestamp has 13 digits and is thus most likely given in milliseconds, like `1528473343123`, a format
+    /// used by some foreign tools.
+    ///
+    /// Use [`decode_with_millisecond_timestamps()`](function::decode_with_millisecond_timestamps()) to convert it to seconds.
+    MillisecondTimestamp,
+    /// The timezone offset is missing, like in `name <email> 1528473343`, and `+0000` is assumed.
+    ///
+    /// Only tolerated in [`Mode::Lenient`].
+    MissingOffset,
+    /// The hours and minutes of the timezone offset are separated by a colon, like in `+02:30`.
+    ///
+    /// Only tolerated in [`Mode::Lenient`].
+    ColonInOffset,
+    /// The timezone is given by its name instead of an offset, like `GMT` or `PST`, which is converted to the corresponding offset.
+    ///
+    /// Only tolerated in [`Mode::Lenient`], and only for the zones that RFC 2822 knows, which are `UT`, `UTC`, `GMT`, `Z`,
+    /// and the North American `EST`, `EDT`, `CST`, `CDT`, `MST`, `MDT`, `PST` and `PDT`.
+    NamedTimezone,
+}
+
+/// Determine how strictly [`decode_with_mode()`](function::decode_with_mode()) decodes signatures.
+///
+/// All tolerated irregularities are reported as [`Anomaly`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Decode signatures as they are written by `git`, which is what [`decode()`](function::decode()) does
+    /// and the right choice for reading objects.
+    ///
+    /// It tolerates only what `git` itself produced at some point:
+    ///
+    /// * a [repeated sign](Anomaly::DoubleDashSign) of the timezone offset, like `--0700`,
+    /// * [single-digit minutes](Anomaly::SingleDigitMinutes) of the timezone offset, like `-070`.
+    ///
+    /// [Millisecond timestamps](Anomaly::MillisecondTimestamp) are reported, but taken as seconds.
+    #[default]
+    Strict,
+    /// Decode signatures produced by foreign tools as well, which is useful when importing or migrating data.
+    ///
+    /// In addition to what [`Strict`](Mode::Strict) tolerates, it
+    ///
+    /// * converts [millisecond timestamps](Anomaly::MillisecondTimestamp) to seconds,
+    /// * assumes `+0000` if the [timezone offset is missing](Anomaly::MissingOffset) at the end of the input or line,
+    /// * accepts a [colon in the timezone offset](Anomaly::ColonInOffset), like `+02:30`,
+    /// * converts [well-known timezone names](Anomaly::NamedTimezone) like `GMT` to their offset.
+    Lenient,
+}
+
 #[cfg(test)]
 mod tests {
     mod parse_signature {
@@ -109,7 +324,7 @@ mod tests {
         use gix_testtools::to_bstr_err;
         use nom::IResult;
 
-        use crate::{signature, SignatureRef, Time};
+        use crate::{signature, signature::decode::Anomaly, SignatureRef, Time};
 
         fn decode(i: &[u8]) -> IResult<&[u8], SignatureRef<'_>, nom::error::VerboseError<&[u8]>> {
             signature::decode(i)
@@ -169,6 +384,194 @@ mod tests {
             );
         }
 
+        #[test]
+        fn negative_offset_double_dash_is_reported_as_anomaly() {
+            let (_, (actual, anomalies)) = signature::decode_with_warnings::<nom::error::VerboseError<&[u8]>>(
+                b"name <name@example.com> 1288373970 --700",
+            )
+            .expect("parse to work");
+            assert_eq!(
+                actual,
+                signature("name", "name@example.com", 1288373970, Sign::Minus, -252000)
+            );
+            assert_eq!(
+                anomalies,
+                [Anomaly::DoubleDashSign, Anomaly::SingleDigitMinutes],
+                "hours are 70, the single 0 is read as minutes"
+            );
+
+            let (_, (_, anomalies)) =
+                signature::decode_with_warnings::<()>(b"name <name@example.com> 1288373970 -0700")
+                    .expect("parse to work");
+            assert!(anomalies.is_empty(), "well-formed signatures have no anomalies");
+        }
+
+        #[test]
+        fn millisecond_timestamps_are_converted_only_on_request() {
+            let input = b"name <name@example.com> 1528473343123 +0000";
+            assert_eq!(
+                decode(input).expect("parse to work").1,
+                signature("name", "name@example.com", 1528473343123, Sign::Plus, 0),
+                "by default, the timestamp is taken as is"
+            );
+            let (_, (_, anomalies)) = signature::decode_with_warnings::<()>(input).expect("parse to work");
+            assert_eq!(anomalies, [Anomaly::MillisecondTimestamp]);
+
+            assert_eq!(
+                signature::decode_with_millisecond_timestamps::<()>(input)
+                    .expect("parse to work")
+                    .1,
+                signature("name", "name@example.com", 1528473343, Sign::Plus, 0),
+                "the milliseconds are dropped"
+            );
+
+            for seconds in ["1528473343", "99999999999", "10000000000000"] {
+                let input = format!("name <name@example.com> {seconds} +0000");
+                assert_eq!(
+                    signature::decode_with_millisecond_timestamps::<()>(input.as_bytes())
+                        .expect("parse to work")
+                        .1
+                        .time
+                        .seconds
+                        .to_string(),
+                    seconds,
+                    "timestamps that don't have 13 digits are kept as seconds"
+                );
+            }
+        }
+
+        #[test]
+        fn strict_mode_is_the_default_decoding() {
+            for input in [
+                &b"name <name@example.com> 1288373970 --700"[..],
+                b"name <name@example.com> 1528473343123 +0000",
+                b"name <name@example.com> 1288373970 -0700\nrest",
+            ] {
+                assert_eq!(
+                    signature::decode_with_mode::<()>(input, signature::decode::Mode::Strict),
+                    signature::decode_with_warnings::<()>(input),
+                    "{input:?}"
+                );
+            }
+            for input in [
+                &b"name <name@example.com> 1288373970"[..],
+                b"name <name@example.com> 1288373970 +02:30",
+                b"name <name@example.com> 1288373970 GMT",
+            ] {
+                assert!(signature::decode_with_mode::<()>(input, signature::decode::Mode::Strict).is_err());
+            }
+        }
+
+        #[test]
+        fn lenient_mode_tolerates_missing_and_colon_offsets_and_converts_milliseconds() {
+            use signature::decode::Mode::Lenient;
+            let (rest, (actual, anomalies)) =
+                signature::decode_with_mode::<()>(b"name <name@example.com> 1288373970\nrest", Lenient)
+                    .expect("parse to work");
+            assert_eq!(actual, signature("name", "name@example.com", 1288373970, Sign::Plus, 0));
+            assert_eq!(anomalies, [Anomaly::MissingOffset]);
+            assert_eq!(rest, b"\nrest", "the line ends where the offset should have been");
+
+            let (rest, (actual, anomalies)) =
+                signature::decode_with_mode::<()>(b"name <name@example.com> 1288373970 --02:30", Lenient)
+                    .expect("parse to work");
+            assert_eq!(
+                actual,
+                signature("name", "name@example.com", 1288373970, Sign::Minus, -9000)
+            );
+            assert_eq!(anomalies, [Anomaly::DoubleDashSign, Anomaly::ColonInOffset]);
+            assert!(rest.is_empty());
+
+            let (_, (actual, anomalies)) =
+                signature::decode_with_mode::<()>(b"name <name@example.com> 1528473343123", Lenient)
+                    .expect("parse to work");
+            assert_eq!(actual, signature("name", "name@example.com", 1528473343, Sign::Plus, 0));
+            assert_eq!(anomalies, [Anomaly::MissingOffset, Anomaly::MillisecondTimestamp]);
+
+            let (_, (actual, anomalies)) =
+                signature::decode_with_mode::<()>(b"name <name@example.com> 1288373970 +0230", Lenient)
+                    .expect("parse to work");
+            assert_eq!(
+                actual,
+                signature("name", "name@example.com", 1288373970, Sign::Plus, 9000)
+            );
+            assert!(anomalies.is_empty(), "well-formed signatures are decoded as usual");
+
+            for input in [
+                &b"name <name@example.com> 1288373970 +2:30"[..],
+                b"name <name@example.com> 1288373970 abc",
+                b"name <name@example.com>",
+            ] {
+                assert!(
+                    signature::decode_with_mode::<()>(input, Lenient).is_err(),
+                    "{input:?} is still malformed"
+                );
+            }
+        }
+
+        #[test]
+        fn lenient_mode_converts_known_timezone_names() {
+            use signature::decode::Mode::Lenient;
+            let (rest, (actual, anomalies)) =
+                signature::decode_with_mode::<()>(b"name <name@example.com> 1528473343 GMT\nrest", Lenient)
+                    .expect("parse to work");
+            assert_eq!(actual, signature("name", "name@example.com", 1528473343, Sign::Plus, 0));
+            assert_eq!(anomalies, [Anomaly::NamedTimezone]);
+            assert_eq!(rest, b"\nrest");
+
+            let (_, (actual, anomalies)) =
+                signature::decode_with_mode::<()>(b"name <name@example.com> 1528473343 PST", Lenient)
+                    .expect("parse to work");
+            assert_eq!(
+                actual,
+                signature("name", "name@example.com", 1528473343, Sign::Minus, -28800)
+            );
+            assert_eq!(anomalies, [Anomaly::NamedTimezone]);
+
+            for input in [
+                &b"name <name@example.com> 1528473343 XYZ"[..],
+                b"name <name@example.com> 1528473343 gmt",
+            ] {
+                assert!(
+                    signature::decode_with_mode::<()>(input, Lenient).is_err(),
+                    "{input:?}: unknown zones are rejected"
+                );
+            }
+        }
+
+        #[test]
+        fn repeated_negative_signs_are_a_single_sign() {
+            let (_, (actual, anomalies)) = signature::decode_with_warnings::<nom::error::VerboseError<&[u8]>>(
+                b"name <name@example.com> 1288373970 ---0700",
+            )
+            .expect("parse to work");
+            assert_eq!(
+                actual,
+                signature("name", "name@example.com", 1288373970, Sign::Minus, -25200)
+            );
+            assert_eq!(anomalies, [Anomaly::DoubleDashSign]);
+        }
+
+        #[test]
+        fn mixed_signs_are_rejected() {
+            for input in [
+                &b"name <name@example.com> 1288373970 +-0700"[..],
+                b"name <name@example.com> 1288373970 -+0700",
+                b"name <name@example.com> 1288373970 --+0700",
+            ] {
+                assert!(
+                    matches!(
+                        crate::decode(input).expect_err("the sign is ambiguous"),
+                        crate::decode::Error::Field {
+                            field: crate::decode::Field::Offset,
+                            ..
+                        }
+                    ),
+                    "{input:?}"
+                );
+            }
+        }
+
         #[test]
         fn empty_name_and_email() {
             assert_eq!(
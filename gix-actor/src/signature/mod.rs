@@ -22,6 +22,9 @@ mod _ref {
         }
 
         /// Trim whitespace surrounding the name and email and return a new signature.
+        ///
+        /// Whitespace within the name is kept, and a name that consists only of whitespace becomes empty,
+        /// similar to how `git` presents identities. The decoder itself keeps all whitespace to allow lossless round-trips.
         pub fn trim(&self) -> SignatureRef<'a> {
             SignatureRef {
                 name: self.name.trim().as_bstr(),
@@ -73,7 +76,7 @@ mod convert {
 }
 
 pub(crate) mod write {
-    use bstr::{BStr, ByteSlice};
+    use bstr::{BStr, BString, ByteSlice};
 
     use crate::{Signature, SignatureRef};
 
@@ -97,6 +100,12 @@ pub(crate) mod write {
         pub fn write_to(&self, out: impl std::io::Write) -> std::io::Result<()> {
             self.to_ref().write_to(out)
         }
+        /// Serialize this instance into a newly allocated string in the git serialization format for actors.
+        ///
+        /// See [`SignatureRef::to_bstring()`] for the signatures that round-trip through [`decode_exact()`](crate::signature::decode_exact()).
+        pub fn to_bstring(&self) -> std::io::Result<BString> {
+            self.to_ref().to_bstring()
+        }
         /// Computes the number of bytes necessary to serialize this signature
         pub fn size(&self) -> usize {
             self.to_ref().size()
@@ -113,6 +122,22 @@ pub(crate) mod write {
             out.write_all(b"> ")?;
             self.time.write_to(out)
         }
+        /// Serialize this instance into a newly allocated string in the git serialization format for actors,
+        /// or fail if name or email contain characters that can't be represented.
+        ///
+        /// [`decode_exact()`](crate::signature::decode_exact()) is the inverse of this method and yields the original
+        /// signature for every valid one, which is a signature whose
+        ///
+        /// * name and email don't contain `<`, `>` or `\n`, but may be empty,
+        /// * timezone offset is given in whole minutes and is less than 25 hours, as writing larger offsets panics,
+        /// * sign is [`Minus`](gix_date::time::Sign::Minus) if the offset is negative. A zero offset may have either sign.
+        ///
+        /// All timestamps are valid.
+        pub fn to_bstring(&self) -> std::io::Result<BString> {
+            let mut buf = Vec::with_capacity(self.size());
+            self.write_to(&mut buf)?;
+            Ok(buf.into())
+        }
         /// Computes the number of bytes necessary to serialize this signature
         pub fn size(&self) -> usize {
             self.name.len() + 2 /* space <*/ + self.email.len() +  2 /* > space */ + self.time.size()
@@ -129,4 +154,10 @@ pub(crate) mod write {
 
 ///
 pub mod decode;
-pub use decode::function::decode;
+pub use decode::function::{
+    decode, decode_exact, decode_with_millisecond_timestamps, decode_with_mode, decode_with_warnings,
+};
+
+///
+pub mod validate;
+pub use validate::function::validate_canonical;
@@ -0,0 +1,118 @@
+pub(crate) mod function {
+    use bstr::ByteSlice;
+    use nom::error::VerboseError;
+
+    use super::Issue;
+    use crate::signature::{decode::Anomaly, decode_with_warnings};
+
+    /// Check that `data` holds a signature like `name <email> 1528473343 +0230` in the canonical form `git` writes,
+    /// along with an optional trailing newline, and return all [issues](Issue) found otherwise.
+    ///
+    /// This is stricter than [`decode()`](crate::signature::decode()), which tolerates deviations like
+    /// [whitespace around the name](Issue::NameWithTrailingWhitespace) or [malformed offsets](Issue::Anomaly),
+    /// and is meant for `fsck`-like validation.
+    ///
+    /// Note that [timestamps in milliseconds](Anomaly::MillisecondTimestamp) are not reported as they are written
+    /// in the canonical form, even though their precision is unusual.
+    pub fn validate_canonical(data: &[u8]) -> Result<(), Vec<Issue>> {
+        let mut issues = Vec::new();
+        match decode_with_warnings::<VerboseError<&[u8]>>(data) {
+            Ok((rest, (signature, anomalies))) => {
+                if signature.name.first().map_or(false, u8::is_ascii_whitespace) {
+                    issues.push(Issue::NameWithLeadingWhitespace);
+                }
+                if signature.name.last().map_or(false, u8::is_ascii_whitespace) {
+                    issues.push(Issue::NameWithTrailingWhitespace);
+                }
+                let timestamp_start = signature.name.len() + 2 /* space < */ + signature.email.len() + 2 /* > space */;
+                let timestamp = data[timestamp_start..]
+                    .split_str(" ")
+                    .next()
+                    .expect("the timestamp was parsed");
+                if timestamp.len() > 1 && timestamp[0] == b'0' {
+                    issues.push(Issue::ZeroPaddedTimestamp);
+                }
+                if !timestamp.iter().all(u8::is_ascii_digit) {
+                    issues.push(Issue::SignedTimestamp);
+                }
+                issues.extend(
+                    anomalies
+                        .into_iter()
+                        .filter(|anomaly| *anomaly != Anomaly::MillisecondTimestamp)
+                        .map(Issue::Anomaly),
+                );
+                if !rest.is_empty() && rest != b"\n" {
+                    issues.push(Issue::TrailingData);
+                }
+            }
+            Err(_) => {
+                missing_separators(data, &mut issues);
+                if issues.is_empty() {
+                    if let Err(crate::decode::Error::Field { field, .. }) = crate::decode(data) {
+                        issues.push(Issue::Malformed { field });
+                    }
+                }
+            }
+        }
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Find the spaces that are missing between the fields of the signature in `data`, which `decode()` can't recover from.
+    fn missing_separators(data: &[u8], issues: &mut Vec<Issue>) {
+        let Some(email_start) = data.find_byte(b'<') else {
+            return;
+        };
+        if email_start == 0 || data[email_start - 1] != b' ' {
+            issues.push(Issue::MissingSpaceBeforeEmail);
+        }
+        let Some(email_end) = data[email_start..].find_byte(b'>').map(|pos| email_start + pos) else {
+            return;
+        };
+        let timestamp = &data[email_end + 1..];
+        let timestamp = match timestamp.strip_prefix(b" ") {
+            Some(timestamp) => timestamp,
+            None => {
+                issues.push(Issue::MissingSpaceBeforeTimestamp);
+                timestamp
+            }
+        };
+        let digits = timestamp.iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits > 0 && matches!(timestamp.get(digits), Some(b'+' | b'-')) {
+            issues.push(Issue::MissingSpaceBeforeOffset);
+        }
+    }
+}
+
+/// A deviation from the canonical form of a signature as found by [`validate_canonical()`](function::validate_canonical()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Issue {
+    /// The signature couldn't be decoded at all as the given `field` is invalid.
+    Malformed {
+        /// The field that couldn't be decoded.
+        field: crate::decode::Field,
+    },
+    /// There is no space between the name and the `<` opening the email, like in `name<email>`.
+    MissingSpaceBeforeEmail,
+    /// There is no space between the `>` closing the email and the timestamp, like in `<email>1528473343`.
+    MissingSpaceBeforeTimestamp,
+    /// There is no space between the timestamp and the timezone offset, like in `1528473343+0230`.
+    MissingSpaceBeforeOffset,
+    /// The name starts with whitespace, like in ` name <email>`.
+    NameWithLeadingWhitespace,
+    /// The name ends with whitespace, like in `name  <email>`.
+    NameWithTrailingWhitespace,
+    /// The timestamp has leading zeroes, like `01528473343`.
+    ZeroPaddedTimestamp,
+    /// The timestamp starts with a `+` or `-` sign.
+    SignedTimestamp,
+    /// The timezone offset is malformed in a way that is tolerated when decoding, like `+230` instead of `+0230`.
+    ///
+    /// This is never [`Anomaly::MillisecondTimestamp`](super::decode::Anomaly::MillisecondTimestamp), which doesn't affect the offset.
+    Anomaly(super::decode::Anomaly),
+    /// There is data after the signature other than a single newline.
+    TrailingData,
+}
@@ -4,5 +4,6 @@ pub fn fixture(path: &str) -> PathBuf {
     PathBuf::from("tests/fixtures").join(path)
 }
 
+mod commit_signatures;
 mod identity;
 mod signature;
@@ -0,0 +1,79 @@
+use gix_actor::{
+    commit_signatures,
+    commit_signatures::{Error, Role},
+};
+
+const COMMIT: &[u8] = b"tree 9f4fd6f2d3d3b4c8c5ef1d4a1dd0bde4e1f0e7a2
+parent 5b86da5bd7f4c1de5d0ce3d5bd1aa4f8c06e3bfa
+author Sebastian Thiel <sebastian.thiel@icloud.com> 1688289487 +0200
+committer GitHub <noreply@github.com> 1688289537 +0200
+gpgsig -----BEGIN PGP SIGNATURE-----
+ 
+ wsBcBAABCAAQBQJkoJkBCRBK7hj4Ov3rIwAAqEcIAHwPw8nYh+bQmm6qTxRwFqVI
+ -----END PGP SIGNATURE-----
+
+Merge pull request #1 from branch
+
+author Someone Else <else@example.com> 0 +0000
+";
+
+#[test]
+fn author_and_committer_are_found_in_the_header() {
+    let signatures = commit_signatures(COMMIT).unwrap();
+    assert_eq!(signatures.author.name, "Sebastian Thiel");
+    assert_eq!(signatures.author.email, "sebastian.thiel@icloud.com");
+    assert_eq!(signatures.author.time.seconds, 1688289487);
+    assert_eq!(signatures.author.time.offset, 7200);
+    assert_eq!(signatures.committer.name, "GitHub");
+    assert_eq!(signatures.committer.email, "noreply@github.com");
+    assert_eq!(signatures.committer.time.seconds, 1688289537);
+}
+
+#[test]
+fn adjacent_lines_suffice() {
+    let signatures =
+        commit_signatures(b"author a <a@example.com> 1 +0000\ncommitter c <c@example.com> 2 -0130").unwrap();
+    assert_eq!(signatures.author.name, "a");
+    assert_eq!(signatures.committer.name, "c");
+    assert_eq!(signatures.committer.time.offset, -5400);
+}
+
+#[test]
+fn the_first_line_of_each_kind_is_used() {
+    let signatures = commit_signatures(
+        b"committer c <c@example.com> 2 +0000\nauthor a <a@example.com> 1 +0000\nauthor b <b@example.com> 1 +0000\n",
+    )
+    .unwrap();
+    assert_eq!(signatures.author.name, "a");
+    assert_eq!(signatures.committer.name, "c", "the order of lines doesn't matter");
+}
+
+#[test]
+fn missing_lines_are_named_in_the_error() {
+    let err = commit_signatures(b"tree abc\nauthor a <a@example.com> 1 +0000\n\ncommitter c <c@example.com> 2 +0000\n")
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::Missing { role: Role::Committer }),
+        "lines in the message aren't considered"
+    );
+    assert_eq!(err.to_string(), "The commit header has no committer line");
+
+    let err = commit_signatures(b"committer c <c@example.com> 2 +0000\n").unwrap_err();
+    assert_eq!(err.to_string(), "The commit header has no author line");
+}
+
+#[test]
+fn malformed_lines_are_named_in_the_error() {
+    let err = commit_signatures(b"author a <a@example.com> 1 +0000\ncommitter c <c@example.com>\n").unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Decode {
+            role: Role::Committer,
+            ..
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "Could not decode the committer line of the commit header"
+    );
+}
@@ -16,3 +16,17 @@ fn round_trip() -> gix_testtools::Result {
     }
     Ok(())
 }
+
+#[test]
+fn name_tokens() -> gix_testtools::Result {
+    let identity = gix_actor::IdentityRef::from_bytes::<()>(b" Sebastian  \t Thiel-Mayer Jr. <byronimo@gmail.com>")?;
+    assert_eq!(
+        identity.name_tokens(),
+        ["Sebastian", "Thiel-Mayer", "Jr."],
+        "all whitespace separates tokens, and there are no empty ones"
+    );
+
+    let identity = gix_actor::IdentityRef::from_bytes::<()>(b"single <a@b>")?;
+    assert_eq!(identity.name_tokens(), ["single"]);
+    Ok(())
+}
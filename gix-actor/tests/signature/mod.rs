@@ -63,6 +63,34 @@ fn trim() {
     assert_eq!(sig.email, "email");
 }
 
+#[test]
+fn trim_only_affects_surrounding_whitespace_of_the_name() {
+    for (input, expected_name) in [
+        (&b"  Name  <e> 1 -0030"[..], "Name"),
+        (b"Name 	 <e> 1 -0030", "Name"),
+        (b"	First  	 Last  <e> 1 -0030", "First  	 Last"),
+        (b"Name <e> 1 -0030", "Name"),
+    ] {
+        let sig = gix_actor::SignatureRef::from_bytes::<()>(input).unwrap();
+        assert_eq!(
+            sig.trim().name,
+            expected_name,
+            "internal whitespace is kept like git does when displaying names"
+        );
+    }
+}
+
+#[test]
+fn trim_keeps_empty_names_empty() {
+    for input in [&b" <e> 1 -0030"[..], b"   <e> 1 -0030"] {
+        let sig = gix_actor::SignatureRef::from_bytes::<()>(input).unwrap();
+        let trimmed = sig.trim();
+        assert_eq!(trimmed.name, "", "names consisting of whitespace only become empty");
+        assert_eq!(trimmed.email, "e");
+        assert_eq!(trimmed.time, sig.time);
+    }
+}
+
 #[test]
 fn round_trip() -> Result<(), Box<dyn std::error::Error>> {
     static DEFAULTS: &[&[u8]] =     &[
@@ -80,3 +108,225 @@ fn round_trip() -> Result<(), Box<dyn std::error::Error>> {
     }
     Ok(())
 }
+
+#[test]
+fn to_bstring() -> Result<(), Box<dyn std::error::Error>> {
+    let input = b"Sebastian Thiel <byronimo@gmail.com> 1528473343 +0230";
+    let signature = gix_actor::SignatureRef::from_bytes::<()>(input)?;
+    assert_eq!(signature.to_bstring()?, input.as_bstr());
+    assert_eq!(
+        signature.to_owned().to_bstring()?,
+        input.as_bstr(),
+        "owned signatures encode the same way"
+    );
+
+    let invalid = Signature {
+        name: "invalid < name".into(),
+        ..signature.to_owned()
+    };
+    assert!(
+        invalid.to_bstring().is_err(),
+        "validation happens just like when writing"
+    );
+    Ok(())
+}
+
+#[test]
+fn decode_exact() {
+    let input = b"Sebastian Thiel <byronimo@gmail.com> 1528473343 +0230";
+    let expected = gix_actor::SignatureRef::from_bytes::<()>(input).unwrap();
+    assert_eq!(gix_actor::signature::decode_exact(input).unwrap(), expected);
+    assert_eq!(
+        gix_actor::signature::decode_exact(b"Sebastian Thiel <byronimo@gmail.com> 1528473343 +0230\n").unwrap(),
+        expected,
+        "a single trailing newline is tolerated"
+    );
+
+    for (input, expected_err) in [
+        (
+            &b"Sebastian Thiel <byronimo@gmail.com> 1528473343 +0230 garbage"[..],
+            "Unexpected data after the signature: \" garbage\"",
+        ),
+        (
+            b"Sebastian Thiel <byronimo@gmail.com> 1528473343 +0230\n\n",
+            "Unexpected data after the signature: \"\\n\\n\"",
+        ),
+        (
+            b"hello < 12345 -1215",
+            "Could not parse the email of a signature from \"hello < 12345 -1215\"",
+        ),
+    ] {
+        assert_eq!(
+            gix_actor::signature::decode_exact(input).unwrap_err().to_string(),
+            expected_err
+        );
+    }
+}
+
+#[test]
+fn generated_signatures_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    use gix_date::{time::Sign, Time};
+
+    /// A xorshift generator, which is enough to produce many different signatures deterministically.
+    struct Rng(u64);
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+        fn below(&mut self, max: u64) -> u64 {
+            self.next() % max
+        }
+        fn token(&mut self) -> Vec<u8> {
+            let len = [0, 1, 2, 10, 100][self.below(5) as usize];
+            (0..len)
+                .map(|_| self.below(256) as u8)
+                .filter(|b| !b"<>\n".contains(b))
+                .collect()
+        }
+    }
+
+    let max_offset = 24 * 3600 + 59 * 60;
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for round in 0..5000 {
+        let seconds = match round % 4 {
+            0 => rng.next() as i64,
+            1 => [0, 1, i64::MAX, i64::MIN + 1, 1_528_473_343_123][rng.below(5) as usize],
+            _ => rng.below(10_000_000_000) as i64,
+        };
+        let offset = match round % 3 {
+            0 => [0, max_offset, -max_offset][rng.below(3) as usize],
+            _ => (rng.below(2 * max_offset as u64 / 60 + 1) as i32 - max_offset / 60) * 60,
+        };
+        let sign = if offset < 0 || (offset == 0 && rng.below(2) == 0) {
+            Sign::Minus
+        } else {
+            Sign::Plus
+        };
+        let signature = Signature {
+            name: rng.token().into(),
+            email: rng.token().into(),
+            time: Time { seconds, offset, sign },
+        };
+
+        let encoded = signature.to_bstring()?;
+        assert_eq!(
+            gix_actor::signature::decode_exact(&encoded)?.to_owned(),
+            signature,
+            "{encoded:?}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn decode_reports_the_failed_field() {
+    use gix_actor::decode::{Error, Field};
+    let input = b"Sebastian Thiel <byronimo@gmail.com> 1528473343 +0230 and more";
+    assert_eq!(
+        gix_actor::decode(input).unwrap(),
+        gix_actor::SignatureRef::from_bytes::<()>(input).unwrap(),
+        "trailing data is ignored"
+    );
+
+    for (input, expected) in [
+        (&b"no email"[..], Field::Name),
+        (b"name <unterminated 1 +0000", Field::Email),
+        (b"name <email>", Field::Timestamp),
+        (b"name <email> abc +0000", Field::Timestamp),
+        (b"name <email> 1 0000", Field::Offset),
+        (b"name <email> 1 +0", Field::Offset),
+    ] {
+        match gix_actor::decode(input).unwrap_err() {
+            Error::Field { field, input: actual } => {
+                assert_eq!(field, expected, "{input:?}");
+                assert_eq!(actual, input.as_bstr(), "the whole input is retained for context");
+            }
+            err => unreachable!("unexpected error: {err}"),
+        }
+    }
+}
+
+mod validate_canonical {
+    use gix_actor::{
+        decode::Field,
+        signature::{decode::Anomaly, validate::Issue, validate_canonical},
+    };
+
+    #[test]
+    fn canonical_signatures_have_no_issues() {
+        for input in [
+            &b"Sebastian Thiel <byronimo@gmail.com> 1528473343 +0230"[..],
+            b"Sebastian Thiel <byronimo@gmail.com> 1528473343 -0000\n",
+            b" <> 0 +0000",
+            b"name <e> 1528473343123 +0000",
+        ] {
+            assert_eq!(validate_canonical(input), Ok(()), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn tolerated_deviations_are_reported() {
+        for (input, expected) in [
+            (&b"name  <e> 1 +0000"[..], vec![Issue::NameWithTrailingWhitespace]),
+            (
+                b"\tname\t <e> 1 +0000",
+                vec![Issue::NameWithLeadingWhitespace, Issue::NameWithTrailingWhitespace],
+            ),
+            (b"name <e> 01 +0000", vec![Issue::ZeroPaddedTimestamp]),
+            (b"name <e> -1 +0000", vec![Issue::SignedTimestamp]),
+            (b"name <e> 1 +700", vec![Issue::Anomaly(Anomaly::SingleDigitMinutes)]),
+            (
+                b"name <e> 1 --700",
+                vec![
+                    Issue::Anomaly(Anomaly::DoubleDashSign),
+                    Issue::Anomaly(Anomaly::SingleDigitMinutes),
+                ],
+            ),
+            (b"name <e> 1 +0000\nmore", vec![Issue::TrailingData]),
+        ] {
+            assert_eq!(
+                gix_actor::decode(input).map(|_| ()).ok(),
+                Some(()),
+                "{input:?} can be decoded"
+            );
+            assert_eq!(validate_canonical(input), Err(expected), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn missing_separators_are_reported() {
+        for (input, expected) in [
+            (&b"name<e> 1 +0000"[..], vec![Issue::MissingSpaceBeforeEmail]),
+            (b"<e> 1 +0000", vec![Issue::MissingSpaceBeforeEmail]),
+            (b"name <e>1 +0000", vec![Issue::MissingSpaceBeforeTimestamp]),
+            (b"name <e> 1+0000", vec![Issue::MissingSpaceBeforeOffset]),
+            (
+                b"name<e>1+0000",
+                vec![
+                    Issue::MissingSpaceBeforeEmail,
+                    Issue::MissingSpaceBeforeTimestamp,
+                    Issue::MissingSpaceBeforeOffset,
+                ],
+            ),
+        ] {
+            assert_eq!(validate_canonical(input), Err(expected), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn undecodable_signatures_report_the_failed_field() {
+        assert_eq!(
+            validate_canonical(b"name <e> abc +0000"),
+            Err(vec![Issue::Malformed {
+                field: Field::Timestamp
+            }])
+        );
+        assert_eq!(
+            validate_canonical(b"name <e 1 +0000"),
+            Err(vec![Issue::Malformed { field: Field::Email }])
+        );
+    }
+}
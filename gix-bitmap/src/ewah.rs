@@ -54,7 +54,7 @@ pub fn decode(data: &[u8]) -> Result<(Vec, &[u8]), decode::Error> {
 mod access {
     use std::convert::{TryFrom, TryInto};
 
-    use super::Vec;
+    use super::{rlw_literal_words, rlw_runbit_is_set, rlw_running_len, Vec};
 
     impl Vec {
         /// Call `f(index)` for each bit that is true, given the index of the bit that identifies it uniquely within the bit array.
@@ -100,26 +100,290 @@ mod access {
     fn rlw_running_len_bits(w: &u64) -> u64 {
         rlw_running_len(w) * 64
     }
+}
+
+mod write {
+    use std::convert::TryFrom;
+
+    use super::{
+        rlw_literal_words, rlw_runbit_is_set, rlw_running_len, Vec, RLW_LARGEST_LITERAL_COUNT,
+        RLW_LARGEST_RUNNING_COUNT, RLW_RUNNING_BITS,
+    };
+
+    impl Default for Vec {
+        fn default() -> Self {
+            Vec {
+                num_bits: 0,
+                bits: vec![0],
+                rlw: 0,
+            }
+        }
+    }
+
+    impl Vec {
+        /// Set the bit at `index` to true, compressing the bitmap just like `git` does.
+        ///
+        /// # Panics
+        ///
+        /// If `index` isn't larger than the index of the previously set bit, as bits can only be appended.
+        pub fn set(&mut self, index: usize) {
+            let num_bits = self.num_bits();
+            assert!(
+                index >= num_bits,
+                "BUG: bits must be set in increasing order, but {index} < {num_bits}"
+            );
+            let num_words = |num_bits: usize| (num_bits + 63) / 64;
+            let words_to_add = num_words(index + 1) - num_words(num_bits);
+            self.num_bits = u32::try_from(index + 1).expect("not more than u32::MAX bits");
+            let bit = 1 << (index % 64);
+            if words_to_add > 0 {
+                if words_to_add > 1 {
+                    self.add_empty_words(false, (words_to_add - 1) as u64);
+                }
+                self.add_literal(bit);
+                return;
+            }
+            let rlw = self.rlw as usize;
+            if rlw_literal_words(&self.bits[rlw]) == 0 {
+                let running_len = rlw_running_len(&self.bits[rlw]);
+                rlw_set_running_len(&mut self.bits[rlw], running_len - 1);
+                self.add_literal(bit);
+                return;
+            }
+            let last = self.bits.last_mut().expect("at least one word");
+            *last |= bit;
+            if *last == u64::MAX {
+                self.bits.pop();
+                let literal_words = rlw_literal_words(&self.bits[rlw]);
+                rlw_set_literal_words(&mut self.bits[rlw], literal_words - 1);
+                self.add_empty_word(true);
+            }
+        }
+
+        /// Serialize this bitmap to `out`, the inverse of [`decode()`](super::decode()).
+        pub fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+            let num_words = u32::try_from(self.bits.len()).expect("not more than u32::MAX words");
+            out.write_all(&self.num_bits.to_be_bytes())?;
+            out.write_all(&num_words.to_be_bytes())?;
+            for word in &self.bits {
+                out.write_all(&word.to_be_bytes())?;
+            }
+            let rlw = u32::try_from(self.rlw).expect("rlw is an index into the words");
+            out.write_all(&rlw.to_be_bytes())
+        }
+
+        fn push_rlw(&mut self) {
+            self.bits.push(0);
+            self.rlw = (self.bits.len() - 1) as u64;
+        }
+
+        fn rlw_mut(&mut self) -> &mut u64 {
+            &mut self.bits[self.rlw as usize]
+        }
+
+        fn add_literal(&mut self, word: u64) {
+            let literal_words = rlw_literal_words(self.rlw_mut());
+            if literal_words >= RLW_LARGEST_LITERAL_COUNT {
+                self.push_rlw();
+                rlw_set_literal_words(self.rlw_mut(), 1);
+            } else {
+                rlw_set_literal_words(self.rlw_mut(), literal_words + 1);
+            }
+            self.bits.push(word);
+        }
+
+        fn add_empty_word(&mut self, value: bool) {
+            let rlw = self.rlw_mut();
+            let no_literal = rlw_literal_words(rlw) == 0;
+            let running_len = rlw_running_len(rlw);
+            if no_literal && running_len == 0 {
+                rlw_set_runbit(rlw, value);
+            }
+            if no_literal && rlw_runbit_is_set(rlw) == value && running_len < RLW_LARGEST_RUNNING_COUNT {
+                rlw_set_running_len(rlw, running_len + 1);
+            } else {
+                self.push_rlw();
+                let rlw = self.rlw_mut();
+                rlw_set_runbit(rlw, value);
+                rlw_set_running_len(rlw, 1);
+            }
+        }
+
+        fn add_empty_words(&mut self, value: bool, mut count: u64) {
+            let rlw = self.rlw_mut();
+            if rlw_runbit_is_set(rlw) != value && rlw_running_len(rlw) + rlw_literal_words(rlw) == 0 {
+                rlw_set_runbit(rlw, value);
+            } else if rlw_literal_words(rlw) != 0 || rlw_runbit_is_set(rlw) != value {
+                self.push_rlw();
+                rlw_set_runbit(self.rlw_mut(), value);
+            }
+
+            let rlw = self.rlw_mut();
+            let running_len = rlw_running_len(rlw);
+            let can_add = count.min(RLW_LARGEST_RUNNING_COUNT - running_len);
+            rlw_set_running_len(rlw, running_len + can_add);
+            count -= can_add;
+
+            while count > 0 {
+                let can_add = count.min(RLW_LARGEST_RUNNING_COUNT);
+                self.push_rlw();
+                let rlw = self.rlw_mut();
+                rlw_set_runbit(rlw, value);
+                rlw_set_running_len(rlw, can_add);
+                count -= can_add;
+            }
+        }
+    }
 
     #[inline]
-    fn rlw_running_len(w: &u64) -> u64 {
-        (w >> 1) & RLW_LARGEST_RUNNING_COUNT
+    fn rlw_set_runbit(w: &mut u64, value: bool) {
+        if value {
+            *w |= 1;
+        } else {
+            *w &= !1;
+        }
     }
 
     #[inline]
-    fn rlw_literal_words(w: &u64) -> u64 {
-        w >> (1 + RLW_RUNNING_BITS)
+    fn rlw_set_running_len(w: &mut u64, len: u64) {
+        *w = (*w & !(RLW_LARGEST_RUNNING_COUNT << 1)) | (len << 1);
     }
 
     #[inline]
-    fn rlw_runbit_is_set(w: &u64) -> bool {
-        w & 1 == 1
+    fn rlw_set_literal_words(w: &mut u64, len: u64) {
+        *w = (*w & RLW_RUNNING_LEN_PLUS_BIT) | (len << (RLW_RUNNING_BITS + 1));
+    }
+
+    const RLW_RUNNING_LEN_PLUS_BIT: u64 = (1 << (RLW_RUNNING_BITS + 1)) - 1;
+
+    #[cfg(test)]
+    mod tests {
+        use super::super::{decode, Vec, RLW_LARGEST_RUNNING_COUNT};
+
+        fn write(bitmap: &Vec) -> std::vec::Vec<u8> {
+            let mut buf = std::vec::Vec::new();
+            bitmap.write_to(&mut buf).expect("writing to memory works");
+            buf
+        }
+
+        /// Write `bitmap`, decode it again and return the decoded bitmap along with all of its set bits.
+        fn roundtrip(bitmap: &Vec) -> (Vec, std::vec::Vec<usize>) {
+            let buf = write(bitmap);
+            let (decoded, rest) = decode(&buf).expect("valid");
+            assert!(rest.is_empty(), "everything that was written is read back");
+            assert_eq!(decoded.num_bits, bitmap.num_bits);
+            assert_eq!(decoded.bits, bitmap.bits);
+            assert_eq!(decoded.rlw, bitmap.rlw);
+            let mut set_bits = std::vec::Vec::new();
+            decoded.for_each_set_bit(|index| {
+                set_bits.push(index);
+                Some(())
+            });
+            (decoded, set_bits)
+        }
+
+        fn bitmap_with(indices: impl IntoIterator<Item = usize>) -> Vec {
+            let mut bitmap = Vec::default();
+            for index in indices {
+                bitmap.set(index);
+            }
+            bitmap
+        }
+
+        #[test]
+        fn empty() {
+            let (decoded, set_bits) = roundtrip(&Vec::default());
+            assert_eq!(decoded.num_bits(), 0);
+            assert!(set_bits.is_empty());
+            assert_eq!(decoded.bits.len(), 1, "there is only the marker word");
+        }
+
+        #[test]
+        fn sparse_bits() {
+            let indices = [0, 5, 63, 64, 130, 10_000];
+            let (decoded, set_bits) = roundtrip(&bitmap_with(indices));
+            assert_eq!(set_bits, indices);
+            assert_eq!(decoded.num_bits(), 10_001);
+            assert_eq!(
+                decoded.bits.len(),
+                6,
+                "one marker word for the first three literal words, and one for the run of empty words before the last one"
+            );
+        }
+
+        #[test]
+        fn all_ones_words_are_compressed_into_a_run() {
+            let indices: std::vec::Vec<_> = (0..128).chain(Some(130)).collect();
+            let (decoded, set_bits) = roundtrip(&bitmap_with(indices.iter().copied()));
+            assert_eq!(set_bits, indices);
+            assert_eq!(
+                decoded.bits.len(),
+                2,
+                "a marker word with a run of two words with all bits set, followed by one literal word"
+            );
+        }
+
+        #[test]
+        #[cfg(target_pointer_width = "64")]
+        fn runs_longer_than_what_fits_into_one_marker_word() {
+            let mut bitmap = Vec::default();
+            bitmap.add_empty_words(false, RLW_LARGEST_RUNNING_COUNT + 2);
+            bitmap.add_literal(0b10);
+            let (decoded, set_bits) = roundtrip(&bitmap);
+            assert_eq!(set_bits, [(RLW_LARGEST_RUNNING_COUNT as usize + 2) * 64 + 1]);
+            assert_eq!(
+                decoded.bits.len(),
+                3,
+                "one marker word for the longest possible run, and one for the rest of the run and the literal word"
+            );
+        }
+
+        #[test]
+        fn setting_a_bit_in_the_last_word_of_a_run() {
+            let mut buf = std::vec::Vec::new();
+            buf.extend_from_slice(&65_u32.to_be_bytes());
+            buf.extend_from_slice(&1_u32.to_be_bytes());
+            buf.extend_from_slice(&(2_u64 << 1).to_be_bytes());
+            buf.extend_from_slice(&0_u32.to_be_bytes());
+            let (mut bitmap, _) = decode(&buf).expect("valid");
+            assert_eq!(
+                roundtrip(&bitmap).1,
+                std::vec::Vec::<usize>::new(),
+                "a run of two empty words"
+            );
+
+            bitmap.set(100);
+            let (decoded, set_bits) = roundtrip(&bitmap);
+            assert_eq!(set_bits, [100]);
+            assert_eq!(decoded.num_bits(), 101);
+            assert_eq!(
+                decoded.bits.len(),
+                2,
+                "the last word of the run is taken out of it to become a literal word"
+            );
+        }
     }
+}
+
+#[inline]
+fn rlw_running_len(w: &u64) -> u64 {
+    (w >> 1) & RLW_LARGEST_RUNNING_COUNT
+}
+
+#[inline]
+fn rlw_literal_words(w: &u64) -> u64 {
+    w >> (1 + RLW_RUNNING_BITS)
+}
 
-    const RLW_RUNNING_BITS: u64 = 4 * 8;
-    const RLW_LARGEST_RUNNING_COUNT: u64 = (1 << RLW_RUNNING_BITS) - 1;
+#[inline]
+fn rlw_runbit_is_set(w: &u64) -> bool {
+    w & 1 == 1
 }
 
+const RLW_RUNNING_BITS: u64 = 4 * 8;
+const RLW_LARGEST_RUNNING_COUNT: u64 = (1 << RLW_RUNNING_BITS) - 1;
+const RLW_LARGEST_LITERAL_COUNT: u64 = (1 << (64 - 1 - RLW_RUNNING_BITS)) - 1;
+
 /// A growable collection of u64 that are seen as stream of individual bits.
 #[allow(dead_code)]
 #[derive(Clone)]
@@ -93,8 +93,17 @@ mod relative {
     use crate::parse::Error;
 
     fn parse_inner(input: &str) -> Option<Duration> {
-        let mut split = input.split_whitespace();
-        let multiplier = i64::from_str(split.next()?).ok()?;
+        // Like git, allow dots instead of spaces, as in `2.days.ago`.
+        let mut split = input
+            .split(|c: char| c.is_whitespace() || c == '.')
+            .filter(|token| !token.is_empty());
+        let first = split.next()?;
+        match first {
+            "now" if split.next().is_none() => return Some(Duration::ZERO),
+            "yesterday" if split.next().is_none() => return Some(Duration::days(1)),
+            _ => {}
+        }
+        let multiplier = i64::from_str(first).ok()?;
         let period = split.next()?;
         if split.next()? != "ago" {
             return None;
@@ -139,5 +148,18 @@ mod relative {
         fn two_weeks_ago() {
             assert_eq!(parse_inner("2 weeks ago"), Some(Duration::weeks(2)));
         }
+
+        #[test]
+        fn dots_separate_like_spaces() {
+            assert_eq!(parse_inner("2.days.ago"), Some(Duration::days(2)));
+            assert_eq!(parse_inner("2.days ago"), Some(Duration::days(2)));
+        }
+
+        #[test]
+        fn special_words() {
+            assert_eq!(parse_inner("now"), Some(Duration::ZERO));
+            assert_eq!(parse_inner("yesterday"), Some(Duration::days(1)));
+            assert_eq!(parse_inner("yesterday ago"), None);
+        }
     }
 }
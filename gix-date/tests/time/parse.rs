@@ -146,6 +146,25 @@ mod relative {
         assert_eq!(date.seconds, -1);
     }
 
+    #[test]
+    fn now_and_yesterday() {
+        let now = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        assert_eq!(gix_date::parse("now", Some(now)).unwrap().seconds, 1_000_000);
+        assert_eq!(
+            gix_date::parse("yesterday", Some(now)).unwrap().seconds,
+            1_000_000 - 24 * 60 * 60
+        );
+        assert_eq!(
+            gix_date::parse("2.days.ago", Some(now)).unwrap(),
+            gix_date::parse("2 days ago", Some(now)).unwrap(),
+            "dots can separate tokens like in git"
+        );
+        assert!(matches!(
+            gix_date::parse("now", None),
+            Err(gix_date::parse::Error::MissingCurrentTime)
+        ));
+    }
+
     #[test]
     fn various() {
         let now = Some(SystemTime::now());
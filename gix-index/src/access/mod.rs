@@ -1,9 +1,11 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::BTreeSet};
 
-use bstr::{BStr, ByteSlice, ByteVec};
+use bstr::{BStr, BString, ByteSlice, ByteVec};
 use filetime::FileTime;
+use gix_object::TreeRefIter;
+use gix_traverse::tree::breadthfirst;
 
-use crate::{entry, extension, Entry, PathStorage, State, Version};
+use crate::{entry, entry::Mode, extension, EntriesMut, Entry, PathStorage, State, Version};
 
 // TODO: integrate this somehow, somewhere, depending on later usage.
 #[allow(dead_code)]
@@ -128,6 +130,32 @@ impl State {
             .ok()
     }
 
+    /// Find the entry index in [`entries()`][State::entries()] matching the given repository-relative `path` and `stage`,
+    /// or `None`, while only [`entries()[..sorted_len]`][State::entries()] are sorted.
+    ///
+    /// The sorted entries are searched with [`entry_index_by_path_and_stage_bounded()`][State::entry_index_by_path_and_stage_bounded()]
+    /// first, and only then the remaining entries are scanned one by one. This allows finding entries that were pushed
+    /// with [`dangerously_push_entry()`][State::dangerously_push_entry()] without [sorting][State::sort_entries()] first,
+    /// with `sorted_len` being the amount of entries before the first push.
+    ///
+    /// # Panics
+    ///
+    /// If `sorted_len` is out of bounds of our entries array.
+    pub fn entry_index_by_path_and_stage_with_unsorted_tail(
+        &self,
+        path: &BStr,
+        stage: entry::Stage,
+        sorted_len: usize,
+    ) -> Option<usize> {
+        self.entry_index_by_path_and_stage_bounded(path, stage, sorted_len)
+            .or_else(|| {
+                self.entries[sorted_len..]
+                    .iter()
+                    .position(|e| e.stage() == stage && e.path(self) == path)
+                    .map(|ofs| sorted_len + ofs)
+            })
+    }
+
     /// Like [`entry_index_by_path_and_stage()`][State::entry_index_by_path_and_stage()],
     /// but returns the entry instead of the index.
     pub fn entry_by_path_and_stage(&self, path: &BStr, stage: entry::Stage) -> Option<&Entry> {
@@ -170,7 +198,7 @@ impl State {
         let mut high = low
             + self.entries[low..].partition_point(|e| e.path(self).get(..prefix_len).map_or(false, |p| p <= prefix));
 
-        let low_entry = &self.entries[low];
+        let low_entry = self.entries.get(low)?;
         if low_entry.stage() != 0 {
             low = self
                 .entry_index_by_idx_and_stage(low_entry.path(self), low, 0, low_entry.stage().cmp(&0))
@@ -186,6 +214,83 @@ impl State {
         (low != high).then_some(low..high).map(|range| &self.entries[range])
     }
 
+    /// Return `true` if there is at least one entry whose path starts with `prefix`, like any entry in `d/` for `d/`.
+    ///
+    /// This only needs a single binary search and is thus cheaper than [`prefixed_entries()`][State::prefixed_entries()]
+    /// if the entries themselves aren't needed. An empty `prefix` matches if there is any entry.
+    pub fn has_prefix(&self, prefix: &BStr) -> bool {
+        let idx = self.entries.partition_point(|e| e.path(self) < prefix);
+        self.entries
+            .get(idx)
+            .map_or(false, |e| e.path(self).starts_with(prefix))
+    }
+
+    /// Return all entries whose path starts with `prefix` when ignoring the case of ASCII characters, in order,
+    /// as needed on case-insensitive filesystems where `D/` refers to the same directory as `d/`.
+    ///
+    /// As entries are sorted case-sensitively, matches aren't necessarily adjacent, which is why this returns an
+    /// iterator instead of a slice like [`prefixed_entries()`][State::prefixed_entries()] does.
+    /// Only the entries starting with either case of the first character of `prefix` are looked at.
+    pub fn prefixed_entries_icase<'a>(&'a self, prefix: &'a BStr) -> impl Iterator<Item = &'a Entry> + 'a {
+        let first_byte_range = |byte: u8| {
+            let low = self.entries.partition_point(|e| e.path(self).first() < Some(&byte));
+            low..low + self.entries[low..].partition_point(|e| e.path(self).first() == Some(&byte))
+        };
+        let ranges = match prefix.first() {
+            None => [0..self.entries.len(), 0..0],
+            Some(byte) => {
+                let (upper, lower) = (byte.to_ascii_uppercase(), byte.to_ascii_lowercase());
+                [
+                    first_byte_range(upper),
+                    if upper == lower { 0..0 } else { first_byte_range(lower) },
+                ]
+            }
+        };
+        ranges
+            .into_iter()
+            .flat_map(move |range| self.entries[range].iter())
+            .filter(move |e| {
+                e.path(self)
+                    .get(..prefix.len())
+                    .map_or(false, |p| p.eq_ignore_ascii_case(prefix))
+            })
+    }
+
+    /// Return the entries within the directory `dir`, like `d/a` or `d/sub/b` for `d`, or `None` if there is none.
+    ///
+    /// As opposed to [`prefixed_entries()`][State::prefixed_entries()], `dir` is treated as a whole path component,
+    /// so entries like `dog` or `d-file` that merely start with `d` are not included.
+    /// An empty `dir` is the root of the repository and matches all entries.
+    pub fn entries_under_dir(&self, dir: &BStr) -> Option<&[Entry]> {
+        let dir = dir.strip_suffix(b"/").unwrap_or(dir);
+        if dir.is_empty() {
+            return (!self.entries.is_empty()).then_some(self.entries());
+        }
+        let mut prefix = BString::from(dir);
+        prefix.push_byte(b'/');
+        self.prefixed_entries(prefix.as_ref())
+    }
+
+    /// Return all entries which share the same `prefix` like [`prefixed_entries()`][State::prefixed_entries()],
+    /// but only those at `stage`, like `0` for entries without conflict or `2` for our side of a conflict.
+    pub fn prefixed_entries_with_stage(&self, prefix: &BStr, stage: entry::Stage) -> impl Iterator<Item = &Entry> + '_ {
+        self.prefixed_entries(prefix)
+            .unwrap_or_default()
+            .iter()
+            .filter(move |entry| entry.stage() == stage)
+    }
+
+    /// Return owned copies of the paths of all entries for which `predicate` returns `true`, in order.
+    ///
+    /// This is useful if the paths are needed after this instance is gone.
+    pub fn collect_paths_where(&self, mut predicate: impl FnMut(&Entry) -> bool) -> Vec<BString> {
+        self.entries
+            .iter()
+            .filter(|entry| predicate(entry))
+            .map(|entry| entry.path(self).to_owned())
+            .collect()
+    }
+
     /// Return the entry at `idx` or _panic_ if the index is out of bounds.
     ///
     /// The `idx` is typically returned by [`entry_by_path_and_stage()`][State::entry_by_path_and_stage()].
@@ -193,6 +298,65 @@ impl State {
         &self.entries[idx]
     }
 
+    /// Return the distinct ids of all blobs referenced by entries at stage 0, which are the ones kept alive by this index.
+    ///
+    /// Submodule commits and sparse directories aren't blobs and are thus ignored.
+    pub fn referenced_blob_oids(&self) -> BTreeSet<gix_hash::ObjectId> {
+        self.entries
+            .iter()
+            .filter(|e| e.stage() == 0 && matches!(e.mode, Mode::FILE | Mode::FILE_EXECUTABLE | Mode::SYMLINK))
+            .map(|e| e.id)
+            .collect()
+    }
+
+    /// Return the entry whose on-disk representation contains the byte at `offset` from the beginning of the file this
+    /// state was decoded from, or `None` if `offset` isn't within the block of entries.
+    ///
+    /// This is useful to map corruption reported at a specific position in the file back to the affected entry.
+    /// Note that this mapping is only available if [`track_source_offsets`](crate::decode::Options::track_source_offsets)
+    /// was enabled when decoding, and that it's lost once entries are added or reordered.
+    pub fn entry_at_source_offset(&self, offset: usize) -> Option<&Entry> {
+        if self.source_offsets.len() != self.entries.len() + 1 {
+            return None;
+        }
+        let offset: u32 = offset.try_into().ok()?;
+        let idx = match self.source_offsets.binary_search(&offset) {
+            Ok(idx) => idx,
+            Err(idx) => idx.checked_sub(1)?,
+        };
+        self.entries.get(idx)
+    }
+
+    /// Return the paths of all entries in `tree` that have no entry in this index at any stage, which are the paths that
+    /// would be deleted when writing this index as tree. Sub-trees are looked up with `find`, just like in
+    /// [`from_tree()`][State::from_tree()].
+    ///
+    /// Paths within sparse directories of this index are considered present.
+    pub fn removed_relative_to_tree<Find>(
+        &self,
+        tree: &gix_hash::oid,
+        find: Find,
+    ) -> Result<Vec<BString>, breadthfirst::Error>
+    where
+        Find: for<'a> FnMut(&gix_hash::oid, &'a mut Vec<u8>) -> Option<TreeRefIter<'a>>,
+    {
+        let tree_state = State::from_tree(tree, find)?;
+        let is_in_sparse_dir = |path: &BStr| {
+            self.is_sparse
+                && path.find_iter("/").any(|pos| {
+                    self.entry_by_path(path[..=pos].as_bstr())
+                        .map_or(false, |e| e.mode.is_sparse())
+                })
+        };
+        Ok(tree_state
+            .entries
+            .iter()
+            .map(|e| e.path(&tree_state))
+            .filter(|path| self.entry_by_path(path).is_none() && !is_in_sparse_dir(path))
+            .map(ToOwned::to_owned)
+            .collect())
+    }
+
     /// Returns a boolean value indicating whether the index is sparse or not.
     ///
     /// An index is sparse if it contains at least one [`Mode::DIR`][entry::Mode::DIR] entry.
@@ -213,9 +377,14 @@ impl State {
         self.path_backing = backing;
     }
 
-    /// Return mutable entries in a slice.
-    pub fn entries_mut(&mut self) -> &mut [Entry] {
-        &mut self.entries
+    /// Return a guard to access all entries mutably, which sorts them on drop if they are out of order.
+    ///
+    /// Changing the stage of entries or replacing them with other entries can affect their order, which is why the order
+    /// is checked once the guard is dropped, and sorting only happens if needed.
+    /// Use [`entries_mut_and_pathbacking()`](Self::entries_mut_and_pathbacking()) to avoid this, while taking responsibility
+    /// for keeping the order intact.
+    pub fn entries_mut(&mut self) -> EntriesMut<'_> {
+        EntriesMut { state: self }
     }
 
     /// Return a writable slice to entries and read-access to their path storage at the same time.
@@ -257,6 +426,27 @@ impl State {
             .map(|idx| &mut self.entries[idx])
     }
 
+    /// Clear the [`FSMONITOR_VALID`][entry::Flags::FSMONITOR_VALID] flag of all entries at `paths`, as reported changed by
+    /// a filesystem monitor, so only these have to be checked for changes in the worktree.
+    ///
+    /// Paths ending with a slash are directories, and all entries within them are affected.
+    /// Entries at all stages are affected.
+    ///
+    /// Each path is found with a binary search, which requires the entries to be [sorted][State::sort_entries()].
+    pub fn mark_possibly_dirty(&mut self, paths: &[&BStr]) {
+        for changed in paths {
+            let is_dir = changed.ends_with(b"/");
+            let start = self.entries.partition_point(|e| e.path(self) < *changed);
+            let len = self.entries[start..].partition_point(|e| {
+                let path = e.path(self);
+                path == *changed || (is_dir && path.starts_with(changed))
+            });
+            for entry in &mut self.entries[start..][..len] {
+                entry.flags.remove(entry::Flags::FSMONITOR_VALID);
+            }
+        }
+    }
+
     /// Push a new entry containing `stat`, `id`, `flags` and `mode` and `path` to the end of our storage, without performing
     /// any sanity checks. This means it's possible to push a new entry to the same path on the same stage and even after sorting
     /// the entries lookups may still return the wrong one of them unless the correct binary search criteria is chosen.
@@ -281,6 +471,7 @@ impl State {
             path_start..self.path_backing.len()
         };
 
+        self.source_offsets.clear();
         self.entries.push(Entry {
             stat,
             id,
@@ -290,8 +481,54 @@ impl State {
         });
     }
 
+    /// Remove all entries for which `should_remove(idx, path, entry)` returns `true`, with `idx` being the index of
+    /// the entry before any removal.
+    ///
+    /// The paths of removed entries remain in the path backing until [`compact_path_backing()`][State::compact_path_backing()]
+    /// is called.
+    pub fn remove_entries(&mut self, mut should_remove: impl FnMut(usize, &BStr, &mut Entry) -> bool) {
+        self.source_offsets.clear();
+        let paths = &self.path_backing;
+        let mut idx = 0;
+        self.entries.retain_mut(|entry| {
+            let remove = should_remove(idx, entry.path_in(paths), entry);
+            idx += 1;
+            !remove
+        });
+    }
+
+    /// Rebuild the path backing so it only contains the paths of the current entries, in order, and release the memory
+    /// held by the paths of removed entries.
+    ///
+    /// This is useful after many entries were removed or replaced in a long-lived state.
+    /// Note that the path backing must not be [taken][State::take_path_backing()] while calling this.
+    pub fn compact_path_backing(&mut self) {
+        let mut backing = PathStorage::with_capacity(self.entries.iter().map(|entry| entry.path.len()).sum());
+        for entry in &mut self.entries {
+            let start = backing.len();
+            backing.extend_from_slice(&self.path_backing[entry.path.clone()]);
+            entry.path = start..backing.len();
+        }
+        self.path_backing = backing;
+    }
+
+    /// Return `true` if the entries are ordered by path and stage as [`sort_entries()`][State::sort_entries()] leaves them,
+    /// which is required for lookups by path to work.
+    ///
+    /// Unlike [`verify_entries()`][State::verify_entries()], entries with the same path and stage are accepted, and no error
+    /// is produced.
+    pub fn is_sorted(&self) -> bool {
+        self.entries
+            .windows(2)
+            .all(|pair| pair[0].cmp(&pair[1], self) != Ordering::Greater)
+    }
+
     /// Unconditionally sort entries as needed to perform lookups quickly.
+    ///
+    /// The sort is stable, so entries with the same path and stage, which are invalid but may exist after
+    /// [pushing entries](State::dangerously_push_entry()), keep their relative order.
     pub fn sort_entries(&mut self) {
+        self.source_offsets.clear();
         let path_backing = &self.path_backing;
         self.entries.sort_by(|a, b| {
             Entry::cmp_filepaths(a.path_in(path_backing), b.path_in(path_backing))
@@ -300,8 +537,9 @@ impl State {
     }
 
     /// Similar to [`sort_entries()`][State::sort_entries()], but applies `compare` after comparing
-    /// by path and stage as a third criteria.
+    /// by path and stage as a third criteria. The sort is stable as well.
     pub fn sort_entries_by(&mut self, mut compare: impl FnMut(&Entry, &Entry) -> Ordering) {
+        self.source_offsets.clear();
         let path_backing = &self.path_backing;
         self.entries.sort_by(|a, b| {
             Entry::cmp_filepaths(a.path_in(path_backing), b.path_in(path_backing))
@@ -311,6 +549,52 @@ impl State {
     }
 }
 
+impl std::ops::Deref for EntriesMut<'_> {
+    type Target = [Entry];
+
+    fn deref(&self) -> &Self::Target {
+        &self.state.entries
+    }
+}
+
+impl std::ops::Index<usize> for EntriesMut<'_> {
+    type Output = Entry;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.state.entries[index]
+    }
+}
+
+impl std::ops::IndexMut<usize> for EntriesMut<'_> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.state.entries[index]
+    }
+}
+
+impl EntriesMut<'_> {
+    /// Return an iterator over all entries for altering them one at a time.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Entry> {
+        self.state.entries.iter_mut()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut EntriesMut<'_> {
+    type Item = &'a mut Entry;
+    type IntoIter = std::slice::IterMut<'a, Entry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.state.entries.iter_mut()
+    }
+}
+
+impl Drop for EntriesMut<'_> {
+    fn drop(&mut self) {
+        if !self.state.is_sorted() {
+            self.state.sort_entries();
+        }
+    }
+}
+
 /// Extensions
 impl State {
     /// Access the `tree` extension.
@@ -39,10 +39,15 @@ pub fn estimate_path_storage_requirements_in_bytes(
     }
 }
 
-/// Note that `data` must point to the beginning of the entries, right past the header.
+/// Note that `data` must point to the beginning of the entries, right past the header, and that it
+/// starts at `data_offset` bytes from the beginning of the file.
+/// The offsets of all entries are pushed to `source_offsets` if it is set.
+#[allow(clippy::too_many_arguments)]
 pub fn chunk<'a>(
     mut data: &'a [u8],
+    data_offset: usize,
     entries: &mut Vec<Entry>,
+    mut source_offsets: Option<&mut Vec<u32>>,
     path_backing: &mut Vec<u8>,
     num_entries: u32,
     object_hash: gix_hash::Kind,
@@ -52,8 +57,12 @@ pub fn chunk<'a>(
     let has_delta_paths = version == Version::V4;
     let mut prev_path = None;
     let mut delta_buf = Vec::<u8>::with_capacity(AVERAGE_V4_DELTA_PATH_LEN_IN_BYTES);
+    let first_byte = data.as_ptr() as usize;
 
     for idx in 0..num_entries {
+        if let Some(source_offsets) = source_offsets.as_deref_mut() {
+            source_offsets.push((data_offset + (data.as_ptr() as usize - first_byte)) as u32);
+        }
         let (entry, remaining) = load_one(
             data,
             path_backing,
@@ -50,6 +50,11 @@ pub struct Options {
     ///
     /// We will abort reading this file if it doesn't match.
     pub expected_checksum: Option<gix_hash::ObjectId>,
+    /// If true, remember the offset of each entry in the decoded data to allow looking entries up by offset
+    /// with [`State::entry_at_source_offset()`], which is useful when diagnosing corrupt index files.
+    ///
+    /// This costs 4 bytes per entry, which is why it's disabled by default.
+    pub track_source_offsets: bool,
 }
 
 impl State {
@@ -63,6 +68,7 @@ impl State {
             thread_limit,
             min_extension_block_in_bytes_for_threading,
             expected_checksum,
+            track_source_offsets,
         }: Options,
     ) -> Result<(Self, gix_hash::ObjectId), Error> {
         let _span = gix_features::trace::detail!("gix_index::State::from_bytes()");
@@ -107,6 +113,11 @@ impl State {
                                             let num_entries_for_chunks =
                                                 chunks.iter().map(|c| c.num_entries).sum::<u32>() as usize;
                                             let mut entries = Vec::with_capacity(num_entries_for_chunks);
+                                            let mut source_offsets = Vec::with_capacity(if track_source_offsets {
+                                                num_entries_for_chunks
+                                            } else {
+                                                0
+                                            });
                                             let path_backing_buffer_size_for_chunks =
                                                 entries::estimate_path_storage_requirements_in_bytes(
                                                     num_entries_for_chunks as u32,
@@ -126,7 +137,9 @@ impl State {
                                                     _data,
                                                 ) = entries::chunk(
                                                     &data[offset.from_beginning_of_file as usize..],
+                                                    offset.from_beginning_of_file as usize,
                                                     &mut entries,
+                                                    track_source_offsets.then_some(&mut source_offsets),
                                                     &mut path_backing,
                                                     offset.num_entries,
                                                     object_hash,
@@ -138,6 +151,7 @@ impl State {
                                                 id,
                                                 EntriesOutcome {
                                                     entries,
+                                                    source_offsets,
                                                     path_backing,
                                                     is_sparse,
                                                 },
@@ -161,6 +175,7 @@ impl State {
                                 match res {
                                     Ok(rhs) => {
                                         lhs.is_sparse |= rhs.is_sparse;
+                                        lhs.source_offsets.extend(rhs.source_offsets);
                                         let ofs = lhs.path_backing.len();
                                         lhs.path_backing.extend(rhs.path_backing);
                                         lhs.entries.extend(rhs.entries.into_iter().map(|mut e| {
@@ -182,6 +197,7 @@ impl State {
                             num_entries,
                             object_hash,
                             version,
+                            track_source_offsets,
                         ),
                     };
                     let ext_res = extension_loading.map_or_else(
@@ -191,17 +207,25 @@ impl State {
                     (entries_res, ext_res)
                 });
                 let (ext, data) = ext_res?;
-                (entries_res?.0, ext, data)
+                let mut entries = entries_res?.0;
+                if track_source_offsets {
+                    entries.source_offsets.push(offset as u32);
+                }
+                (entries, ext, data)
             }
             None | Some(_) => {
-                let (entries, data) = entries(
+                let (mut entries, rest) = entries(
                     post_header_data,
                     path_backing_buffer_size,
                     num_entries,
                     object_hash,
                     version,
+                    track_source_offsets,
                 )?;
-                let (ext, data) = extension::decode::all(data, object_hash)?;
+                if track_source_offsets {
+                    entries.source_offsets.push((data.len() - rest.len()) as u32);
+                }
+                let (ext, data) = extension::decode::all(rest, object_hash)?;
                 (entries, ext, data)
             }
         };
@@ -224,6 +248,7 @@ impl State {
         }
         let EntriesOutcome {
             entries,
+            source_offsets,
             path_backing,
             mut is_sparse,
         } = entries;
@@ -243,6 +268,7 @@ impl State {
                 timestamp,
                 version,
                 entries,
+                source_offsets,
                 path_backing,
                 is_sparse,
 
@@ -259,6 +285,7 @@ impl State {
 
 struct EntriesOutcome {
     pub entries: Vec<Entry>,
+    pub source_offsets: Vec<u32>,
     pub path_backing: Vec<u8>,
     pub is_sparse: bool,
 }
@@ -269,12 +296,20 @@ fn entries(
     num_entries: u32,
     object_hash: gix_hash::Kind,
     version: Version,
+    track_source_offsets: bool,
 ) -> Result<(EntriesOutcome, &[u8]), Error> {
     let mut entries = Vec::with_capacity(num_entries as usize);
+    let mut source_offsets = Vec::with_capacity(if track_source_offsets {
+        num_entries as usize + 1
+    } else {
+        0
+    });
     let mut path_backing = Vec::with_capacity(path_backing_buffer_size);
     entries::chunk(
         post_header_data,
+        header::SIZE,
         &mut entries,
+        track_source_offsets.then_some(&mut source_offsets),
         &mut path_backing,
         num_entries,
         object_hash,
@@ -284,6 +319,7 @@ fn entries(
         (
             EntriesOutcome {
                 entries,
+                source_offsets,
                 path_backing,
                 is_sparse,
             },
@@ -305,13 +341,13 @@ pub(crate) fn stat(data: &[u8]) -> Option<(entry::Stat, &[u8])> {
     Some((
         entry::Stat {
             mtime: entry::stat::Time {
-                secs: ctime_secs,
-                nsecs: ctime_nsecs,
-            },
-            ctime: entry::stat::Time {
                 secs: mtime_secs,
                 nsecs: mtime_nsecs,
             },
+            ctime: entry::stat::Time {
+                secs: ctime_secs,
+                nsecs: ctime_nsecs,
+            },
             dev,
             ino,
             uid,
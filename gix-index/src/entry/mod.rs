@@ -75,6 +75,46 @@ mod access {
         pub fn stage(&self) -> entry::Stage {
             self.flags.stage()
         }
+
+        /// Return `true` if the entry is assumed to be unchanged in the worktree, like after
+        /// `git update-index --assume-unchanged`.
+        pub fn is_assume_valid(&self) -> bool {
+            self.flags.contains(entry::Flags::ASSUME_VALID)
+        }
+
+        /// Set the entry to be assumed unchanged in the worktree if `toggle` is `true`, or unset it otherwise.
+        pub fn set_assume_valid(&mut self, toggle: bool) {
+            self.flags.set(entry::Flags::ASSUME_VALID, toggle);
+        }
+
+        /// Return `true` if the entry was added with `git add --intent-to-add`.
+        pub fn is_intent_to_add(&self) -> bool {
+            self.flags.contains(entry::Flags::INTENT_TO_ADD)
+        }
+
+        /// Mark the entry as intended to be added if `toggle` is `true`, or unmark it otherwise.
+        pub fn set_intent_to_add(&mut self, toggle: bool) {
+            self.set_extended_flag(entry::Flags::INTENT_TO_ADD, toggle);
+        }
+
+        /// Return `true` if the entry is excluded from the worktree, typically due to a sparse checkout.
+        pub fn is_skip_worktree(&self) -> bool {
+            self.flags.contains(entry::Flags::SKIP_WORKTREE)
+        }
+
+        /// Exclude the entry from the worktree if `toggle` is `true`, or include it otherwise.
+        pub fn set_skip_worktree(&mut self, toggle: bool) {
+            self.set_extended_flag(entry::Flags::SKIP_WORKTREE, toggle);
+        }
+
+        /// Set or unset `flag`, and keep the `EXTENDED` bit in sync so the flags are stored when writing the index.
+        fn set_extended_flag(&mut self, flag: entry::Flags, toggle: bool) {
+            self.flags.set(flag, toggle);
+            let needs_extension = self
+                .flags
+                .intersects(entry::Flags::INTENT_TO_ADD | entry::Flags::SKIP_WORKTREE);
+            self.flags.set(entry::Flags::EXTENDED, needs_extension);
+        }
     }
 }
 
@@ -1,10 +1,37 @@
 use std::convert::TryInto;
 
-use crate::{entry, Entry, State};
+use bstr::BStr;
+
+use crate::{entry, util, Entry, State};
 
 impl Entry {
     /// Serialize ourselves to `out` with path access via `state`, without padding.
     pub fn write_to(&self, mut out: impl std::io::Write, state: &State) -> std::io::Result<()> {
+        let path = self.path(state);
+        self.write_stat_id_and_flags(&mut out, path)?;
+        out.write_all(path)?;
+        out.write_all(b"\0")
+    }
+
+    /// Serialize ourselves to `out` like index V4 does, storing `path` relative to the `previous_path` written before it.
+    pub(crate) fn write_to_with_delta_path(
+        &self,
+        mut out: impl std::io::Write,
+        path: &BStr,
+        previous_path: &BStr,
+    ) -> std::io::Result<()> {
+        self.write_stat_id_and_flags(&mut out, path)?;
+        let common_len = path
+            .iter()
+            .zip(previous_path.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        util::write_var_int(&mut out, (previous_path.len() - common_len) as u64)?;
+        out.write_all(&path[common_len..])?;
+        out.write_all(b"\0")
+    }
+
+    fn write_stat_id_and_flags(&self, mut out: impl std::io::Write, path: &BStr) -> std::io::Result<()> {
         let stat = self.stat;
         out.write_all(&stat.ctime.secs.to_be_bytes())?;
         out.write_all(&stat.ctime.nsecs.to_be_bytes())?;
@@ -17,7 +44,6 @@ impl Entry {
         out.write_all(&stat.gid.to_be_bytes())?;
         out.write_all(&stat.size.to_be_bytes())?;
         out.write_all(self.id.as_bytes())?;
-        let path = self.path(state);
         let path_len: u16 = if path.len() >= entry::Flags::PATH_LEN.bits() as usize {
             entry::Flags::PATH_LEN.bits() as u16
         } else {
@@ -33,7 +59,26 @@ impl Entry {
                     .to_be_bytes(),
             )?;
         }
-        out.write_all(path)?;
-        out.write_all(b"\0")
+        Ok(())
+    }
+}
+
+impl entry::Stat {
+    /// Serialize ourselves to `out` like `git` does outside of index entries, which don't store the mode in between.
+    pub(crate) fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        for field in [
+            self.ctime.secs,
+            self.ctime.nsecs,
+            self.mtime.secs,
+            self.mtime.nsecs,
+            self.dev,
+            self.ino,
+            self.uid,
+            self.gid,
+            self.size,
+        ] {
+            out.write_all(&field.to_be_bytes())?;
+        }
+        Ok(())
     }
 }
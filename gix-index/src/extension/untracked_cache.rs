@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 
 use bstr::BString;
 use gix_hash::ObjectId;
@@ -6,11 +6,11 @@ use gix_hash::ObjectId;
 use crate::{
     entry,
     extension::{Signature, UntrackedCache},
-    util::{read_u32, split_at_byte_exclusive, split_at_pos, var_int},
+    util::{read_u32, split_at_byte_exclusive, split_at_pos, var_int, write_var_int},
 };
 
 /// A structure to track filesystem stat information along with an object id, linking a worktree file with what's in our ODB.
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OidStat {
     /// The file system stat information
     pub stat: entry::Stat,
@@ -19,7 +19,7 @@ pub struct OidStat {
 }
 
 /// A directory with information about its untracked files, and its sub-directories
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Directory {
     /// The directories name, or an empty string if this is the root directory.
     pub name: BString,
@@ -49,9 +49,11 @@ pub fn decode(data: &[u8], object_hash: gix_hash::Kind) -> Option<UntrackedCache
     let (identifier, data) = split_at_pos(data, identifier_len.try_into().ok()?)?;
 
     let hash_len = object_hash.len_in_bytes();
-    let (info_exclude, data) = decode_oid_stat(data, hash_len)?;
-    let (excludes_file, data) = decode_oid_stat(data, hash_len)?;
+    let (info_exclude_stat, data) = crate::decode::stat(data)?;
+    let (excludes_file_stat, data) = crate::decode::stat(data)?;
     let (dir_flags, data) = read_u32(data)?;
+    let (info_exclude, data) = decode_oid_stat(info_exclude_stat, data, hash_len)?;
+    let (excludes_file, data) = decode_oid_stat(excludes_file_stat, data, hash_len)?;
     let (exclude_filename_per_dir, data) = split_at_byte_exclusive(data, 0)?;
 
     let (num_directory_blocks, data) = var_int(data)?;
@@ -143,8 +145,7 @@ fn decode_directory_block<'a>(data: &'a [u8], directories: &mut Vec<Directory>)
     data.into()
 }
 
-fn decode_oid_stat(data: &[u8], hash_len: usize) -> Option<(OidStat, &[u8])> {
-    let (stat, data) = crate::decode::stat(data)?;
+fn decode_oid_stat(stat: entry::Stat, data: &[u8], hash_len: usize) -> Option<(OidStat, &[u8])> {
     let (hash, data) = split_at_pos(data, hash_len)?;
     Some((
         OidStat {
@@ -154,3 +155,77 @@ fn decode_oid_stat(data: &[u8], hash_len: usize) -> Option<(OidStat, &[u8])> {
         data,
     ))
 }
+
+impl UntrackedCache {
+    /// Return the stat information and object id of `.git/info/exclude`, if it was seen when the cache was last updated.
+    pub fn info_exclude(&self) -> Option<&OidStat> {
+        self.info_exclude.as_ref()
+    }
+
+    /// Return the stat information and object id of the file configured in `core.excludesFile`, if it was seen when the cache was last updated.
+    pub fn excludes_file(&self) -> Option<&OidStat> {
+        self.excludes_file.as_ref()
+    }
+
+    /// Serialize this instance to `out`, the inverse of [`decode()`], with hashes of kind `object_hash`.
+    ///
+    /// Like `git`, the stat information of invalid directories isn't written.
+    pub fn write_to(&self, mut out: impl std::io::Write, object_hash: gix_hash::Kind) -> Result<(), std::io::Error> {
+        let null = ObjectId::null(object_hash);
+        let null_oid_stat = OidStat {
+            stat: Default::default(),
+            id: null,
+        };
+        let info_exclude = self.info_exclude.as_ref().unwrap_or(&null_oid_stat);
+        let excludes_file = self.excludes_file.as_ref().unwrap_or(&null_oid_stat);
+
+        let mut data = Vec::new();
+        write_var_int(&mut data, self.identifier.len() as u64)?;
+        data.extend_from_slice(&self.identifier);
+        info_exclude.stat.write_to(&mut data)?;
+        excludes_file.stat.write_to(&mut data)?;
+        data.extend_from_slice(&self.dir_flags.to_be_bytes());
+        data.extend_from_slice(info_exclude.id.as_bytes());
+        data.extend_from_slice(excludes_file.id.as_bytes());
+        data.extend_from_slice(&self.exclude_filename_per_dir);
+        data.push(0);
+
+        write_var_int(&mut data, self.directories.len() as u64)?;
+        if !self.directories.is_empty() {
+            let mut valid = gix_bitmap::ewah::Vec::default();
+            let mut check_only = gix_bitmap::ewah::Vec::default();
+            let mut hash_valid = gix_bitmap::ewah::Vec::default();
+            let (mut stats, mut hashes) = (Vec::new(), Vec::new());
+            for (index, dir) in self.directories.iter().enumerate() {
+                write_var_int(&mut data, dir.untracked_entries.len() as u64)?;
+                write_var_int(&mut data, dir.sub_directories.len() as u64)?;
+                for name in std::iter::once(&dir.name).chain(&dir.untracked_entries) {
+                    data.extend_from_slice(name);
+                    data.push(0);
+                }
+
+                if dir.check_only {
+                    check_only.set(index);
+                }
+                if let Some(stat) = &dir.stat {
+                    valid.set(index);
+                    stat.write_to(&mut stats)?;
+                }
+                if let Some(id) = dir.exclude_file_oid.filter(|id| !id.is_null()) {
+                    hash_valid.set(index);
+                    hashes.extend_from_slice(id.as_bytes());
+                }
+            }
+            valid.write_to(&mut data)?;
+            check_only.write_to(&mut data)?;
+            hash_valid.write_to(&mut data)?;
+            data.extend_from_slice(&stats);
+            data.extend_from_slice(&hashes);
+            data.push(0);
+        }
+
+        out.write_all(&SIGNATURE)?;
+        out.write_all(&(u32::try_from(data.len()).expect("less than 4GB untracked cache extension")).to_be_bytes())?;
+        out.write_all(&data)
+    }
+}
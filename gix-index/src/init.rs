@@ -22,6 +22,7 @@ mod from_tree {
                 timestamp: filetime::FileTime::now(),
                 version: Version::V2,
                 entries: vec![],
+                source_offsets: Vec::new(),
                 path_backing: vec![],
                 is_sparse: false,
                 tree: None,
@@ -59,6 +60,7 @@ mod from_tree {
                 timestamp: filetime::FileTime::now(),
                 version: Version::V2,
                 entries,
+                source_offsets: Vec::new(),
                 path_backing,
                 is_sparse: false,
                 tree: None,
@@ -24,12 +24,17 @@ mod access;
 
 mod init;
 
+mod merge;
+
 ///
 pub mod decode;
 
 ///
 pub mod verify;
 
+///
+pub mod status;
+
 ///
 pub mod write;
 
@@ -72,6 +77,14 @@ pub struct File {
     pub(crate) checksum: Option<gix_hash::ObjectId>,
 }
 
+/// Mutable access to all entries of a [`State`], as obtained by [`State::entries_mut()`].
+///
+/// Entries can be altered one at a time through it. If that changed their order, for instance by altering their stage
+/// or by swapping them, they are sorted again once it's dropped to keep lookups by path working.
+pub struct EntriesMut<'a> {
+    state: &'a mut State,
+}
+
 /// The type to use and store paths to all entries.
 pub type PathStorage = Vec<u8>;
 /// The type to use and store paths to all entries, as reference
@@ -94,6 +107,12 @@ pub struct State {
     timestamp: FileTime,
     version: Version,
     entries: Vec<Entry>,
+    /// The byte offsets at which each of our `entries` starts in the file we were decoded from, followed by
+    /// the offset right past the last entry.
+    ///
+    /// It's empty unless we were decoded with [`track_source_offsets`](decode::Options::track_source_offsets) enabled,
+    /// or if entries were added or reordered since.
+    source_offsets: Vec<u32>,
     /// A memory area keeping all index paths, in full length, independently of the index version.
     ///
     /// Ranges into this storage are referred to by parts of `entries`.
@@ -151,6 +170,21 @@ pub(crate) mod util {
         (num, data).into()
     }
 
+    /// Write `value` as variable-length integer, the inverse of [`var_int()`].
+    pub fn write_var_int(mut out: impl std::io::Write, mut value: u64) -> std::io::Result<()> {
+        let mut buf = [0u8; 10];
+        let mut pos = buf.len() - 1;
+        buf[pos] = (value & 0x7f) as u8;
+        value >>= 7;
+        while value != 0 {
+            value -= 1;
+            pos -= 1;
+            buf[pos] = 0x80 | (value & 0x7f) as u8;
+            value >>= 7;
+        }
+        out.write_all(&buf[pos..])
+    }
+
     #[inline]
     pub fn read_u32(data: &[u8]) -> Option<(u32, &[u8])> {
         split_at_pos(data, 4).map(|(num, data)| (u32::from_be_bytes(num.try_into().unwrap()), data))
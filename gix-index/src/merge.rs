@@ -0,0 +1,95 @@
+use std::collections::BTreeSet;
+
+use bstr::{BStr, ByteSlice};
+
+use crate::{entry::Flags, Entry, State};
+
+impl State {
+    /// Perform a three-way merge of the stage 0 entries of `base`, `ours` and `theirs`, similar to `git read-tree -m`,
+    /// and return the result as new index.
+    ///
+    /// Paths which are the same in `ours` and `theirs`, or which changed only on one side compared to `base`, are resolved
+    /// at stage 0. All other paths are conflicting and are added with their `base` entry at stage 1, `ours` at stage 2
+    /// and `theirs` at stage 3, leaving out the sides on which the path doesn't exist.
+    /// A path that is a file on one side and a directory on the other is conflicting as well, so its file is added at
+    /// the stages of the sides that have it, while the entries within the directory are merged as usual.
+    ///
+    /// Note that entries are only compared by id and mode, and that no extension data is produced.
+    pub fn merge(base: &State, ours: &State, theirs: &State) -> State {
+        let paths: BTreeSet<&BStr> = [base, ours, theirs]
+            .iter()
+            .flat_map(|state| {
+                state
+                    .entries()
+                    .iter()
+                    .filter(|entry| entry.stage() == 0)
+                    .map(move |entry| entry.path(state))
+            })
+            .collect();
+
+        let merged: Vec<_> = paths
+            .into_iter()
+            .map(|path| {
+                let sides = [base, ours, theirs].map(|state| state.entry_by_path_and_stage(path, 0));
+                let [b, o, t] = sides;
+                let resolved = if is_same(o, t) {
+                    Some(o)
+                } else if is_same(b, o) {
+                    Some(t)
+                } else if is_same(b, t) {
+                    Some(o)
+                } else {
+                    None
+                };
+                (path, sides, resolved)
+            })
+            .collect();
+        let remaining_paths: BTreeSet<&BStr> = merged
+            .iter()
+            .filter(|(_, _, resolved)| !matches!(resolved, Some(None)))
+            .map(|(path, _, _)| *path)
+            .collect();
+
+        let mut out = State::new(ours.object_hash());
+        for (path, sides, resolved) in merged {
+            match resolved {
+                Some(None) => {}
+                Some(Some(entry)) if !has_paths_within(&remaining_paths, path) => {
+                    push_at_stage(&mut out, entry, path, 0);
+                }
+                _ => {
+                    for (stage, entry) in (1..).zip(sides) {
+                        if let Some(entry) = entry {
+                            push_at_stage(&mut out, entry, path, stage);
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Return `true` if `paths` contains a path within the directory `path`.
+fn has_paths_within(paths: &BTreeSet<&BStr>, path: &BStr) -> bool {
+    let mut dir = path.to_owned();
+    dir.push(b'/');
+    paths
+        .range::<&BStr, _>(dir.as_bstr()..)
+        .next()
+        .map_or(false, |other| other.starts_with(&dir))
+}
+
+/// Return `true` if `a` and `b` are both missing, or if they have the same content.
+fn is_same(a: Option<&Entry>, b: Option<&Entry>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.id == b.id && a.mode == b.mode,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn push_at_stage(out: &mut State, entry: &Entry, path: &BStr, stage: u32) {
+    let flags = (entry.flags - Flags::STAGE_MASK) | Flags::from_bits_retain(stage << 12);
+    out.dangerously_push_entry(entry.stat, entry.id, flags, entry.mode, path);
+}
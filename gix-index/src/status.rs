@@ -0,0 +1,232 @@
+use std::{collections::BTreeMap, io, path::Path};
+
+use bstr::{BStr, BString};
+use gix_object::TreeRefIter;
+
+use crate::{
+    entry::{self, mode, Flags, Mode, Stat},
+    Entry, State,
+};
+
+/// The result of [`State::entry_matches_stat()`], telling how an entry relates to its file in the worktree
+/// judging only by the file's metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatMatch {
+    /// The stat information matches and isn't racy, so the file is known to be unchanged without looking at its content.
+    Unchanged,
+    /// The file is known to be modified, for example because its size or executable bit changed.
+    Modified,
+    /// The kind of file changed, like a file that was replaced by a symlink or a directory.
+    TypeChange,
+    /// The stat information can't be trusted to decide, so the content has to be hashed and compared to the entry's id.
+    ///
+    /// This is the case if some stat information changed but not the size, if the entry is racy, or if
+    /// the entry's size is 0 without it being the empty blob.
+    /// For submodules it's the `HEAD` of the submodule that has to be compared.
+    NeedsContentCheck,
+}
+
+/// Options for use in [`State::entry_matches_stat()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The options for comparing the stat information of the entry with the one from disk.
+    pub stat: entry::stat::Options,
+    /// If false, symlinks are stored as normal files on disk, as configured by `core.symlinks`.
+    ///
+    /// Default `true`.
+    pub has_symlinks: bool,
+    /// If false, the executable bit of files on disk isn't trusted, as configured by `core.fileMode`.
+    ///
+    /// Default `true`.
+    pub executable_bit: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            stat: Default::default(),
+            has_symlinks: true,
+            executable_bit: true,
+        }
+    }
+}
+
+/// A path along with its status in the index and in the worktree, as printed by `git status --porcelain`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct PorcelainEntry {
+    /// The path relative to the root of the worktree.
+    pub path: BString,
+    /// The status of the index compared to the `HEAD` tree, like `b'A'` for added paths, or `b' '` if unchanged.
+    ///
+    /// For unmerged paths, it describes what happened on our side.
+    pub index: u8,
+    /// The status of the worktree compared to the index, like `b'M'` for modified files, or `b' '` if unchanged.
+    ///
+    /// For unmerged paths, it describes what happened on their side.
+    pub worktree: u8,
+}
+
+impl PorcelainEntry {
+    /// Return the two-letter status code, like `"A "` or `" M"`.
+    pub fn code(&self) -> [u8; 2] {
+        [self.index, self.worktree]
+    }
+}
+
+impl State {
+    /// Decide quickly if `entry` is unchanged, modified or changed its type compared to the file in the worktree
+    /// with `metadata` as obtained by [`std::fs::symlink_metadata()`], using only the stat information and the
+    /// [timestamp][State::timestamp()] of this index.
+    ///
+    /// Like git, a changed size marks the entry as modified right away, while other changes to the stat information,
+    /// a racy entry, or an entry of size 0 that isn't the empty blob require [a content check][StatMatch::NeedsContentCheck].
+    pub fn entry_matches_stat(&self, entry: &Entry, metadata: &std::fs::Metadata, options: Options) -> StatMatch {
+        match entry
+            .mode
+            .change_to_match_fs(metadata, options.has_symlinks, options.executable_bit)
+        {
+            Some(mode::Change::Type { .. }) => return StatMatch::TypeChange,
+            Some(mode::Change::ExecutableBit) => return StatMatch::Modified,
+            None => {}
+        }
+        if matches!(entry.mode, Mode::COMMIT | Mode::DIR) {
+            return StatMatch::NeedsContentCheck;
+        }
+        let stat = match Stat::from_fs(metadata) {
+            Ok(stat) => stat,
+            Err(_) => return StatMatch::NeedsContentCheck,
+        };
+
+        let is_smudged = entry.stat.size == 0 && entry.id != gix_hash::ObjectId::empty_blob(entry.id.kind());
+        if is_smudged || !entry.stat.matches(&stat, options.stat) {
+            return if entry.stat.size != 0 && entry.stat.size != stat.size {
+                StatMatch::Modified
+            } else {
+                StatMatch::NeedsContentCheck
+            };
+        }
+        if entry.stat.is_racy(self.timestamp, options.stat) {
+            return StatMatch::NeedsContentCheck;
+        }
+        StatMatch::Unchanged
+    }
+
+    /// Compare this index to the tree with id `head_tree` and to the files in the worktree at `root`, and return all paths that
+    /// differ in either of them along with their two-letter status code, sorted by path.
+    /// Sub-trees are looked up with `find`, just like in [`from_tree()`][State::from_tree()].
+    ///
+    /// The codes are the same as the ones of `git status --porcelain`, but as limitations,
+    /// untracked files aren't listed, renames aren't detected and worktree files are compared by hashing them as they are,
+    /// without applying any filters. Entries marked as skip-worktree or within sparse directories aren't checked in
+    /// the worktree, and submodules are never reported as modified in the worktree as their `HEAD` isn't looked at.
+    pub fn porcelain_status<Find>(
+        &self,
+        head_tree: &gix_hash::oid,
+        find: Find,
+        root: &Path,
+    ) -> io::Result<Vec<PorcelainEntry>>
+    where
+        Find: for<'a> FnMut(&gix_hash::oid, &'a mut Vec<u8>) -> Option<TreeRefIter<'a>>,
+    {
+        let head = State::from_tree(head_tree, find).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        let mut out = BTreeMap::<&BStr, [u8; 2]>::new();
+
+        for entry in head.entries() {
+            let path = entry.path(&head);
+            match self.entry_by_path_and_stage(path, 0) {
+                Some(ours) if is_type_change(ours.mode, entry.mode) => {
+                    out.insert(path, [b'T', b' ']);
+                }
+                Some(ours) if ours.id != entry.id || ours.mode != entry.mode => {
+                    out.insert(path, [b'M', b' ']);
+                }
+                Some(_) => {}
+                None if self.entry_by_path(path).is_none() => {
+                    out.insert(path, [b'D', b' ']);
+                }
+                None => {}
+            }
+        }
+
+        let mut unmerged = BTreeMap::<&BStr, [bool; 3]>::new();
+        for entry in self.entries() {
+            let path = entry.path(self);
+            let stage = entry.stage();
+            if stage != 0 {
+                unmerged.entry(path).or_default()[stage as usize - 1] = true;
+                continue;
+            }
+            if head.entry_by_path_and_stage(path, 0).is_none() {
+                out.insert(path, [b'A', b' ']);
+            }
+            if entry.flags.contains(Flags::SKIP_WORKTREE) || entry.mode.is_sparse() {
+                continue;
+            }
+            if let Some(worktree) = self.worktree_status(entry, path, root)? {
+                out.entry(path).or_insert([b' ', b' '])[1] = worktree;
+            }
+        }
+        for (path, stages) in unmerged {
+            let code = match stages {
+                [true, false, false] => *b"DD",
+                [false, true, false] => *b"AU",
+                [true, true, false] => *b"UD",
+                [false, false, true] => *b"UA",
+                [true, false, true] => *b"DU",
+                [false, true, true] => *b"AA",
+                _ => *b"UU",
+            };
+            out.insert(path, code);
+        }
+
+        Ok(out
+            .into_iter()
+            .map(|(path, [index, worktree])| PorcelainEntry {
+                path: path.to_owned(),
+                index,
+                worktree,
+            })
+            .collect())
+    }
+
+    /// Return the worktree status of `entry` at `path` relative to `root`, or `None` if it's unchanged.
+    fn worktree_status(&self, entry: &Entry, path: &BStr, root: &Path) -> io::Result<Option<u8>> {
+        let location = root.join(gix_path::from_bstr(path));
+        let metadata = match std::fs::symlink_metadata(&location) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Some(b'D')),
+            Err(err) => return Err(err),
+        };
+        let options = Options {
+            executable_bit: cfg!(unix),
+            ..Default::default()
+        };
+        match self.entry_matches_stat(entry, &metadata, options) {
+            StatMatch::Unchanged => return Ok(None),
+            StatMatch::Modified => return Ok(Some(b'M')),
+            StatMatch::TypeChange => return Ok(Some(b'T')),
+            StatMatch::NeedsContentCheck => {}
+        }
+        let data: Vec<u8> = match entry.mode {
+            Mode::COMMIT => return Ok(None),
+            Mode::SYMLINK if metadata.file_type().is_symlink() => {
+                gix_path::into_bstr(std::fs::read_link(&location)?).into_owned().into()
+            }
+            _ => std::fs::read(&location)?,
+        };
+        let id = gix_object::compute_hash(self.object_hash(), gix_object::Kind::Blob, &data);
+        Ok((id != entry.id).then_some(b'M'))
+    }
+}
+
+/// Return true if `a` and `b` are different kinds of entries, like a file and a symlink, instead of only differing in their executable bit.
+fn is_type_change(a: Mode, b: Mode) -> bool {
+    let kind = |mode: Mode| {
+        if mode == Mode::FILE_EXECUTABLE {
+            Mode::FILE
+        } else {
+            mode
+        }
+    };
+    kind(a) != kind(b)
+}
@@ -19,6 +19,8 @@ pub enum Extensions {
         tree_cache: bool,
         /// Write the end-of-index-entry extension.
         end_of_index_entry: bool,
+        /// Write the untracked-cache extension, if present.
+        untracked_cache: bool,
     },
     /// Write no optional extension at all for what should be the smallest possible index
     None,
@@ -33,9 +35,11 @@ impl Extensions {
             Extensions::Given {
                 tree_cache,
                 end_of_index_entry,
+                untracked_cache,
             } => match signature {
                 extension::tree::SIGNATURE => tree_cache,
                 extension::end_of_index_entry::SIGNATURE => end_of_index_entry,
+                extension::untracked_cache::SIGNATURE => untracked_cache,
                 _ => &false,
             }
             .then(|| signature),
@@ -50,13 +54,25 @@ impl Extensions {
 pub struct Options {
     /// Configures which extensions to write
     pub extensions: Extensions,
+    /// The version to write, or `None` to write the lowest version that can represent all entries.
+    ///
+    /// Note that `V2` is upgraded to `V3` if entries have extended flags, as these couldn't be stored otherwise.
+    /// The version that was actually written is returned by [`write_to()`][State::write_to()].
+    pub version: Option<Version>,
 }
 
 impl State {
-    /// Serialize this instance to `out` with [`options`][Options].
-    pub fn write_to(&self, out: impl std::io::Write, Options { extensions }: Options) -> std::io::Result<Version> {
+    /// Serialize this instance to `out` with [`options`][Options], and return the version that was written.
+    pub fn write_to(
+        &self,
+        out: impl std::io::Write,
+        Options { extensions, version }: Options,
+    ) -> std::io::Result<Version> {
         let _span = gix_features::trace::detail!("gix_index::State::write()");
-        let version = self.detect_required_version();
+        let version = match version {
+            None | Some(Version::V2) => self.detect_required_version(),
+            Some(version @ (Version::V3 | Version::V4)) => version,
+        };
 
         let mut write = CountBytes::new(out);
         let num_entries: u32 = self
@@ -73,7 +89,7 @@ impl State {
             .expect("definitely not too many entries");
 
         let offset_to_entries = header(&mut write, version, num_entries - removed_entries)?;
-        let offset_to_extensions = entries(&mut write, self, offset_to_entries)?;
+        let offset_to_extensions = entries(&mut write, self, version, offset_to_entries)?;
         let (extension_toc, out) = self.write_extensions(write, offset_to_extensions, extensions)?;
 
         if num_entries > 0
@@ -104,6 +120,14 @@ impl State {
                     .should_write(extension::tree::SIGNATURE)
                     .and_then(|signature| self.tree().map(|tree| tree.write_to(write).map(|_| signature)))
             },
+            &|write| {
+                extensions
+                    .should_write(extension::untracked_cache::SIGNATURE)
+                    .and_then(|signature| {
+                        self.untracked()
+                            .map(|untracked| untracked.write_to(write, self.object_hash).map(|_| signature))
+                    })
+            },
             &|write| {
                 self.is_sparse()
                     .then(|| extension::sparse::write_to(write).map(|_| extension::sparse::SIGNATURE))
@@ -151,11 +175,23 @@ fn header<T: std::io::Write>(
     Ok(out.count)
 }
 
-fn entries<T: std::io::Write>(out: &mut CountBytes<T>, state: &State, header_size: u32) -> Result<u32, std::io::Error> {
+fn entries<T: std::io::Write>(
+    out: &mut CountBytes<T>,
+    state: &State,
+    version: Version,
+    header_size: u32,
+) -> Result<u32, std::io::Error> {
+    let mut previous_path = None;
     for entry in state.entries() {
         if entry.flags.contains(entry::Flags::REMOVE) {
             continue;
         }
+        if version == Version::V4 {
+            let path = entry.path(state);
+            entry.write_to_with_delta_path(&mut *out, path, previous_path.unwrap_or_default())?;
+            previous_path = Some(path);
+            continue;
+        }
         entry.write_to(&mut *out, state)?;
         match (out.count - header_size) % 8 {
             0 => {}
@@ -0,0 +1,37 @@
+#!/bin/bash
+set -eu -o pipefail
+
+export GIT_INDEX_VERSION=2;
+git init -q
+git config index.threads 1
+
+echo a > a
+echo b > b
+echo c > c
+echo d > d
+echo e > e
+echo f > f
+git add .
+git commit -q -m "init"
+
+echo added > added
+git add added
+
+echo modified >> a
+
+echo staged >> b
+git add b
+echo unstaged >> b
+
+rm c
+git rm -q --cached d
+
+rm f
+ln -s a f
+git add f
+
+echo staged-then-modified > new
+git add new
+echo modified >> new
+
+git status --porcelain --untracked-files=no > status.baseline
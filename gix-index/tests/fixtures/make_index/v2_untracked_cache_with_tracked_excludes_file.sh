@@ -0,0 +1,16 @@
+#!/bin/bash
+set -eu -o pipefail
+
+export GIT_INDEX_VERSION=2
+git init -q
+git config core.excludesFile excludes
+git config core.untrackedCache true
+
+echo "*.tmp" > excludes
+# an old modification time keeps it apart from the creation time, which is always the current time
+touch -m -d @1000000000 excludes
+git add excludes
+git update-index --untracked-cache
+git status --porcelain >/dev/null
+
+git ls-files --debug > ls-files-debug.txt
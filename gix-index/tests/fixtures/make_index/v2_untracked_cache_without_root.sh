@@ -0,0 +1,9 @@
+#!/bin/bash
+set -eu -o pipefail
+
+export GIT_INDEX_VERSION=2
+git init -q
+
+touch a
+git add a
+git update-index --untracked-cache
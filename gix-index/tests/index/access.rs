@@ -1,4 +1,4 @@
-use crate::index::Fixture;
+use crate::index::{hex_to_id, Fixture};
 
 #[test]
 fn entry_by_path_and_stage() {
@@ -41,6 +41,359 @@ fn entry_by_path_with_conflicting_file() {
     );
 }
 
+#[test]
+fn has_prefix() {
+    let file = Fixture::Generated("v4_more_files_IEOT").open();
+    for prefix in ["d/", "d", "d/last/", "d/last/12", "x", "a", ""] {
+        assert!(file.has_prefix(prefix.into()), "{prefix:?} is a prefix of at least one entry");
+        assert!(file.prefixed_entries(prefix.into()).is_some(), "{prefix:?}");
+    }
+    for prefix in ["zz", "d/last/2", "d/z", "0", "d/a/"] {
+        assert!(!file.has_prefix(prefix.into()), "{prefix:?} isn't a prefix of any entry");
+        assert!(file.prefixed_entries(prefix.into()).is_none(), "{prefix:?}");
+    }
+
+    let file = Fixture::Loose("conflicting-file").open();
+    assert!(file.has_prefix("fil".into()), "entries in conflict are found as well");
+
+    let state = gix_index::State::new(gix_hash::Kind::Sha1);
+    assert!(!state.has_prefix("".into()), "nothing matches without entries");
+}
+
+#[test]
+fn prefixed_entries_icase() {
+    let file = Fixture::Generated("v4_more_files_IEOT").open();
+    let paths = |prefix: &str| {
+        file.prefixed_entries_icase(prefix.into())
+            .map(|e| e.path(&file).to_string())
+            .collect::<Vec<_>>()
+    };
+    let expected = ["d/a", "d/b", "d/c", "d/last/123", "d/last/34", "d/last/6"];
+    assert_eq!(paths("D/"), expected);
+    assert_eq!(paths("d/"), expected, "the exact case matches as well");
+    assert_eq!(paths("D/LAST/"), ["d/last/123", "d/last/34", "d/last/6"]);
+    assert_eq!(paths("X"), ["x"]);
+    assert_eq!(paths("Q"), Vec::<String>::new(), "no match yields nothing");
+    assert_eq!(paths("").len(), file.entries().len());
+
+    let file = Fixture::Loose("conflicting-file").open();
+    let entries: Vec<_> = file.prefixed_entries_icase("FIL".into()).collect();
+    assert_eq!(
+        entries.iter().map(|e| (e.path(&file), e.stage())).collect::<Vec<_>>(),
+        [("file".into(), 1), ("file".into(), 2), ("file".into(), 3)],
+        "all stages are returned"
+    );
+
+    let mut state = gix_index::State::new(gix_hash::Kind::Sha1);
+    for path in ["d/lower", "E", "D/upper", "dog", ""] {
+        state.dangerously_push_entry(
+            Default::default(),
+            gix_hash::Kind::Sha1.null(),
+            gix_index::entry::Flags::empty(),
+            gix_index::entry::Mode::FILE,
+            path.into(),
+        );
+    }
+    state.sort_entries();
+    assert_eq!(
+        state
+            .prefixed_entries_icase("d/".into())
+            .map(|e| e.path(&state))
+            .collect::<Vec<_>>(),
+        ["D/upper", "d/lower"],
+        "matches aren't adjacent in the case-sensitively sorted index"
+    );
+    assert_eq!(
+        state.prefixed_entries_icase("e".into()).count(),
+        1,
+        "invalid entries with empty paths don't get in the way"
+    );
+}
+
+#[test]
+fn remove_entries_and_compact_path_backing() -> crate::Result {
+    let mut state = gix_index::State::new(gix_hash::Kind::Sha1);
+    let paths: Vec<_> = (0..100).map(|n| format!("dir/file-{n:03}")).collect();
+    for path in &paths {
+        state.dangerously_push_entry(
+            Default::default(),
+            gix_hash::Kind::Sha1.null(),
+            gix_index::entry::Flags::empty(),
+            gix_index::entry::Mode::FILE,
+            path.as_str().into(),
+        );
+    }
+    state.sort_entries();
+    let full_len = state.path_backing().len();
+
+    state.remove_entries(|idx, path, _entry| {
+        assert_eq!(path, paths[idx], "indices are the ones before removal");
+        idx % 3 != 0
+    });
+    assert_eq!(state.entries().len(), 34);
+    assert_eq!(
+        state.path_backing().len(),
+        full_len,
+        "the paths of removed entries are still present"
+    );
+
+    state.compact_path_backing();
+    let expected: Vec<_> = paths.iter().step_by(3).map(String::as_str).collect();
+    assert_eq!(
+        state.path_backing().len(),
+        expected.iter().map(|p| p.len()).sum::<usize>(),
+        "only the paths of remaining entries are kept"
+    );
+    assert_eq!(
+        state.entries().iter().map(|e| e.path(&state)).collect::<Vec<_>>(),
+        expected
+    );
+    state.verify_entries()?;
+    for path in expected {
+        assert!(
+            state.entry_by_path_and_stage(path.into(), 0).is_some(),
+            "lookups by path still work"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn entry_index_by_path_and_stage_with_unsorted_tail() {
+    let mut file = Fixture::Generated("v4_more_files_IEOT").open();
+    let sorted_len = file.entries().len();
+    let entry = file.entries()[0].clone();
+    file.dangerously_push_entry(entry.stat, entry.id, entry.flags, entry.mode, "a-new-entry".into());
+    assert_eq!(
+        file.entry_index_by_path_and_stage("a-new-entry".into(), 0),
+        None,
+        "the pushed entry isn't found with a binary search over the unsorted entries"
+    );
+    assert_eq!(
+        file.entry_index_by_path_and_stage_with_unsorted_tail("a-new-entry".into(), 0, sorted_len),
+        Some(sorted_len),
+        "but it is found by scanning the unsorted tail"
+    );
+    assert_eq!(
+        file.entry_index_by_path_and_stage_with_unsorted_tail("a-new-entry".into(), 1, sorted_len),
+        None,
+        "the stage must match as well"
+    );
+    for (idx, entry) in file.entries()[..sorted_len].iter().enumerate() {
+        assert_eq!(
+            file.entry_index_by_path_and_stage_with_unsorted_tail(entry.path(&file), entry.stage(), sorted_len),
+            Some(idx),
+            "sorted entries are found as before"
+        );
+    }
+    assert_eq!(
+        file.entry_index_by_path_and_stage_with_unsorted_tail("missing".into(), 0, sorted_len),
+        None
+    );
+}
+
+#[test]
+fn prefixed_entries_with_stage() {
+    let file = Fixture::Loose("conflicting-file").open();
+    let entries: Vec<_> = file.prefixed_entries_with_stage("fil".into(), 2).collect();
+    assert_eq!(entries.len(), 1, "only our side of the conflict");
+    assert_eq!(entries[0].path(&file), "file");
+    assert_eq!(entries[0].stage(), 2);
+    assert_eq!(
+        Some(entries[0]),
+        file.entry_by_path_and_stage("file".into(), 2),
+        "it's the same entry"
+    );
+
+    assert_eq!(
+        file.prefixed_entries_with_stage("fil".into(), 0).count(),
+        0,
+        "there is no stage 0 during a conflict"
+    );
+    assert_eq!(file.prefixed_entries_with_stage("".into(), 3).count(), 1);
+    assert_eq!(
+        file.prefixed_entries_with_stage("missing".into(), 2).count(),
+        0,
+        "no match yields nothing"
+    );
+}
+
+#[test]
+fn referenced_blob_oids() {
+    let file = Fixture::Generated("v2_all_file_kinds").open();
+    let oids = file.referenced_blob_oids();
+    assert_eq!(oids.len(), 2, "empty files are deduplicated, the submodule is not a blob");
+    assert!(oids.contains(&hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391")));
+    assert!(
+        oids.contains(&hex_to_id("2e65efe2a145dda7ee51d1741299f848e5bf752e")),
+        "symlink targets are stored as blobs"
+    );
+
+    let file = Fixture::Loose("conflicting-file").open();
+    assert!(
+        file.referenced_blob_oids().is_empty(),
+        "only stage 0 entries are considered"
+    );
+}
+
+#[test]
+fn collect_paths_where() {
+    let paths = {
+        let file = Fixture::Generated("v2_all_file_kinds").open();
+        file.collect_paths_where(|e| e.mode == gix_index::entry::Mode::FILE_EXECUTABLE)
+    };
+    assert_eq!(paths, ["b"], "the paths outlive the index they were collected from");
+}
+
+#[test]
+fn entry_at_source_offset() {
+    let opts = gix_index::decode::Options {
+        track_source_offsets: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        Fixture::Loose("conflicting-file").open().entry_at_source_offset(12),
+        None,
+        "offsets aren't tracked by default"
+    );
+    let file = Fixture::Loose("conflicting-file").open_opts(opts);
+    assert_eq!(file.entry_at_source_offset(0), None, "the header isn't an entry");
+    for (offset, expected_stage) in [(12, 1), (83, 1), (84, 2), (155, 2), (156, 3), (227, 3)] {
+        assert_eq!(
+            file.entry_at_source_offset(offset).map(|e| e.stage()),
+            Some(expected_stage),
+            "each entry takes 72 bytes, including the padding, right after the header"
+        );
+    }
+    assert_eq!(file.entry_at_source_offset(228), None, "past the last entry");
+
+    for fixture in [Fixture::Generated("v2_more_files"), Fixture::Generated("v4_more_files_IEOT")] {
+        let mut file = fixture.open_opts(opts);
+        let data = std::fs::read(fixture.to_path()).unwrap();
+        let mut seen = Vec::<&gix_index::Entry>::new();
+        for offset in 0..data.len() {
+            if let Some(entry) = file.entry_at_source_offset(offset) {
+                if seen.last().map_or(true, |last| !std::ptr::eq(*last, entry)) {
+                    seen.push(entry);
+                }
+            }
+        }
+        assert_eq!(seen.len(), file.entries().len(), "every entry is found, in order");
+        assert!(seen.into_iter().zip(file.entries()).all(|(a, b)| std::ptr::eq(a, b)));
+
+        file.sort_entries();
+        assert_eq!(
+            file.entry_at_source_offset(12),
+            None,
+            "the mapping is lost once entries may have been reordered"
+        );
+    }
+}
+
+#[test]
+fn mark_possibly_dirty() {
+    let mut file = Fixture::Generated("v4_more_files_IEOT").open();
+    for entry in &mut file.entries_mut() {
+        entry.flags.insert(gix_index::entry::Flags::FSMONITOR_VALID);
+    }
+
+    file.mark_possibly_dirty(&["a".into(), "d/last/".into(), "does-not-exist".into()]);
+    let needs_check: Vec<_> = file
+        .entries()
+        .iter()
+        .filter(|e| !e.flags.contains(gix_index::entry::Flags::FSMONITOR_VALID))
+        .map(|e| e.path(&file))
+        .collect();
+    assert_eq!(
+        needs_check,
+        ["a", "d/last/123", "d/last/34", "d/last/6"],
+        "only the given paths and entries within the given directories have to be checked"
+    );
+
+    let mut file = Fixture::Loose("conflicting-file").open();
+    for entry in &mut file.entries_mut() {
+        entry.flags.insert(gix_index::entry::Flags::FSMONITOR_VALID);
+    }
+    let path = file.entries()[0].path(&file).to_owned();
+    file.mark_possibly_dirty(&[path.as_ref()]);
+    assert!(
+        file.entries()
+            .iter()
+            .all(|e| !e.flags.contains(gix_index::entry::Flags::FSMONITOR_VALID)),
+        "all stages of a path are affected"
+    );
+}
+
+mod entries_mut {
+    use gix_index::{
+        entry::{Flags, Mode},
+        Entry,
+    };
+
+    use crate::index::Fixture;
+
+    #[test]
+    fn altering_modes_keeps_the_order() {
+        let mut file = Fixture::Generated("v2_more_files").open_opts(gix_index::decode::Options {
+            track_source_offsets: true,
+            ..Default::default()
+        });
+        let paths: Vec<_> = file.entries().iter().map(|e| e.path(&file).to_owned()).collect();
+        for entry in &mut file.entries_mut() {
+            entry.mode = Mode::FILE_EXECUTABLE;
+        }
+        assert!(file.verify_entries().is_ok());
+        assert!(file.entries().iter().all(|e| e.mode == Mode::FILE_EXECUTABLE));
+        assert_eq!(
+            file.entries().iter().map(|e| e.path(&file).to_owned()).collect::<Vec<_>>(),
+            paths
+        );
+        assert!(
+            file.entry_at_source_offset(12).is_some(),
+            "entries weren't sorted, hence the mapping to their source is still available"
+        );
+    }
+
+    #[test]
+    fn changing_stages_sorts_entries_on_drop() {
+        let mut file = Fixture::Loose("conflicting-file").open();
+        let stage_of = |entry: &Entry| entry.stage();
+        assert_eq!(file.entries().iter().map(stage_of).collect::<Vec<_>>(), [1, 2, 3]);
+        let ids: Vec<_> = file.entries().iter().map(|e| e.id).collect();
+        {
+            let mut entries = file.entries_mut();
+            for (idx, stage) in [(0, 3), (2, 1)] {
+                entries[idx].flags.remove(Flags::STAGE_MASK);
+                entries[idx].flags.insert(Flags::from_bits_retain(stage << 12));
+            }
+        }
+        assert!(file.verify_entries().is_ok(), "the order was restored");
+        assert_eq!(file.entries().iter().map(stage_of).collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(
+            file.entries().iter().map(|e| e.id).rev().collect::<Vec<_>>(),
+            ids,
+            "the entries that changed their stage also changed their place"
+        );
+    }
+
+    #[test]
+    fn swapping_entries_sorts_them_on_drop() {
+        let mut file = Fixture::Generated("v2_more_files").open();
+        let paths: Vec<_> = file.entries().iter().map(|e| e.path(&file).to_owned()).collect();
+        {
+            let mut entries = file.entries_mut();
+            let first = entries[0].clone();
+            entries[0] = entries[1].clone();
+            entries[1] = first;
+        }
+        assert!(file.verify_entries().is_ok(), "the order was restored");
+        assert_eq!(
+            file.entries().iter().map(|e| e.path(&file).to_owned()).collect::<Vec<_>>(),
+            paths
+        );
+    }
+}
+
 #[test]
 fn sort_entries() {
     let mut file = Fixture::Generated("v4_more_files_IEOT").open();
@@ -92,6 +445,102 @@ fn sort_entries() {
     check_prefix(&file, "x", &["x"]);
 }
 
+#[test]
+fn sort_entries_is_stable() {
+    let mut state = gix_index::State::new(gix_hash::Kind::Sha1);
+    let ids = [
+        hex_to_id("1111111111111111111111111111111111111111"),
+        hex_to_id("2222222222222222222222222222222222222222"),
+        hex_to_id("3333333333333333333333333333333333333333"),
+    ];
+    for (path, id) in [("b", ids[0]), ("a", ids[1]), ("b", ids[2]), ("a", ids[0])] {
+        state.dangerously_push_entry(
+            Default::default(),
+            id,
+            gix_index::entry::Flags::empty(),
+            gix_index::entry::Mode::FILE,
+            path.into(),
+        );
+    }
+    assert!(!state.is_sorted());
+
+    state.sort_entries();
+    assert!(state.is_sorted(), "entries with equal path and stage are still sorted");
+    assert!(state.verify_entries().is_err(), "but they are invalid nonetheless");
+    assert_eq!(
+        state
+            .entries()
+            .iter()
+            .map(|e| (e.path(&state).to_string(), e.id))
+            .collect::<Vec<_>>(),
+        [
+            ("a".into(), ids[1]),
+            ("a".into(), ids[0]),
+            ("b".into(), ids[0]),
+            ("b".into(), ids[2])
+        ],
+        "entries that compare equal keep the order in which they were pushed"
+    );
+}
+
+#[test]
+fn is_sorted() {
+    let mut file = Fixture::Generated("v4_more_files_IEOT").open();
+    assert!(file.is_sorted());
+
+    let entry = file.entry(0).clone();
+    file.dangerously_push_entry(entry.stat, entry.id, entry.flags, entry.mode, "a-late-entry".into());
+    assert!(!file.is_sorted());
+    file.sort_entries();
+    assert!(file.is_sorted());
+
+    assert!(gix_index::State::new(gix_hash::Kind::Sha1).is_sorted());
+}
+
+#[test]
+fn entries_under_dir() {
+    let mut file = Fixture::Generated("v4_more_files_IEOT").open();
+    let entry = file.entry(0).clone();
+    for path in ["dog", "d-file"] {
+        file.dangerously_push_entry(entry.stat, entry.id, entry.flags, entry.mode, path.into());
+    }
+    file.sort_entries();
+
+    check_prefix(
+        &file,
+        "d",
+        &[
+            "d-file",
+            "d/a",
+            "d/b",
+            "d/c",
+            "d/last/123",
+            "d/last/34",
+            "d/last/6",
+            "dog",
+        ],
+    );
+    let under_dir = |dir: &str| {
+        file.entries_under_dir(dir.into())
+            .map(|entries| entries.iter().map(|e| e.path(&file)).collect::<Vec<_>>())
+    };
+    let expected = ["d/a", "d/b", "d/c", "d/last/123", "d/last/34", "d/last/6"];
+    assert_eq!(
+        under_dir("d").expect("present"),
+        expected,
+        "only actual descendants of the directory match"
+    );
+    assert_eq!(
+        under_dir("d/").expect("present"),
+        expected,
+        "a trailing slash is the same"
+    );
+    assert_eq!(under_dir("d/last").expect("present"), ["d/last/123", "d/last/34", "d/last/6"]);
+    assert_eq!(under_dir("d/las"), None, "partial components don't match");
+    assert_eq!(under_dir("dog"), None, "files aren't directories");
+    assert_eq!(under_dir("").expect("present").len(), file.entries().len());
+}
+
 fn check_prefix(index: &gix_index::State, prefix: &str, expected: &[&str]) {
     assert_eq!(
         index
@@ -0,0 +1,57 @@
+use gix_index::entry::Flags;
+
+use crate::index::Fixture;
+
+fn conflicting_entry() -> gix_index::Entry {
+    let file = Fixture::Loose("conflicting-file").open();
+    file.entry_by_path_and_stage("file".into(), 2)
+        .expect("our side of the conflict")
+        .clone()
+}
+
+#[test]
+fn assume_valid_roundtrip() {
+    let mut entry = conflicting_entry();
+    assert!(!entry.is_assume_valid());
+    entry.set_assume_valid(true);
+    assert!(entry.is_assume_valid());
+    assert_eq!(entry.stage(), 2, "the stage isn't affected");
+    assert!(!entry.flags.contains(Flags::EXTENDED), "it's not an extended flag");
+    entry.set_assume_valid(false);
+    assert!(!entry.is_assume_valid());
+    assert_eq!(entry.stage(), 2);
+}
+
+#[test]
+fn intent_to_add_roundtrip() {
+    let mut entry = conflicting_entry();
+    assert!(!entry.is_intent_to_add());
+    entry.set_intent_to_add(true);
+    assert!(entry.is_intent_to_add());
+    assert!(entry.flags.contains(Flags::EXTENDED), "it needs to be stored in extended flags");
+    assert_eq!(entry.stage(), 2, "the stage isn't affected");
+    entry.set_intent_to_add(false);
+    assert!(!entry.is_intent_to_add());
+    assert!(!entry.flags.contains(Flags::EXTENDED));
+    assert_eq!(entry.stage(), 2);
+}
+
+#[test]
+fn skip_worktree_roundtrip() {
+    let mut entry = conflicting_entry();
+    assert!(!entry.is_skip_worktree());
+    entry.set_skip_worktree(true);
+    entry.set_intent_to_add(true);
+    assert!(entry.is_skip_worktree());
+    assert_eq!(entry.stage(), 2, "the stage isn't affected");
+
+    entry.set_intent_to_add(false);
+    assert!(
+        entry.flags.contains(Flags::EXTENDED),
+        "extended flags are still needed for skip-worktree"
+    );
+    entry.set_skip_worktree(false);
+    assert!(!entry.is_skip_worktree());
+    assert!(!entry.flags.contains(Flags::EXTENDED));
+    assert_eq!(entry.stage(), 2);
+}
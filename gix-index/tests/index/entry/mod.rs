@@ -1,3 +1,4 @@
+mod flags;
 mod mode;
 mod stat;
 mod time;
@@ -178,6 +178,53 @@ fn untr_extension_with_oids() {
     assert!(file.untracked().is_some());
 }
 
+#[test]
+fn untr_extension_without_root_directory() {
+    let file = Fixture::Generated("v2_untracked_cache_without_root").open();
+    assert_eq!(file.version(), Version::V2);
+
+    assert!(
+        file.untracked().is_some(),
+        "git writes the extension without any directory until the first `git status`"
+    );
+}
+
+#[test]
+fn untr_extension_stat_times_match_git() -> crate::Result {
+    let index_path = crate::fixture_index_path("v2_untracked_cache_with_tracked_excludes_file");
+    let file = gix_index::File::at(&index_path, gix_hash::Kind::Sha1, Default::default())?;
+    let debug = std::fs::read_to_string(index_path.parent().unwrap().parent().unwrap().join("ls-files-debug.txt"))?;
+    let time_of = |field: &str| -> entry::stat::Time {
+        let value = debug
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(field))
+            .expect("field is present");
+        let (secs, nsecs) = value.split_once(':').expect("secs:nsecs");
+        entry::stat::Time {
+            secs: secs.parse().unwrap(),
+            nsecs: nsecs.parse().unwrap(),
+        }
+    };
+    let (ctime, mtime) = (time_of("ctime: "), time_of("mtime: "));
+    assert_ne!(ctime, mtime, "the fixture makes sure both are different");
+
+    let entry = &file.entries()[0];
+    assert_eq!(entry.path(&file), "excludes");
+    assert_eq!((entry.stat.ctime, entry.stat.mtime), (ctime, mtime));
+
+    let excludes_file = file
+        .untracked()
+        .expect("present")
+        .excludes_file()
+        .expect("the tracked `core.excludesFile` was seen");
+    assert_eq!(
+        (excludes_file.stat.ctime, excludes_file.stat.mtime),
+        (ctime, mtime),
+        "the untracked cache stores the stat of the same file, which has to be decoded the same way"
+    );
+    Ok(())
+}
+
 #[test]
 fn fsmn_v1() {
     let file = loose_file("FSMN");
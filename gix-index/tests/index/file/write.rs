@@ -10,11 +10,15 @@ fn roundtrips() -> crate::Result {
         (Loose("extended-flags"), only_tree_ext()),
         (Loose("conflicting-file"), only_tree_ext()),
         (Loose("very-long-path"), only_tree_ext()),
+        (Loose("UNTR"), only_untracked_cache_ext()),
+        (Loose("UNTR-with-oids"), only_untracked_cache_ext()),
+        (Generated("v2_untracked_cache_without_root"), only_untracked_cache_ext()),
         (
             Generated("v2"),
             options_with(write::Extensions::Given {
                 tree_cache: true,
                 end_of_index_entry: true,
+                untracked_cache: true,
             }),
         ),
         (Generated("V2_empty"), only_tree_ext()),
@@ -92,9 +96,7 @@ fn state_comparisons_with_various_extension_configurations() {
         Generated("v2_more_files"),
         Generated("v2_all_file_kinds"),
         Generated("v2_split_index"),
-        // TODO: this fails because git allows to configure the index version while gitoxide doesn't
-        //       the fixture artificially sets the version to V4 and gitoxide writes it back out as the lowest required version, V2
-        // Generated("v4_more_files_IEOT"),
+        Generated("v4_more_files_IEOT"),
         Generated("v3_skip_worktree"),
         Generated("v3_added_files"),
         Generated("v3_sparse_index_non_cone"),
@@ -108,14 +110,20 @@ fn state_comparisons_with_various_extension_configurations() {
             options_with(write::Extensions::Given {
                 tree_cache: true,
                 end_of_index_entry: false,
+                untracked_cache: true,
             }),
             options_with(write::Extensions::Given {
                 tree_cache: false,
                 end_of_index_entry: true,
+                untracked_cache: false,
             }),
         ] {
             let expected = fixture.open();
             let fixture = fixture.to_name();
+            let options = Options {
+                version: Some(expected.version()),
+                ..options
+            };
 
             let mut out = Vec::<u8>::new();
             let (actual_version, _digest) = expected.write_to(&mut out, options).unwrap();
@@ -140,13 +148,60 @@ fn extended_flags_automatically_upgrade_the_version_to_avoid_data_loss() -> crat
     Ok(())
 }
 
+#[test]
+fn version_can_be_chosen_when_writing() -> crate::Result {
+    let expected = Generated("v4_more_files_IEOT").open();
+    assert_eq!(expected.version(), Version::V4);
+
+    let mut v2 = Vec::new();
+    let (actual_version, _digest) = expected.write_to(&mut v2, options_with(write::Extensions::None))?;
+    assert_eq!(actual_version, Version::V2, "by default, the lowest possible version is written");
+    let (actual, _) = State::from_bytes(&v2, FileTime::now(), gix_hash::Kind::Sha1, Default::default())?;
+    assert_eq!(actual.version(), Version::V2);
+    assert_eq!(actual.entries(), expected.entries());
+    assert_eq!(actual.path_backing(), expected.path_backing());
+
+    let mut v4 = Vec::new();
+    let (actual_version, _digest) = expected.write_to(
+        &mut v4,
+        Options {
+            version: Some(Version::V4),
+            ..options_with(write::Extensions::None)
+        },
+    )?;
+    assert_eq!(actual_version, Version::V4);
+    let (actual, _) = State::from_bytes(&v4, FileTime::now(), gix_hash::Kind::Sha1, Default::default())?;
+    assert_eq!(actual.version(), Version::V4);
+    assert_eq!(actual.entries(), expected.entries());
+    assert_eq!(actual.path_backing(), expected.path_backing());
+    assert!(v4.len() < v2.len(), "paths are prefix-compressed and not padded");
+    Ok(())
+}
+
+#[test]
+fn forcing_v2_still_upgrades_to_v3_for_extended_flags() -> crate::Result {
+    let mut expected = Generated("v2").open();
+    expected.entries_mut()[0].flags.insert(entry::Flags::EXTENDED);
+
+    let mut buf = Vec::new();
+    let (actual_version, _digest) = expected.write_to(
+        &mut buf,
+        Options {
+            version: Some(Version::V2),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(actual_version, Version::V3, "extended flags can't be stored in V2");
+    Ok(())
+}
+
 #[test]
 fn remove_flag_is_respected() -> crate::Result {
     let mut index = Generated("v4_more_files_IEOT").open();
     let total_entries = 10;
     assert_eq!(index.entries().len(), total_entries);
     let entries_to_remove = 4;
-    for entry in &mut index.entries_mut()[..entries_to_remove] {
+    for entry in index.entries_mut().iter_mut().take(entries_to_remove) {
         entry.flags.toggle(entry::Flags::REMOVE);
     }
     let mut buf = Vec::<u8>::new();
@@ -183,6 +238,11 @@ fn compare_states_against_baseline(
         expected.tree(),
         "tree extension mismatch, actual vs expected in {fixture:?}"
     );
+    assert_eq!(
+        actual.untracked(),
+        expected.untracked(),
+        "untracked-cache extension mismatch, actual vs expected in {fixture:?}"
+    );
 }
 
 fn compare_states(actual: &State, actual_version: Version, expected: &State, options: Options, fixture: &str) {
@@ -202,6 +262,14 @@ fn compare_states(actual: &State, actual_version: Version, expected: &State, opt
             .and_then(|_| expected.tree()),
         "tree extension mismatch, actual vs option in {fixture:?}"
     );
+    assert_eq!(
+        actual.untracked(),
+        options
+            .extensions
+            .should_write(extension::untracked_cache::SIGNATURE)
+            .and_then(|_| expected.untracked()),
+        "untracked-cache extension mismatch, actual vs option in {fixture:?}"
+    );
 
     // As `write_to` does / should not mutate we can test those properties here.
     // Anything that can be configured has to be tested separately when comparing against baseline
@@ -252,10 +320,26 @@ fn only_tree_ext() -> Options {
         extensions: write::Extensions::Given {
             end_of_index_entry: false,
             tree_cache: true,
+            untracked_cache: false,
         },
+        ..Default::default()
+    }
+}
+
+fn only_untracked_cache_ext() -> Options {
+    Options {
+        extensions: write::Extensions::Given {
+            end_of_index_entry: false,
+            tree_cache: false,
+            untracked_cache: true,
+        },
+        ..Default::default()
     }
 }
 
 fn options_with(extensions: write::Extensions) -> Options {
-    Options { extensions }
+    Options {
+        extensions,
+        ..Default::default()
+    }
 }
@@ -25,6 +25,66 @@ fn from_tree() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn from_tree_preserves_all_file_kinds() -> crate::Result {
+    use gix_index::entry::Mode;
+    let repo_dir = scripted_fixture_read_only_standalone("make_index/v2_all_file_kinds.sh")?;
+    let repo = gix::open(&repo_dir)?;
+    let tree_id = repo.head_commit()?.tree_id()?;
+
+    let state = State::from_tree(&tree_id, |oid, buf| repo.objects.find_tree_iter(oid, buf).ok())?;
+    state.verify_entries()?;
+    assert_eq!(
+        state
+            .entries()
+            .iter()
+            .map(|e| (e.path(&state).to_string(), e.mode, e.stage()))
+            .collect::<Vec<_>>(),
+        [
+            ("a".into(), Mode::FILE, 0),
+            ("b".into(), Mode::FILE_EXECUTABLE, 0),
+            ("c".into(), Mode::SYMLINK, 0),
+            ("d/a".into(), Mode::FILE, 0),
+            ("d/b".into(), Mode::FILE, 0),
+            ("d/c".into(), Mode::FILE, 0),
+            ("sub".into(), Mode::COMMIT, 0),
+        ],
+        "entries are sorted, symlinks and submodules keep their mode"
+    );
+    Ok(())
+}
+
+#[test]
+fn removed_relative_to_tree() -> crate::Result {
+    let repo_dir = scripted_fixture_read_only_standalone("make_index/v2_deleted_files.sh")?;
+    let repo = gix::open(&repo_dir)?;
+    let tree_id = repo.head_commit()?.tree_id()?;
+
+    let index = repo.index()?;
+    assert_eq!(
+        index.removed_relative_to_tree(&tree_id, |oid, buf| repo.objects.find_tree_iter(oid, buf).ok())?,
+        ["b", "d/a"],
+        "paths in the tree which aren't in the index anymore are removed"
+    );
+
+    let index = State::from_tree(&tree_id, |oid, buf| repo.objects.find_tree_iter(oid, buf).ok())?;
+    assert!(
+        index.removed_relative_to_tree(&tree_id, |oid, buf| repo.objects.find_tree_iter(oid, buf).ok())?.is_empty(),
+        "nothing is removed relative to the tree the index was created from"
+    );
+
+    let repo_dir = scripted_fixture_read_only_standalone("make_index/v3_sparse_index.sh")?;
+    let repo = gix::open(&repo_dir)?;
+    let tree_id = repo.head_commit()?.tree_id()?;
+    let index = repo.index()?;
+    assert!(index.is_sparse());
+    assert!(
+        index.removed_relative_to_tree(&tree_id, |oid, buf| repo.objects.find_tree_iter(oid, buf).ok())?.is_empty(),
+        "paths within sparse directories are present even though they have no entry"
+    );
+    Ok(())
+}
+
 #[test]
 fn new() {
     let state = State::new(gix_hash::Kind::Sha1);
@@ -0,0 +1,116 @@
+use gix_index::{
+    entry::{Flags, Mode},
+    State,
+};
+
+use crate::index::hex_to_id;
+
+const A: &str = "0000000000000000000000000000000000000001";
+const B: &str = "0000000000000000000000000000000000000002";
+const C: &str = "0000000000000000000000000000000000000003";
+
+fn state(entries: &[(&str, &str)]) -> State {
+    let mut state = State::new(gix_hash::Kind::Sha1);
+    for (path, id) in entries {
+        state.dangerously_push_entry(Default::default(), hex_to_id(id), Flags::empty(), Mode::FILE, (*path).into());
+    }
+    state.sort_entries();
+    state
+}
+
+fn entries(state: &State) -> Vec<(String, u32, gix_hash::ObjectId)> {
+    state
+        .entries()
+        .iter()
+        .map(|e| (e.path(state).to_string(), e.stage(), e.id))
+        .collect()
+}
+
+#[test]
+fn diverging_changes_produce_all_conflict_stages() {
+    let base = state(&[("file", A), ("other", A)]);
+    let ours = state(&[("file", B), ("other", A)]);
+    let theirs = state(&[("file", C), ("other", A)]);
+
+    let merged = State::merge(&base, &ours, &theirs);
+    merged.verify_entries().expect("valid");
+    assert_eq!(
+        entries(&merged),
+        [
+            ("file".into(), 1, hex_to_id(A)),
+            ("file".into(), 2, hex_to_id(B)),
+            ("file".into(), 3, hex_to_id(C)),
+            ("other".into(), 0, hex_to_id(A)),
+        ]
+    );
+    assert_eq!(
+        merged.entry_by_path_and_stage("file".into(), 2).map(|e| e.id),
+        Some(hex_to_id(B)),
+        "lookups work on the result"
+    );
+}
+
+#[test]
+fn one_sided_and_identical_changes_are_resolved() {
+    let base = state(&[("changed-by-ours", A), ("changed-by-theirs", A), ("deleted-by-theirs", A), ("same", A)]);
+    let ours = state(&[
+        ("added-by-both", C),
+        ("changed-by-ours", B),
+        ("changed-by-theirs", A),
+        ("deleted-by-theirs", A),
+        ("same", A),
+    ]);
+    let theirs = state(&[
+        ("added-by-both", C),
+        ("added-by-theirs", C),
+        ("changed-by-ours", A),
+        ("changed-by-theirs", B),
+        ("same", A),
+    ]);
+
+    let merged = State::merge(&base, &ours, &theirs);
+    merged.verify_entries().expect("valid");
+    assert_eq!(
+        entries(&merged),
+        [
+            ("added-by-both".into(), 0, hex_to_id(C)),
+            ("added-by-theirs".into(), 0, hex_to_id(C)),
+            ("changed-by-ours".into(), 0, hex_to_id(B)),
+            ("changed-by-theirs".into(), 0, hex_to_id(B)),
+            ("same".into(), 0, hex_to_id(A)),
+        ]
+    );
+}
+
+#[test]
+fn modify_delete_conflicts_leave_out_the_missing_side() {
+    let base = state(&[("file", A)]);
+    let ours = state(&[("file", B)]);
+    let theirs = state(&[]);
+
+    let merged = State::merge(&base, &ours, &theirs);
+    merged.verify_entries().expect("valid");
+    assert_eq!(
+        entries(&merged),
+        [("file".into(), 1, hex_to_id(A)), ("file".into(), 2, hex_to_id(B))]
+    );
+}
+
+#[test]
+fn directory_file_conflicts_keep_the_file_at_its_stage() {
+    let base = state(&[("other", A)]);
+    let ours = state(&[("a", B), ("other", A)]);
+    let theirs = state(&[("a/b", C), ("other", A)]);
+
+    let merged = State::merge(&base, &ours, &theirs);
+    merged.verify_entries().expect("valid");
+    assert_eq!(
+        entries(&merged),
+        [
+            ("a".into(), 2, hex_to_id(B)),
+            ("a/b".into(), 0, hex_to_id(C)),
+            ("other".into(), 0, hex_to_id(A)),
+        ],
+        "the file can't be resolved as the other side has a directory in its place"
+    );
+}
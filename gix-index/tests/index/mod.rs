@@ -6,6 +6,8 @@ mod access;
 mod entry;
 mod file;
 mod init;
+mod merge;
+mod status;
 
 pub fn hex_to_id(hex: &str) -> ObjectId {
     ObjectId::from_hex(hex.as_bytes()).expect("40 bytes hex")
@@ -50,7 +52,10 @@ impl Fixture {
     }
 
     pub fn open(&self) -> gix_index::File {
-        gix_index::File::at(self.to_path(), gix_hash::Kind::Sha1, Default::default())
-            .expect("fixtures are always readable")
+        self.open_opts(Default::default())
+    }
+
+    pub fn open_opts(&self, opts: gix_index::decode::Options) -> gix_index::File {
+        gix_index::File::at(self.to_path(), gix_hash::Kind::Sha1, opts).expect("fixtures are always readable")
     }
 }
@@ -0,0 +1,169 @@
+use bstr::ByteSlice;
+use gix::prelude::FindExt;
+use gix_testtools::scripted_fixture_read_only_standalone;
+
+#[test]
+fn porcelain_status() -> crate::Result {
+    let worktree = scripted_fixture_read_only_standalone("make_index/v2_status.sh")?;
+    let repo = gix::open(&worktree)?;
+    let tree_id = repo.head_commit()?.tree_id()?;
+
+    let index = repo.index()?;
+    let status = index.porcelain_status(&tree_id, |oid, buf| repo.objects.find_tree_iter(oid, buf).ok(), &worktree)?;
+    let actual: Vec<_> = status
+        .iter()
+        .map(|e| format!("{} {}", e.code().as_bstr(), e.path))
+        .collect();
+    assert_eq!(
+        actual,
+        [" M a", "A  added", "MM b", " D c", "D  d", "T  f", "AM new"],
+        "staged changes are on the left, unstaged ones on the right, and unchanged files are omitted"
+    );
+
+    let baseline = std::fs::read_to_string(worktree.join("status.baseline"))?;
+    assert_eq!(
+        actual,
+        baseline.lines().collect::<Vec<_>>(),
+        "the codes and their order match the ones produced by git"
+    );
+    Ok(())
+}
+
+mod entry_matches_stat {
+    use std::path::Path;
+
+    use filetime::FileTime;
+    use gix_index::{
+        entry::{Flags, Mode, Stat},
+        status::{Options, StatMatch},
+        State,
+    };
+
+    fn state_with_entry(path: &Path, mode: Mode, size: Option<u32>, timestamp: FileTime) -> crate::Result<State> {
+        let metadata = std::fs::symlink_metadata(path)?;
+        let mut stat = Stat::from_fs(&metadata)?;
+        if let Some(size) = size {
+            stat.size = size;
+        }
+        let data = std::fs::read(path)?;
+        let id = gix::objs::compute_hash(gix_hash::Kind::Sha1, gix::objs::Kind::Blob, &data);
+        let mut state = State::new(gix_hash::Kind::Sha1);
+        state.dangerously_push_entry(stat, id, Flags::empty(), mode, "file".into());
+        state.set_timestamp(timestamp);
+        Ok(state)
+    }
+
+    fn matches(state: &State, path: &Path, options: Options) -> crate::Result<StatMatch> {
+        let metadata = std::fs::symlink_metadata(path)?;
+        Ok(state.entry_matches_stat(&state.entries()[0], &metadata, options))
+    }
+
+    fn file_with_content(content: &str) -> crate::Result<(gix_testtools::tempfile::TempDir, std::path::PathBuf)> {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let path = dir.path().join("file");
+        std::fs::write(&path, content)?;
+        filetime::set_file_mtime(&path, FileTime::from_unix_time(1_000_000, 0))?;
+        Ok((dir, path))
+    }
+
+    fn after_mtime() -> FileTime {
+        FileTime::from_unix_time(2_000_000, 0)
+    }
+
+    #[test]
+    fn unchanged_unless_racy() -> crate::Result {
+        let (_dir, path) = file_with_content("content")?;
+        let state = state_with_entry(&path, Mode::FILE, None, after_mtime())?;
+        assert_eq!(matches(&state, &path, Options::default())?, StatMatch::Unchanged);
+
+        let state = state_with_entry(&path, Mode::FILE, None, FileTime::from_unix_time(1_000_000, 0))?;
+        assert_eq!(
+            matches(&state, &path, Options::default())?,
+            StatMatch::NeedsContentCheck,
+            "an entry written in the same second as the index was written might have changed without the stat showing it"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn size_change_is_a_modification_but_other_stat_changes_need_a_content_check() -> crate::Result {
+        let (_dir, path) = file_with_content("content")?;
+        let state = state_with_entry(&path, Mode::FILE, None, after_mtime())?;
+
+        filetime::set_file_mtime(&path, FileTime::from_unix_time(1_500_000, 0))?;
+        assert_eq!(
+            matches(&state, &path, Options::default())?,
+            StatMatch::NeedsContentCheck,
+            "the mtime changed, but the content may still be the same"
+        );
+
+        std::fs::write(&path, "more content")?;
+        assert_eq!(matches(&state, &path, Options::default())?, StatMatch::Modified);
+        Ok(())
+    }
+
+    #[test]
+    fn entries_of_size_zero_need_a_content_check_unless_they_are_the_empty_blob() -> crate::Result {
+        let (_dir, path) = file_with_content("content")?;
+        let state = state_with_entry(&path, Mode::FILE, Some(0), after_mtime())?;
+        assert_eq!(
+            matches(&state, &path, Options::default())?,
+            StatMatch::NeedsContentCheck,
+            "the size of an entry is 0 if the stat wasn't recorded, like after reading a tree, so it can't be trusted"
+        );
+
+        std::fs::write(&path, "")?;
+        filetime::set_file_mtime(&path, FileTime::from_unix_time(1_000_000, 0))?;
+        let state = state_with_entry(&path, Mode::FILE, None, after_mtime())?;
+        assert_eq!(matches(&state, &path, Options::default())?, StatMatch::Unchanged);
+        Ok(())
+    }
+
+    #[test]
+    fn type_changes() -> crate::Result {
+        let (_dir, path) = file_with_content("content")?;
+        let state = state_with_entry(&path, Mode::FILE, None, after_mtime())?;
+        std::fs::remove_file(&path)?;
+        std::fs::create_dir(&path)?;
+        assert_eq!(matches(&state, &path, Options::default())?, StatMatch::TypeChange);
+
+        let (_dir, path) = file_with_content("content")?;
+        let state = state_with_entry(&path, Mode::SYMLINK, None, after_mtime())?;
+        assert_eq!(matches(&state, &path, Options::default())?, StatMatch::TypeChange);
+        assert_ne!(
+            matches(
+                &state,
+                &path,
+                Options {
+                    has_symlinks: false,
+                    ..Default::default()
+                }
+            )?,
+            StatMatch::TypeChange,
+            "without symlink support, symlinks are checked out as files"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn executable_bit_changes_are_modifications_if_trusted() -> crate::Result {
+        use std::os::unix::fs::PermissionsExt;
+        let (_dir, path) = file_with_content("content")?;
+        let state = state_with_entry(&path, Mode::FILE, None, after_mtime())?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        assert_eq!(matches(&state, &path, Options::default())?, StatMatch::Modified);
+        assert_ne!(
+            matches(
+                &state,
+                &path,
+                Options {
+                    executable_bit: false,
+                    ..Default::default()
+                }
+            )?,
+            StatMatch::Modified
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// The submodules of the `multiple` fixture, repeated `count` times under different directories.
+fn modules(count: usize) -> gix_submodule::File {
+    let mut buf = String::new();
+    for idx in 0..count {
+        for name in ["submodule", "a/b", ".a/..c", "a/d\\\\", "a\\\\e"] {
+            buf.push_str(&format!(
+                "[submodule \"{idx}/{name}\"]\n\tpath = {idx}/{name}\n\turl = ../multiple\n"
+            ));
+        }
+    }
+    gix_submodule::File::from_bytes(buf.as_bytes(), None).expect("valid")
+}
+
+fn no_attributes(
+    _: &bstr::BStr,
+    _: gix_pathspec::attributes::glob::pattern::Case,
+    _: bool,
+    _: &mut gix_pathspec::attributes::search::Outcome,
+) -> bool {
+    unreachable!("no attributes are used in pathspecs")
+}
+
+fn is_active(c: &mut Criterion) {
+    let modules = modules(200);
+    let config = gix_config::File::from_str(
+        "[submodule \"0/submodule\"]\n active = 0\n[submodule]\n active = *\n[submodule]\n active = :!*/a*\n[submodule]\n active = :!1*",
+    )
+    .expect("valid");
+    let names: Vec<_> = modules.names().collect();
+
+    c.bench_function("is_active() with a new platform for each submodule", |b| {
+        b.iter(|| {
+            for name in &names {
+                let mut platform = modules
+                    .is_active_platform(&config, Default::default())
+                    .expect("valid pathspecs");
+                black_box(platform.is_active(name, no_attributes).expect("valid"));
+            }
+        })
+    });
+    c.bench_function("is_active() with one platform for all submodules", |b| {
+        b.iter(|| {
+            let mut platform = modules
+                .is_active_platform(&config, Default::default())
+                .expect("valid pathspecs");
+            for name in &names {
+                black_box(platform.is_active(name, no_attributes).expect("valid"));
+            }
+        })
+    });
+    c.bench_function("names_and_active_state()", |b| {
+        b.iter(|| {
+            for (_name, is_active) in modules
+                .names_and_active_state(&config, Default::default(), no_attributes)
+                .expect("valid pathspecs")
+            {
+                black_box(is_active.expect("valid"));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, is_active);
+criterion_main!(benches);
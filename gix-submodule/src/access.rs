@@ -1,6 +1,6 @@
 use crate::config::{Branch, FetchRecurse, Ignore, Update};
-use crate::{config, File};
-use bstr::BStr;
+use crate::{config, CloneRecommendation, File, IsActivePlatform, Submodule, OVERRIDABLE_FIELDS};
+use bstr::{BStr, BString};
 use std::borrow::Cow;
 use std::path::Path;
 
@@ -22,10 +22,24 @@ impl File {
         self.config.sections().filter_map(|s| s.meta().path.as_deref()).next()
     }
 
-    /// Return the unvalidated names of the submodules for which configuration is present.
+    /// Return the names of the submodules for which configuration is present.
+    ///
+    /// Note that names that aren't safe to use as directory in `.git/modules/<name>` are skipped, just like `git` ignores
+    /// such submodules. These are names that are empty, have `..` components or contain control characters, and they can be
+    /// obtained with [`unsafe_names()`](File::unsafe_names()). Other than that, names are not validated.
     ///
     /// Note that these exact names have to be used for querying submodule values.
     pub fn names(&self) -> impl Iterator<Item = &BStr> {
+        self.all_names().filter(|name| is_safe_name(name))
+    }
+
+    /// Return the names of the submodules that are skipped by [`names()`](File::names()) as they aren't safe to use
+    /// as directory in `.git/modules/<name>`, for example to tell the user about them.
+    pub fn unsafe_names(&self) -> impl Iterator<Item = &BStr> {
+        self.all_names().filter(|name| !is_safe_name(name))
+    }
+
+    fn all_names(&self) -> impl Iterator<Item = &BStr> {
         self.config
             .sections_by_name("submodule")
             .into_iter()
@@ -48,7 +62,7 @@ impl File {
         &'a self,
         config: &'a gix_config::File<'static>,
         defaults: gix_pathspec::Defaults,
-        mut attributes: impl FnMut(
+        attributes: impl FnMut(
                 &BStr,
                 gix_pathspec::attributes::glob::pattern::Case,
                 bool,
@@ -59,7 +73,50 @@ impl File {
         impl Iterator<Item = (&BStr, Result<bool, config::names_and_active_state::iter::Error>)> + 'a,
         config::names_and_active_state::Error,
     > {
-        let mut search = config
+        Ok(self
+            .names_and_active_reason(config, defaults, attributes)?
+            .map(|(name, reason)| (name, reason.map(|reason| reason.is_active()))))
+    }
+
+    /// Like [`names_and_active_state()`](File::names_and_active_state()), but instead of a boolean, return the
+    /// [reason](config::ActiveReason) that decided whether the submodule is active or not, which is useful for diagnostics.
+    pub fn names_and_active_reason<'a>(
+        &'a self,
+        config: &'a gix_config::File<'static>,
+        defaults: gix_pathspec::Defaults,
+        mut attributes: impl FnMut(
+                &BStr,
+                gix_pathspec::attributes::glob::pattern::Case,
+                bool,
+                &mut gix_pathspec::attributes::search::Outcome,
+            ) -> bool
+            + 'a,
+    ) -> Result<
+        impl Iterator<
+                Item = (
+                    &BStr,
+                    Result<config::ActiveReason, config::names_and_active_state::iter::Error>,
+                ),
+            > + 'a,
+        config::names_and_active_state::Error,
+    > {
+        let mut platform = self.is_active_platform(config, defaults)?;
+        Ok(self
+            .names()
+            .map(move |name| (name, platform.active_reason(name, &mut attributes))))
+    }
+
+    /// Return a platform to determine whether individual submodules are active with the `submodule.active` pathspecs
+    /// from `config` compiled only once, using `defaults` for parsing them.
+    ///
+    /// This is the building block of [`names_and_active_state()`](File::names_and_active_state()) and more efficient
+    /// if the active state of submodules is queried repeatedly.
+    pub fn is_active_platform<'a>(
+        &'a self,
+        config: &'a gix_config::File<'static>,
+        defaults: gix_pathspec::Defaults,
+    ) -> Result<IsActivePlatform<'a>, config::names_and_active_state::Error> {
+        let search = config
             .strings_by_key("submodule.active")
             .map(|patterns| -> Result<_, config::names_and_active_state::Error> {
                 let patterns = patterns
@@ -73,47 +130,236 @@ impl File {
                 )?)
             })
             .transpose()?;
-        let iter = self.names().map(move |name| {
-            let active = (|| -> Result<_, config::names_and_active_state::iter::Error> {
-                if let Some(val) = config.boolean("submodule", Some(name), "active").transpose()? {
-                    return Ok(val);
-                };
-                if let Some(val) = search
-                    .as_mut()
-                    .and_then(|search| search.pattern_matching_relative_path(name, Some(true), &mut attributes))
-                    .map(|m| !m.is_excluded())
+        Ok(IsActivePlatform {
+            modules: self,
+            config,
+            search,
+        })
+    }
+
+    /// Return the names of submodules for which `config` has a `submodule.<name>` section whose name differs only in case.
+    ///
+    /// As subsection names are case-sensitive, such sections don't apply to the submodule at all, which typically
+    /// is a mistake that leads to overrides being silently ignored.
+    pub fn names_with_mismatched_case(&self, config: &gix_config::File<'_>) -> Vec<BString> {
+        let override_names: Vec<_> = config
+            .sections_by_name("submodule")
+            .into_iter()
+            .flatten()
+            .filter_map(|s| s.header().subsection_name())
+            .collect();
+        let mut out = Vec::<BString>::new();
+        for name in self.names() {
+            if out.iter().any(|n| n == name) {
+                continue;
+            }
+            if override_names
+                .iter()
+                .any(|other| *other != name && other.eq_ignore_ascii_case(name))
+            {
+                out.push(name.to_owned());
+            }
+        }
+        out
+    }
+
+    /// Return the names of all `submodule.<name>` sections in `config` for which there is no submodule of the same name in
+    /// the `.gitmodules` file, typically because it was removed from there while its local configuration remained.
+    ///
+    /// Sections that were added with [`append_submodule_overrides()`](File::append_submodule_overrides()) don't count as
+    /// submodules in the `.gitmodules` file.
+    pub fn orphaned_overrides(&self, config: &gix_config::File<'_>) -> Vec<BString> {
+        let ours = self.config.meta();
+        let known_names: Vec<_> = self
+            .config
+            .sections_by_name("submodule")
+            .into_iter()
+            .flatten()
+            .filter(|s| std::ptr::eq(s.meta(), ours))
+            .filter_map(|s| s.header().subsection_name())
+            .collect();
+        let mut out = Vec::<BString>::new();
+        for name in config
+            .sections_by_name("submodule")
+            .into_iter()
+            .flatten()
+            .filter_map(|s| s.header().subsection_name())
+        {
+            if !known_names.contains(&name) && !out.iter().any(|n| n == name) {
+                out.push(name.to_owned());
+            }
+        }
+        out
+    }
+
+    /// Check the submodule sections of the `.gitmodules` file for problems and return all of them in order of appearance,
+    /// or an empty list if there is none.
+    ///
+    /// This finds submodules with more than one section, those with conflicting `path` values,
+    /// and different submodules that share the same path. As such configuration is still usable,
+    /// these are best presented to the user as warnings.
+    /// Submodules with unsafe names are reported as well, as they are skipped by [`names()`](File::names()).
+    ///
+    /// Sections that were added with [`append_submodule_overrides()`](File::append_submodule_overrides()) are not considered.
+    pub fn validate(&self) -> Vec<config::validate::Issue> {
+        use config::validate::Issue;
+        let ours = self.config.meta();
+        let mut names = Vec::<&BStr>::new();
+        let mut out = Vec::new();
+        for name in self
+            .config
+            .sections_by_name("submodule")
+            .into_iter()
+            .flatten()
+            .filter(|s| std::ptr::eq(s.meta(), ours))
+            .filter_map(|s| s.header().subsection_name())
+        {
+            if !is_safe_name(name) {
+                if !out
+                    .iter()
+                    .any(|issue| matches!(issue, Issue::UnsafeName { name: n } if n == name))
                 {
-                    return Ok(val);
+                    out.push(Issue::UnsafeName { name: name.to_owned() });
                 }
-                Ok(match self.url(name) {
-                    Ok(_) => true,
-                    Err(config::url::Error::Missing { .. }) => false,
-                    Err(err) => return Err(err.into()),
-                })
-            })();
-            (name, active)
-        });
-        Ok(iter)
+                continue;
+            }
+            if names.contains(&name) {
+                if !out
+                    .iter()
+                    .any(|issue| matches!(issue, Issue::DuplicateSection { name: n } if n == name))
+                {
+                    out.push(Issue::DuplicateSection { name: name.to_owned() });
+                }
+            } else {
+                names.push(name);
+            }
+        }
+
+        for name in &names {
+            let mut paths = Vec::<BString>::new();
+            for path in self
+                .config
+                .strings("submodule", Some(name), "path")
+                .into_iter()
+                .flatten()
+            {
+                if !paths.iter().any(|p| p == path.as_ref()) {
+                    paths.push(path.into_owned());
+                }
+            }
+            if paths.len() > 1 {
+                out.push(Issue::ConflictingPaths {
+                    name: (*name).to_owned(),
+                    paths,
+                });
+            }
+        }
+
+        out.extend(
+            self.names_by_path(names)
+                .into_iter()
+                .filter(|(_, names)| names.len() > 1)
+                .map(|(path, names)| Issue::SharedPath {
+                    path: path.into_owned(),
+                    names,
+                }),
+        );
+        out
     }
 
-    /// Given the `relative_path` (as seen from the root of the worktree) of a submodule with possibly platform-specific
-    /// component separators, find the submodule's name associated with this path, or `None` if none was found.
+    /// Given the `relative_path` (as seen from the root of the worktree) of a submodule, find the submodule's name
+    /// associated with this path, or `None` if none was found.
     ///
-    /// Note that this does a linear search and compares `relative_path` in a normalized form to the same form of the path
-    /// associated with the submodule.
+    /// On first call, a mapping of all valid submodule paths to their names is built and cached for later calls.
+    /// Submodules with an invalid or missing `path` are ignored, and so are paths shared by more than one submodule
+    /// as they are ambiguous. The latter are reported by [`validate()`](File::validate()).
     pub fn name_by_path(&self, relative_path: &BStr) -> Option<&BStr> {
-        self.names()
-            .filter_map(|n| self.path(n).ok().map(|p| (n, p)))
-            .find_map(|(n, p)| (p == relative_path).then_some(n))
+        self.names_by_path
+            .get_or_init(|| {
+                self.names_by_path(self.names())
+                    .into_iter()
+                    .filter(|(_, names)| names.len() == 1)
+                    .map(|(path, mut names)| (path.into_owned(), names.pop().expect("one name")))
+                    .collect()
+            })
+            .get(relative_path)
+            .map(AsRef::as_ref)
+    }
+
+    /// Group the distinct submodule `names` by their valid paths, in order of appearance.
+    fn names_by_path<'a>(&'a self, names: impl IntoIterator<Item = &'a BStr>) -> Vec<(Cow<'a, BStr>, Vec<BString>)> {
+        let mut out = Vec::<(Cow<'_, BStr>, Vec<BString>)>::new();
+        for (name, path) in names.into_iter().filter_map(|n| self.path(n).ok().map(|p| (n, p))) {
+            match out.iter_mut().find(|(p, _)| *p == path) {
+                Some((_, names)) => {
+                    if !names.iter().any(|n| n == name) {
+                        names.push(name.to_owned());
+                    }
+                }
+                None => out.push((path, vec![name.to_owned()])),
+            }
+        }
+        out
     }
 }
 
 /// Per-Submodule Access
 impl File {
+    /// Retrieve and validate all known fields of the submodule named `name` at once.
+    ///
+    /// This fails on the first invalid field, and like [`path()`](File::path()) and [`url()`](File::url()) it's an error
+    /// if these required fields are missing.
+    ///
+    /// The `name` itself is validated as well as it's used to place the submodule repository at `.git/modules/<name>`,
+    /// so names with `..` components (with `/` or `\` as separator) or with control characters are rejected.
+    pub fn submodule(&self, name: &BStr) -> Result<Submodule, config::submodule::Error> {
+        if !is_safe_name(name) {
+            return Err(config::submodule::Error::UnsafeName {
+                submodule: name.to_owned(),
+            });
+        }
+        Ok(Submodule {
+            name: name.to_owned(),
+            path: self.path(name)?.into_owned(),
+            url: self.url(name)?,
+            update: self.update(name)?,
+            branch: self.branch(name)?,
+            ignore: self.ignore(name)?,
+            fetch_recurse: self.fetch_recurse(name)?,
+            shallow: self.shallow(name).map_err(|err| config::submodule::Error::Shallow {
+                submodule: name.to_owned(),
+                source: err,
+            })?,
+        })
+    }
+
+    /// Retrieve the [`shallow()`](File::shallow()), [`fetch_recurse()`](File::fetch_recurse()) and [`branch()`](File::branch())
+    /// fields of the submodule named `name` at once, with their defaults applied if they are unset, as needed to clone it.
+    pub fn clone_recommendation(
+        &self,
+        name: &BStr,
+    ) -> Result<CloneRecommendation, config::clone_recommendation::Error> {
+        Ok(CloneRecommendation {
+            shallow: self
+                .shallow(name)
+                .map_err(|err| config::clone_recommendation::Error::Shallow {
+                    submodule: name.to_owned(),
+                    source: err,
+                })?
+                .unwrap_or(false),
+            fetch_recurse: self.fetch_recurse(name)?.unwrap_or_default(),
+            branch: self.branch(name)?.unwrap_or_default(),
+        })
+    }
+
     /// Return the path relative to the root directory of the working tree at which the submodule is expected to be checked out.
     /// It's an error if the path doesn't exist as it's the only way to associate a path in the index with additional submodule
     /// information, like the URL to fetch from.
     ///
+    /// Paths that would be absolute on any platform are rejected everywhere, which includes paths starting with `/` or `\`
+    /// (like UNC paths) and those with a drive prefix like `c:`. Otherwise, components are separated with `/`,
+    /// and additionally with `\` on Windows.
+    ///
     /// ### Deviation
     ///
     /// Git currently allows absolute paths to be used when adding submodules, but fails later as it can't find the submodule by
@@ -131,7 +377,7 @@ impl File {
             });
         }
         let path = gix_path::from_bstr(path_bstr.as_ref());
-        if path.is_absolute() {
+        if path.is_absolute() || is_absolute_on_any_platform(path_bstr.as_ref()) {
             return Err(config::path::Error::Absolute {
                 submodule: name.to_owned(),
                 actual: path_bstr.into_owned(),
@@ -243,8 +489,113 @@ impl File {
 
     /// Retrieve the `shallow` field of the submodule named `name`, or `None` if unset.
     ///
-    /// If `true`, the submodule will be checked out with `depth = 1`. If unset, `false` is assumed.
+    /// If `true`, the submodule will be checked out with `depth = 1`. Note that `None` is returned if the key is missing
+    /// entirely, which callers should treat like `Some(false)`, while an explicit `false` is returned as such.
     pub fn shallow(&self, name: &BStr) -> Result<Option<bool>, gix_config::value::Error> {
         self.config.boolean("submodule", Some(name), "shallow").transpose()
     }
+
+    /// Return the names of all fields of the submodule named `name` that were set by [`append_submodule_overrides()`](File::append_submodule_overrides()),
+    /// in the order in which they are documented there. All other fields, if set, are coming from the `.gitmodules` file.
+    ///
+    /// This is useful to let users know that local configuration is in effect, like an `update` command.
+    pub fn overridden_fields(&self, name: &BStr) -> Vec<&'static str> {
+        let ours = self.config.meta();
+        let foreign_sections: Vec<_> = self
+            .config
+            .sections_by_name("submodule")
+            .into_iter()
+            .flatten()
+            .filter(|s| s.header().subsection_name() == Some(name) && !std::ptr::eq(s.meta(), ours))
+            .collect();
+        OVERRIDABLE_FIELDS
+            .iter()
+            .copied()
+            .filter(|field| foreign_sections.iter().any(|s| s.contains_key(field)))
+            .collect()
+    }
+
+    /// Determine if the submodule named `name` should be cloned shallowly, with `cli_override` taking precedence over
+    /// `submodule.<name>.shallow` in `config`, typically the one of the superproject, which in turn takes precedence
+    /// over the [`shallow()`](File::shallow()) value we know. If none of these are set, `false` is returned.
+    pub fn effective_shallow(
+        &self,
+        name: &BStr,
+        cli_override: Option<bool>,
+        config: &gix_config::File<'_>,
+    ) -> Result<bool, gix_config::value::Error> {
+        if let Some(value) = cli_override {
+            return Ok(value);
+        }
+        if let Some(value) = config.boolean("submodule", Some(name), "shallow").transpose()? {
+            return Ok(value);
+        }
+        Ok(self.shallow(name)?.unwrap_or(false))
+    }
+}
+
+impl IsActivePlatform<'_> {
+    /// Return `true` if the submodule named `name` is active, using `attributes` to resolve the attributes mentioned
+    /// in `submodule.active` pathspecs.
+    pub fn is_active(
+        &mut self,
+        name: &BStr,
+        attributes: impl FnMut(
+            &BStr,
+            gix_pathspec::attributes::glob::pattern::Case,
+            bool,
+            &mut gix_pathspec::attributes::search::Outcome,
+        ) -> bool,
+    ) -> Result<bool, config::names_and_active_state::iter::Error> {
+        self.active_reason(name, attributes).map(|reason| reason.is_active())
+    }
+
+    /// Like [`is_active()`](IsActivePlatform::is_active()), but return the [reason](config::ActiveReason) that decided
+    /// whether the submodule named `name` is active or not.
+    pub fn active_reason(
+        &mut self,
+        name: &BStr,
+        attributes: impl FnMut(
+            &BStr,
+            gix_pathspec::attributes::glob::pattern::Case,
+            bool,
+            &mut gix_pathspec::attributes::search::Outcome,
+        ) -> bool,
+    ) -> Result<config::ActiveReason, config::names_and_active_state::iter::Error> {
+        if let Some(val) = self.config.boolean("submodule", Some(name), "active").transpose()? {
+            return Ok(config::ActiveReason::ActiveField(val));
+        };
+        if let Some(is_excluded) = self
+            .search
+            .as_mut()
+            .and_then(|search| search.pattern_matching_relative_path(name, Some(true), attributes))
+            .map(|m| m.is_excluded())
+        {
+            return Ok(if is_excluded {
+                config::ActiveReason::PathspecExcluded
+            } else {
+                config::ActiveReason::PathspecIncluded
+            });
+        }
+        Ok(match self.modules.url(name) {
+            Ok(_) => config::ActiveReason::HasUrl,
+            Err(config::url::Error::Missing { .. }) => config::ActiveReason::NoUrl,
+            Err(err) => return Err(err.into()),
+        })
+    }
+}
+
+/// Like `check_submodule_name()` in `git`, but also rejects control characters.
+fn is_safe_name(name: &BStr) -> bool {
+    !name.is_empty()
+        && !name.iter().any(u8::is_ascii_control)
+        && !name
+            .split(|b| *b == b'/' || *b == b'\\')
+            .any(|component| component == b"..")
+}
+
+/// Return `true` if `path` is absolute on Windows or elsewhere, as `.gitmodules` files are shared across platforms.
+fn is_absolute_on_any_platform(path: &BStr) -> bool {
+    matches!(path.first(), Some(b'/' | b'\\'))
+        || matches!(path.get(..2), Some([drive, b':']) if drive.is_ascii_alphabetic())
 }
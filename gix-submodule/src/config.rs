@@ -33,6 +33,24 @@ impl TryFrom<&BStr> for Ignore {
     }
 }
 
+impl Ignore {
+    /// Return the value as it would be written to the configuration, the inverse of parsing it.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Ignore::All => "all",
+            Ignore::Dirty => "dirty",
+            Ignore::Untracked => "untracked",
+            Ignore::None => "none",
+        }
+    }
+}
+
+impl std::fmt::Display for Ignore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Determine how to recurse into this module from the superproject when fetching.
 ///
 /// Generally, a fetch is only performed if the submodule commit referenced by the superproject isn't already
@@ -57,23 +75,46 @@ impl FetchRecurse {
     /// Check if `boolean` is set and translate it the respective variant, or check the underlying string
     /// value for non-boolean options.
     /// On error, it returns the obtained string value which would be the invalid value.
+    ///
+    /// Leading and trailing whitespace that survived parsing, like in quoted values such as `" on-demand "`, is ignored
+    /// for both booleans and `on-demand`. Booleans are case-insensitive, but like in `git`, `on-demand` has to be
+    /// spelled in lower-case.
     pub fn new(boolean: Result<bool, gix_config::value::Error>) -> Result<Self, BString> {
-        Ok(match boolean {
-            Ok(value) => {
-                if value {
-                    FetchRecurse::Always
-                } else {
-                    FetchRecurse::Never
-                }
-            }
+        let value = match boolean {
+            Ok(value) => value,
             Err(err) => {
-                if err.input != "on-demand" {
-                    return Err(err.input);
+                let trimmed = err.input.trim().as_bstr();
+                if trimmed == "on-demand" {
+                    return Ok(FetchRecurse::OnDemand);
+                }
+                match gix_config::Boolean::try_from(trimmed) {
+                    Ok(value) if !trimmed.is_empty() => value.into(),
+                    _ => return Err(err.input),
                 }
-                FetchRecurse::OnDemand
             }
+        };
+        Ok(if value {
+            FetchRecurse::Always
+        } else {
+            FetchRecurse::Never
         })
     }
+
+    /// Return the value as it would be written to the configuration, the inverse of [`new()`](Self::new()),
+    /// which is `on-demand` or the boolean that `git` writes for the other variants.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FetchRecurse::OnDemand => "on-demand",
+            FetchRecurse::Always => "true",
+            FetchRecurse::Never => "false",
+        }
+    }
+}
+
+impl std::fmt::Display for FetchRecurse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Describes the branch that should be tracked on the remote.
@@ -104,6 +145,33 @@ impl TryFrom<&BStr> for Branch {
     }
 }
 
+impl Branch {
+    /// Turn [`Branch::CurrentInSuperproject`] into the concrete branch named `superproject_head_branch`, the short name of
+    /// the branch that `HEAD` of the superproject points to, or `None` if `HEAD` is detached.
+    /// All other variants are returned unchanged.
+    ///
+    /// It's an error if the superproject isn't on any branch as there is nothing to follow then.
+    pub fn resolve(self, superproject_head_branch: Option<&BStr>) -> Result<Self, branch::resolve::Error> {
+        match self {
+            Branch::CurrentInSuperproject => {
+                let name = superproject_head_branch.ok_or(branch::resolve::Error::DetachedHead)?;
+                match Branch::try_from(name) {
+                    Ok(Branch::Name(name)) => Ok(Branch::Name(name)),
+                    Ok(Branch::CurrentInSuperproject) => Err(branch::resolve::Error::InvalidName {
+                        actual: name.to_owned(),
+                        source: None,
+                    }),
+                    Err(err) => Err(branch::resolve::Error::InvalidName {
+                        actual: name.to_owned(),
+                        source: Some(err),
+                    }),
+                }
+            }
+            Branch::Name(name) => Ok(Branch::Name(name)),
+        }
+    }
+}
+
 /// Determine how `git submodule update` should deal with this submodule to bring it up-to-date with the
 /// super-project's expectations.
 #[derive(Default, Debug, Clone, Hash, PartialOrd, PartialEq, Ord, Eq)]
@@ -118,6 +186,9 @@ pub enum Update {
     /// A custom command to be called like `<command> hash-of-submodule-commit` that is to be executed to
     /// perform the submodule update.
     ///
+    /// It's the entire value after the leading `!`, including all arguments, like `tool --opt value`, with quotes
+    /// and escapes already processed by the configuration parser. Like `git`, run it with a shell to split it into arguments.
+    ///
     /// Note that this variant is only allowed if the value is coming from an override. Thus it's not allowed to distribute
     /// arbitrary commands via `.gitmodules` for security reasons.
     Command(BString),
@@ -125,6 +196,16 @@ pub enum Update {
     None,
 }
 
+impl Update {
+    /// Return `true` if the commit recorded in the superproject may have to be fetched to perform the update,
+    /// which is the case for all variants but [`Update::None`].
+    ///
+    /// Note that no fetch is needed if the commit is already present in the submodule repository.
+    pub fn requires_fetch(&self) -> bool {
+        !matches!(self, Update::None)
+    }
+}
+
 impl TryFrom<&BStr> for Update {
     type Error = ();
 
@@ -140,6 +221,33 @@ impl TryFrom<&BStr> for Update {
     }
 }
 
+/// The deciding factor for a submodule to be active or inactive, as returned by
+/// [File::names_and_active_reason()](crate::File::names_and_active_reason()).
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ActiveReason {
+    /// `submodule.<name>.active` is set to the contained value, which takes precedence over everything else.
+    ActiveField(bool),
+    /// A `submodule.active` pathspec matched the name of the submodule, making it active.
+    PathspecIncluded,
+    /// A `submodule.active` pathspec with exclusion matched the name of the submodule, making it inactive.
+    PathspecExcluded,
+    /// Nothing else applied, and the submodule is active as it has a `url`.
+    HasUrl,
+    /// Nothing else applied, and the submodule is inactive as it has no `url`.
+    NoUrl,
+}
+
+impl ActiveReason {
+    /// Return `true` if the submodule is active for this reason.
+    pub fn is_active(&self) -> bool {
+        match self {
+            ActiveReason::ActiveField(value) => *value,
+            ActiveReason::PathspecIncluded | ActiveReason::HasUrl => true,
+            ActiveReason::PathspecExcluded | ActiveReason::NoUrl => false,
+        }
+    }
+}
+
 /// The error returned by [File::fetch_recurse()](crate::File::fetch_recurse) and [File::ignore()](crate::File::ignore).
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
@@ -163,6 +271,24 @@ pub mod branch {
         pub actual: BString,
         pub source: gix_refspec::parse::Error,
     }
+
+    ///
+    pub mod resolve {
+        use bstr::BString;
+
+        /// The error returned by [Branch::resolve()](crate::config::Branch::resolve()).
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error {
+            #[error("The submodule branch is configured to follow the superproject, but the superproject is not on any branch")]
+            DetachedHead,
+            #[error("The superproject branch '{actual}' can't be used as submodule branch")]
+            InvalidName {
+                actual: BString,
+                source: Option<gix_refspec::parse::Error>,
+            },
+        }
+    }
 }
 
 ///
@@ -214,9 +340,96 @@ pub mod path {
         OutsideOfWorktree { actual: BString, submodule: BString },
     }
 }
+///
+pub mod validate {
+    use bstr::BString;
+
+    /// A problem with the configuration of submodules, as found by [File::validate()](crate::File::validate()).
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum Issue {
+        /// There is more than one `submodule.<name>` section for the submodule `name`, which are merged with the last value winning.
+        DuplicateSection {
+            /// The name of the submodule with more than one section.
+            name: BString,
+        },
+        /// The submodule `name` has more than one distinct `path` value, of which only the last one is used.
+        ConflictingPaths {
+            /// The name of the submodule with more than one path.
+            name: BString,
+            /// All distinct paths in the order of their appearance.
+            paths: Vec<BString>,
+        },
+        /// The submodules with `names` are all checked out at `path`, which can't work.
+        SharedPath {
+            /// The path shared by all submodules.
+            path: BString,
+            /// The names of all submodules sharing the path, in order of appearance.
+            names: Vec<BString>,
+        },
+        /// The submodule `name` could lead outside of `.git/modules/` if it was used as directory name,
+        /// which is why it's ignored.
+        UnsafeName {
+            /// The name of the submodule.
+            name: BString,
+        },
+    }
+}
+
+///
+pub mod submodule {
+    use bstr::BString;
+
+    /// The error returned by [File::submodule()](crate::File::submodule).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Path(#[from] super::path::Error),
+        #[error(transparent)]
+        Url(#[from] super::url::Error),
+        #[error(transparent)]
+        Update(#[from] super::update::Error),
+        #[error(transparent)]
+        Branch(#[from] super::branch::Error),
+        #[error(transparent)]
+        Field(#[from] super::Error),
+        #[error(
+            "The name of submodule '{submodule}' is unsafe to use as it could lead outside of its module directory"
+        )]
+        UnsafeName { submodule: BString },
+        #[error("The 'shallow' field of submodule '{submodule}' could not be decoded")]
+        Shallow {
+            submodule: BString,
+            source: gix_config::value::Error,
+        },
+    }
+}
+
+///
+pub mod clone_recommendation {
+    use bstr::BString;
+
+    /// The error returned by [File::clone_recommendation()](crate::File::clone_recommendation()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Branch(#[from] super::branch::Error),
+        #[error(transparent)]
+        FetchRecurse(#[from] super::Error),
+        #[error("The 'shallow' field of submodule '{submodule}' could not be decoded")]
+        Shallow {
+            submodule: BString,
+            source: gix_config::value::Error,
+        },
+    }
+}
+
 ///
 pub mod names_and_active_state {
-    /// The error returned by [File::names_and_active_state](crate::File::names_and_active_state()).
+    /// The error returned by [File::names_and_active_state](crate::File::names_and_active_state()),
+    /// [File::names_and_active_reason](crate::File::names_and_active_reason())
+    /// and [File::is_active_platform](crate::File::is_active_platform()).
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
     pub enum Error {
@@ -228,7 +441,9 @@ pub mod names_and_active_state {
 
     ///
     pub mod iter {
-        /// The error returned by the iterator of [File::names_and_active_state](crate::File::names_and_active_state()).
+        /// The error returned by the iterator of [File::names_and_active_state](crate::File::names_and_active_state())
+        /// and [File::names_and_active_reason](crate::File::names_and_active_reason()), as well as by
+        /// [IsActivePlatform::is_active](crate::IsActivePlatform::is_active()).
         #[derive(Debug, thiserror::Error)]
         #[allow(missing_docs)]
         pub enum Error {
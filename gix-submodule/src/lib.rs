@@ -2,7 +2,7 @@
 #![deny(rust_2018_idioms, missing_docs)]
 #![forbid(unsafe_code)]
 
-use bstr::BStr;
+use bstr::{BStr, BString};
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 
@@ -14,10 +14,62 @@ use std::collections::BTreeMap;
 #[derive(Clone)]
 pub struct File {
     config: gix_config::File<'static>,
+    /// A lazily computed mapping from submodule paths to their names, used by [`File::name_by_path()`].
+    names_by_path: once_cell::sync::OnceCell<BTreeMap<BString, BString>>,
+}
+
+/// All validated values of a single submodule, as obtained by [`File::submodule()`].
+///
+/// Fields that are optional in the configuration remain optional here so it stays possible to fall back
+/// to their defaults or to superproject-wide configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submodule {
+    /// The name of the submodule, as used in `submodule.<name>` sections.
+    pub name: BString,
+    /// The path relative to the root of the worktree at which the submodule is checked out.
+    pub path: BString,
+    /// The url to fetch the submodule from.
+    pub url: gix_url::Url,
+    /// How `git submodule update` should update the submodule, if set.
+    pub update: Option<config::Update>,
+    /// The branch to track on the remote, if set.
+    pub branch: Option<config::Branch>,
+    /// How the submodule participates in `git status`, if set.
+    pub ignore: Option<config::Ignore>,
+    /// How to recurse into the submodule when fetching, if set.
+    pub fetch_recurse: Option<config::FetchRecurse>,
+    /// Whether the submodule should be cloned shallowly, if set.
+    pub shallow: Option<bool>,
+}
+
+/// The settings to use when cloning a submodule, as obtained by [`File::clone_recommendation()`].
+///
+/// Unlike in [`Submodule`], the defaults that apply if a field isn't set are filled in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloneRecommendation {
+    /// Whether the submodule should be cloned shallowly, which is `false` if unset.
+    pub shallow: bool,
+    /// How to recurse into the submodule when fetching, which is [`FetchRecurse::OnDemand`](config::FetchRecurse::OnDemand) if unset.
+    ///
+    /// Note that if it's unset in the submodule configuration, `fetch.recurseSubmodules` of the superproject should take precedence.
+    pub fetch_recurse: config::FetchRecurse,
+    /// The branch to track on the remote, which is the remote `HEAD` if unset.
+    pub branch: config::Branch,
+}
+
+/// A platform to determine whether submodules are active, which compiles `submodule.active` pathspecs only once,
+/// as obtained by [`File::is_active_platform()`].
+pub struct IsActivePlatform<'a> {
+    modules: &'a File,
+    config: &'a gix_config::File<'static>,
+    search: Option<gix_pathspec::Search>,
 }
 
 mod access;
 
+/// The fields of a submodule that can be overridden by [`File::append_submodule_overrides()`].
+const OVERRIDABLE_FIELDS: &[&str] = &["url", "fetchRecurseSubmodules", "ignore", "update", "branch", "shallow"];
+
 ///
 pub mod config;
 
@@ -30,6 +82,7 @@ impl File {
     /// * `ignore`
     /// * `update`
     /// * `branch`
+    /// * `shallow`
     ///
     /// These values aren't validated yet, which will happen upon query.
     pub fn append_submodule_overrides(&mut self, config: &gix_config::File<'_>) -> &mut Self {
@@ -40,7 +93,7 @@ impl File {
             .flatten()
             .filter_map(|s| s.header().subsection_name().map(|n| (n, s)))
         {
-            for field in ["url", "fetchRecurseSubmodules", "ignore", "update", "branch"] {
+            for field in OVERRIDABLE_FIELDS.iter().copied() {
                 if let Some(value) = section.value(field) {
                     values.entry((module_name, field)).or_default().push(value);
                 }
@@ -113,7 +166,10 @@ mod init {
                 metadata,
             );
 
-            Ok(Self { config })
+            Ok(Self {
+                config,
+                names_by_path: Default::default(),
+            })
         }
 
         /// Turn ourselves into the underlying parsed configuration file.
@@ -4,6 +4,7 @@ fn submodule(bytes: &str) -> gix_submodule::File {
 
 mod names_and_active_state {
     use bstr::{BStr, ByteSlice};
+    use gix_submodule::config::ActiveReason;
     use std::str::FromStr;
 
     fn multi_modules() -> crate::Result<gix_submodule::File> {
@@ -103,6 +104,208 @@ mod names_and_active_state {
         );
         Ok(())
     }
+
+    fn assume_valid_active_reason<'a>(
+        module: &'a gix_submodule::File,
+        config: &'a gix_config::File<'static>,
+    ) -> crate::Result<Vec<(&'a str, ActiveReason)>> {
+        Ok(module
+            .names_and_active_reason(config, Default::default(), |_, _, _, _| {
+                unreachable!("shouldn't be called")
+            })?
+            .map(|(name, reason)| (name.to_str().expect("valid"), reason.expect("valid")))
+            .collect())
+    }
+
+    #[test]
+    fn the_reason_for_the_active_state_can_be_obtained() -> crate::Result {
+        let module = multi_modules()?;
+        let config = gix_config::File::from_str(
+            "[submodule.submodule]\n active = 0\n[submodule \"a/b\"]\n active = true\n[submodule]\n active = *\n[submodule]\n active = :!a*",
+        )?;
+        assert_eq!(
+            assume_valid_active_reason(&module, &config)?,
+            &[
+                ("submodule", ActiveReason::ActiveField(false)),
+                ("a/b", ActiveReason::ActiveField(true)),
+                (".a/..c", ActiveReason::PathspecIncluded),
+                ("a/d\\", ActiveReason::PathspecExcluded),
+                ("a\\e", ActiveReason::PathspecExcluded)
+            ],
+            "the 'active' field overrides pathspecs, which are the reason for all other submodules"
+        );
+        for (name, reason) in assume_valid_active_reason(&module, &config)? {
+            assert_eq!(
+                reason.is_active(),
+                assume_valid_active_state(&module, &config, Default::default())?
+                    .into_iter()
+                    .find_map(|(n, active)| (n == name).then_some(active))
+                    .expect("present"),
+                "the reason is consistent with the active state"
+            );
+        }
+
+        assert_eq!(
+            assume_valid_active_reason(&module, &Default::default())?[0],
+            ("submodule", ActiveReason::HasUrl),
+            "without any configuration, it's the url that makes a submodule active"
+        );
+
+        let module = crate::file::submodule("[submodule.a]\n path = a");
+        assert_eq!(
+            assume_valid_active_reason(&module, &Default::default())?,
+            &[("a", ActiveReason::NoUrl)],
+            "submodules without url are inactive"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn the_platform_can_be_queried_repeatedly() -> crate::Result {
+        let module = multi_modules()?;
+        let config = gix_config::File::from_str(
+            "[submodule.submodule]\n active = 0\n[submodule]\n active = *\n[submodule]\n active = :!a*",
+        )?;
+        let expected = assume_valid_active_state(&module, &config, Default::default())?;
+        let mut platform = module.is_active_platform(&config, Default::default())?;
+        for _round in 0..2 {
+            for (name, is_active) in &expected {
+                assert_eq!(
+                    platform.is_active((*name).into(), |_, _, _, _| unreachable!("shouldn't be called"))?,
+                    *is_active,
+                    "{name}: the same as when iterating all names"
+                );
+            }
+        }
+        assert_eq!(
+            platform.active_reason("a/b".into(), |_, _, _, _| unreachable!("shouldn't be called"))?,
+            ActiveReason::PathspecExcluded
+        );
+        Ok(())
+    }
+}
+
+mod validate {
+    use crate::file::submodule;
+    use gix_submodule::config::validate::Issue;
+
+    #[test]
+    fn valid_modules_have_no_issues() -> crate::Result {
+        let modules = gix_testtools::scripted_fixture_read_only("basic.sh")?
+            .join("multiple")
+            .join(".gitmodules");
+        let module = gix_submodule::File::from_bytes(std::fs::read(&modules)?.as_slice(), modules)?;
+        assert_eq!(module.validate(), []);
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_sections_and_conflicting_paths() {
+        let module = submodule(
+            "[submodule.a]\n path = a\n url = https://example.com/a\n\
+             [submodule.b]\n path = b\n\
+             [submodule.a]\n path = c\n\
+             [submodule.a]\n url = https://example.com/other\n\
+             [submodule.c]\n path = b\n",
+        );
+        assert_eq!(
+            module.validate(),
+            [
+                Issue::DuplicateSection { name: "a".into() },
+                Issue::ConflictingPaths {
+                    name: "a".into(),
+                    paths: vec!["a".into(), "c".into()]
+                },
+                Issue::SharedPath {
+                    path: "b".into(),
+                    names: vec!["b".into(), "c".into()]
+                }
+            ],
+            "each duplicate section is reported once, and paths are compared after the last value won"
+        );
+    }
+
+    #[test]
+    fn unsafe_names_are_reported_once() {
+        let module = submodule(
+            "[submodule \"../a\"]\n path = a\n\
+             [submodule.b]\n path = b\n\
+             [submodule \"../a\"]\n url = https://example.com/a\n",
+        );
+        assert_eq!(
+            module.validate(),
+            [Issue::UnsafeName { name: "../a".into() }],
+            "the ignored submodule isn't a duplicate either"
+        );
+    }
+
+    #[test]
+    fn overrides_are_not_duplicates() {
+        let mut module = submodule("[submodule.a]\n path = a\n url = https://example.com/a");
+        module.append_submodule_overrides(
+            &gix_config::File::try_from("[submodule.a]\n url = https://example.com/override").expect("valid"),
+        );
+        assert_eq!(module.validate(), []);
+    }
+}
+
+mod name_by_path {
+    use crate::file::submodule;
+    use gix_submodule::config::validate::Issue;
+
+    #[test]
+    fn paths_map_to_names_in_the_multi_module_fixture() -> crate::Result {
+        let modules = gix_testtools::scripted_fixture_read_only("basic.sh")?
+            .join("multiple")
+            .join(".gitmodules");
+        let module = gix_submodule::File::from_bytes(std::fs::read(&modules)?.as_slice(), modules)?;
+        for (path, expected) in [
+            ("submodule", Some("submodule")),
+            ("a/b", Some("a/b")),
+            ("a\\c", Some(".a/..c")),
+            ("a/d\\", Some("a/d\\")),
+            ("a/e", Some("a\\e")),
+            ("a", None),
+            ("does-not-exist", None),
+        ] {
+            assert_eq!(
+                module.name_by_path(path.into()),
+                expected.map(Into::into),
+                "names may differ from paths, and only exact paths match"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn submodules_without_valid_path_are_ignored() -> crate::Result {
+        let module = submodule("[submodule.a]\n path = a\n[submodule.b]\n[submodule.c]\n path = ../c");
+        assert_eq!(module.name_by_path("a".into()), Some("a".into()));
+        assert_eq!(module.name_by_path("../c".into()), None);
+        Ok(())
+    }
+
+    #[test]
+    fn shared_paths_are_ambiguous_and_reported_by_validate() {
+        let module = submodule("[submodule.a]\n path = p\n[submodule.b]\n path = p\n[submodule.c]\n path = c");
+        assert_eq!(
+            module.name_by_path("p".into()),
+            None,
+            "it's unclear which submodule is meant"
+        );
+        assert_eq!(
+            module.name_by_path("c".into()),
+            Some("c".into()),
+            "other paths are unaffected"
+        );
+        assert_eq!(
+            module.validate(),
+            [Issue::SharedPath {
+                path: "p".into(),
+                names: vec!["a".into(), "b".into()]
+            }]
+        );
+    }
 }
 
 mod path {
@@ -133,6 +336,10 @@ mod path {
         ));
         assert!(matches!(submodule_path(""), Error::Missing { .. }));
         assert!(matches!(submodule_path("../attack"), Error::OutsideOfWorktree { .. }));
+        assert!(matches!(
+            submodule_path("a/../../attack"),
+            Error::OutsideOfWorktree { .. }
+        ));
 
         {
             let module = submodule("[submodule.a]\n path");
@@ -144,6 +351,45 @@ mod path {
             assert!(matches!(module.path("a".into()).unwrap_err(), Error::Missing { .. }));
         }
     }
+
+    #[test]
+    fn absolute_paths_of_all_platforms_are_rejected_everywhere() {
+        for absolute in [
+            "/definitely/absolute",
+            "\\\\absolute\\\\without-drive",
+            "\\\\\\\\server\\\\share\\\\unc",
+            "//server/share/unc",
+            "c:\\\\hello",
+            "C:/hello",
+            "c:relative-to-drive",
+        ] {
+            assert!(
+                matches!(submodule_path(absolute), Error::Absolute { .. }),
+                "{absolute:?} is absolute on at least one platform"
+            );
+        }
+    }
+
+    #[test]
+    fn backslashes_are_separators_only_on_windows() -> crate::Result {
+        let module = submodule("[submodule.a]\n path = a\\\\..\\\\..\\\\attack");
+        let res = module.path("a".into());
+        if cfg!(windows) {
+            assert!(matches!(res.unwrap_err(), Error::OutsideOfWorktree { .. }));
+        } else {
+            assert_eq!(
+                res?.as_ref(),
+                "a\\..\\..\\attack",
+                "a single component with backslashes in its name"
+            );
+        }
+
+        for valid in ["a/d\\\\", "a\\\\e"] {
+            let module = submodule(&format!("[submodule.a]\n path = {valid}"));
+            assert!(module.path("a".into()).is_ok(), "{valid:?} stays within the worktree");
+        }
+        Ok(())
+    }
 }
 
 mod url {
@@ -209,6 +455,19 @@ mod update {
         Ok(())
     }
 
+    #[test]
+    fn requires_fetch() {
+        for (update, expected) in [
+            (Update::Checkout, true),
+            (Update::Rebase, true),
+            (Update::Merge, true),
+            (Update::Command("cmd".into()), true),
+            (Update::None, false),
+        ] {
+            assert_eq!(update.requires_fetch(), expected, "{update:?}");
+        }
+    }
+
     #[test]
     fn valid_in_overrides() -> crate::Result {
         let mut module = submodule("[submodule.a]\n update = merge");
@@ -223,6 +482,26 @@ mod update {
         Ok(())
     }
 
+    #[test]
+    fn commands_in_overrides_keep_their_arguments() -> crate::Result {
+        for (value, expected) in [
+            ("!tool --opt value", "tool --opt value"),
+            ("!path/to/script --flag", "path/to/script --flag"),
+            (r#"!tool --opt \"quoted value\""#, r#"tool --opt "quoted value""#),
+            (r#"!tool "--opt  value""#, "tool --opt  value"),
+        ] {
+            let mut module = submodule("[submodule.a]\n update = merge");
+            let repo_config = gix_config::File::from_str(&format!("[submodule.a]\n update = {value}"))?;
+            module.append_submodule_overrides(&repo_config);
+            assert_eq!(
+                module.update("a".into())?.expect("present"),
+                Update::Command(expected.into()),
+                "the whole command line is kept for a shell to interpret, after unquoting by the configuration like git does: {value:?}"
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     fn validate_upon_retrieval() {
         assert!(matches!(submodule_update(""), Error::Invalid { .. }));
@@ -272,11 +551,51 @@ mod fetch_recurse {
         Ok(())
     }
 
+    #[test]
+    fn display_round_trips() -> crate::Result {
+        for (value, expected) in [
+            (FetchRecurse::OnDemand, "on-demand"),
+            (FetchRecurse::Always, "true"),
+            (FetchRecurse::Never, "false"),
+        ] {
+            assert_eq!(value.as_str(), expected);
+            assert_eq!(value.to_string(), expected);
+            let module = submodule(&format!("[submodule.a]\n fetchRecurseSubmodules = {value}"));
+            assert_eq!(
+                module.fetch_recurse("a".into())?.expect("present"),
+                value,
+                "parsing the displayed value yields the original"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn surrounding_whitespace_is_ignored() -> crate::Result {
+        for (quoted, expected) in [
+            (" on-demand ", FetchRecurse::OnDemand),
+            ("\ton-demand", FetchRecurse::OnDemand),
+            (" yes ", FetchRecurse::Always),
+            (" FALSE", FetchRecurse::Never),
+        ] {
+            let module = submodule(&format!("[submodule.a]\n fetchRecurseSubmodules = \"{quoted}\""));
+            assert_eq!(
+                module.fetch_recurse("a".into())?.expect("present"),
+                expected,
+                "quoted whitespace is trimmed before interpreting {quoted:?}"
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     fn validate_upon_retrieval() -> crate::Result {
-        for invalid in ["foo", "ney", "On-demand"] {
+        for invalid in ["foo", "ney", "On-demand", "ON-DEMAND", " ", "on demand"] {
             let module = submodule(&format!("[submodule.a]\n fetchRecurseSubmodules = \"{invalid}\""));
-            assert!(module.fetch_recurse("a".into()).is_err());
+            assert!(
+                module.fetch_recurse("a".into()).is_err(),
+                "{invalid:?} is rejected as `on-demand` is case-sensitive, unlike booleans"
+            );
         }
         Ok(())
     }
@@ -310,6 +629,26 @@ mod ignore {
         Ok(())
     }
 
+    #[test]
+    fn display_round_trips() -> crate::Result {
+        for (value, expected) in [
+            (Ignore::All, "all"),
+            (Ignore::Dirty, "dirty"),
+            (Ignore::Untracked, "untracked"),
+            (Ignore::None, "none"),
+        ] {
+            assert_eq!(value.as_str(), expected);
+            assert_eq!(value.to_string(), expected);
+            let module = submodule(&format!("[submodule.a]\n ignore = {value}"));
+            assert_eq!(
+                module.ignore("a".into())?.expect("present"),
+                value,
+                "parsing the displayed value yields the original"
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     fn validate_upon_retrieval() -> crate::Result {
         for invalid in ["All", ""] {
@@ -353,17 +692,289 @@ mod branch {
         assert!(module.branch("a".into()).is_err());
         Ok(())
     }
+
+    mod resolve {
+        use gix_submodule::config::{branch::resolve::Error, Branch};
+
+        #[test]
+        fn current_in_superproject_with_attached_head() -> crate::Result {
+            assert_eq!(
+                Branch::CurrentInSuperproject.resolve(Some("main".into()))?,
+                Branch::Name("main".into())
+            );
+            assert_eq!(
+                Branch::Name("other".into()).resolve(Some("main".into()))?,
+                Branch::Name("other".into()),
+                "named branches are kept as is"
+            );
+            assert!(matches!(
+                Branch::CurrentInSuperproject.resolve(Some("/invalid".into())),
+                Err(Error::InvalidName { .. })
+            ));
+            Ok(())
+        }
+
+        #[test]
+        fn current_in_superproject_with_detached_head() -> crate::Result {
+            assert!(matches!(
+                Branch::CurrentInSuperproject.resolve(None),
+                Err(Error::DetachedHead)
+            ));
+            assert_eq!(
+                Branch::Name("other".into()).resolve(None)?,
+                Branch::Name("other".into()),
+                "only following the superproject needs its branch"
+            );
+            Ok(())
+        }
+    }
 }
 
-#[test]
-fn shallow() -> crate::Result {
-    let module = submodule("[submodule.a]\n shallow");
-    assert_eq!(
-        module.shallow("a".into())?,
-        Some(true),
-        "shallow is a simple boolean without anything special (yet)"
-    );
-    Ok(())
+mod shallow {
+    use crate::file::submodule;
+    use std::str::FromStr;
+
+    #[test]
+    fn default() -> crate::Result {
+        let module = submodule("[submodule.a]\n path = a");
+        assert_eq!(
+            module.shallow("a".into())?,
+            None,
+            "an unset value is distinguishable from an explicit `false`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn valid() -> crate::Result {
+        let module = submodule("[submodule.a]\n shallow");
+        assert_eq!(
+            module.shallow("a".into())?,
+            Some(true),
+            "shallow is a simple boolean without anything special (yet)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn valid_in_overrides() -> crate::Result {
+        let mut module = submodule("[submodule.a]\n shallow = true");
+        let repo_config = gix_config::File::from_str("[submodule.a]\n shallow = false")?;
+        module.append_submodule_overrides(&repo_config);
+
+        assert_eq!(
+            module.shallow("a".into())?,
+            Some(false),
+            "local configuration can turn off shallow clones"
+        );
+        Ok(())
+    }
+
+    mod effective {
+        use crate::file::submodule;
+        use std::str::FromStr;
+
+        #[test]
+        fn defaults_to_false() -> crate::Result {
+            let module = submodule("[submodule.a]\n path = a");
+            let config = gix_config::File::default();
+            assert!(!module.effective_shallow("a".into(), None, &config)?);
+            Ok(())
+        }
+
+        #[test]
+        fn module_value_is_used_if_nothing_else_is_set() -> crate::Result {
+            let module = submodule("[submodule.a]\n shallow");
+            let config = gix_config::File::from_str("[submodule.b]\n shallow = false")?;
+            assert!(module.effective_shallow("a".into(), None, &config)?);
+            Ok(())
+        }
+
+        #[test]
+        fn config_overrides_module_value() -> crate::Result {
+            let module = submodule("[submodule.a]\n shallow");
+            let config = gix_config::File::from_str("[submodule.a]\n shallow = false")?;
+            assert!(!module.effective_shallow("a".into(), None, &config)?);
+            Ok(())
+        }
+
+        #[test]
+        fn cli_overrides_everything() -> crate::Result {
+            let module = submodule("[submodule.a]\n shallow");
+            let config = gix_config::File::from_str("[submodule.a]\n shallow = true")?;
+            assert!(!module.effective_shallow("a".into(), Some(false), &config)?);
+
+            let module = submodule("[submodule.a]\n shallow = invalid");
+            let config = gix_config::File::from_str("[submodule.a]\n shallow = invalid")?;
+            assert!(
+                module.effective_shallow("a".into(), Some(true), &config)?,
+                "invalid values aren't even looked at"
+            );
+            assert!(module.effective_shallow("a".into(), None, &config).is_err());
+            Ok(())
+        }
+    }
+}
+
+mod submodule {
+    use crate::file::submodule;
+    use gix_submodule::config::{submodule::Error, Branch, FetchRecurse, Ignore, Update};
+
+    #[test]
+    fn all_fields_are_resolved_at_once() -> crate::Result {
+        let module = submodule(
+            "[submodule.a]\n path = a/path\n url = https://example.com/a\n update = rebase\n branch = .\n ignore = dirty\n fetchRecurseSubmodules = false\n shallow",
+        );
+        let sm = module.submodule("a".into())?;
+        assert_eq!(sm.name, "a");
+        assert_eq!(sm.path, "a/path");
+        assert_eq!(sm.url.to_bstring(), "https://example.com/a");
+        assert_eq!(sm.update, Some(Update::Rebase));
+        assert_eq!(sm.branch, Some(Branch::CurrentInSuperproject));
+        assert_eq!(sm.ignore, Some(Ignore::Dirty));
+        assert_eq!(sm.fetch_recurse, Some(FetchRecurse::Never));
+        assert_eq!(sm.shallow, Some(true));
+        Ok(())
+    }
+
+    #[test]
+    fn optional_fields_remain_unset() -> crate::Result {
+        let module = submodule("[submodule.a]\n path = a\n url = https://example.com/a");
+        let sm = module.submodule("a".into())?;
+        assert_eq!(sm.update, None);
+        assert_eq!(sm.branch, None);
+        assert_eq!(sm.ignore, None);
+        assert_eq!(sm.fetch_recurse, None);
+        assert_eq!(sm.shallow, None);
+        Ok(())
+    }
+
+    #[test]
+    fn errors_carry_the_failing_field() {
+        let module = submodule("[submodule.a]\n path = a\n url = https://example.com/a\n ignore = bogus");
+        let err = module.submodule("a".into()).unwrap_err();
+        assert!(matches!(err, Error::Field(ref err) if err.field == "ignore"));
+        assert_eq!(
+            err.to_string(),
+            "The 'ignore' field of submodule 'a' was invalid: 'bogus'"
+        );
+
+        let module = submodule("[submodule.a]\n url = https://example.com/a");
+        assert!(matches!(
+            module.submodule("a".into()).unwrap_err(),
+            Error::Path(gix_submodule::config::path::Error::Missing { .. })
+        ));
+
+        let module = submodule("[submodule.a]\n path = a\n url = https://example.com/a\n shallow = maybe");
+        assert_eq!(
+            module.submodule("a".into()).unwrap_err().to_string(),
+            "The 'shallow' field of submodule 'a' could not be decoded"
+        );
+    }
+
+    #[test]
+    fn unsafe_names_are_rejected() {
+        for name in [
+            "..",
+            "../attack",
+            "a/../../attack",
+            "a/..",
+            "..\\attack",
+            "a\\..\\attack",
+            "a\\..",
+        ] {
+            let module = submodule(&format!(
+                "[submodule {name:?}]\n path = a\n url = https://example.com/a"
+            ));
+            assert!(
+                matches!(module.submodule(name.into()), Err(Error::UnsafeName { .. })),
+                "{name:?} could escape .git/modules/"
+            );
+        }
+    }
+
+    #[test]
+    fn names_resembling_traversal_are_fine() -> crate::Result {
+        for name in ["a..b", "...", "..a/b", "a\\b", "a/b..\\c"] {
+            let module = submodule(&format!(
+                "[submodule {name:?}]\n path = a\n url = https://example.com/a"
+            ));
+            assert_eq!(module.submodule(name.into())?.name, name);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn names_with_control_characters_are_rejected() {
+        let module = submodule("[submodule \"a\tb\"]\n path = a\n url = https://example.com/a");
+        assert!(matches!(module.submodule("a\tb".into()), Err(Error::UnsafeName { .. })));
+    }
+
+    #[test]
+    fn unsafe_names_are_skipped_when_listing_names() {
+        let module = submodule(
+            "[submodule \"../attack\"]\n path = a\n url = https://example.com/a\n\
+             [submodule \"a\tb\"]\n path = b\n url = https://example.com/b\n\
+             [submodule.c]\n path = c\n url = https://example.com/c",
+        );
+        assert_eq!(
+            module.names().collect::<Vec<_>>(),
+            ["c"],
+            "like git, submodules with unsafe names are ignored"
+        );
+        assert_eq!(
+            module.unsafe_names().collect::<Vec<_>>(),
+            ["../attack", "a\tb"],
+            "but they can still be listed"
+        );
+    }
+}
+
+mod clone_recommendation {
+    use crate::file::submodule;
+    use gix_submodule::{
+        config::{Branch, FetchRecurse},
+        CloneRecommendation,
+    };
+
+    #[test]
+    fn set_fields_are_used() -> crate::Result {
+        let module = submodule("[submodule.a]\n path = a\n url = https://example.com/a\n shallow\n branch = stable");
+        assert_eq!(
+            module.clone_recommendation("a".into())?,
+            CloneRecommendation {
+                shallow: true,
+                fetch_recurse: FetchRecurse::OnDemand,
+                branch: Branch::Name("stable".into()),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn defaults_are_applied_to_unset_fields() -> crate::Result {
+        let module = submodule("[submodule.a]\n path = a\n url = https://example.com/a");
+        assert_eq!(
+            module.clone_recommendation("a".into())?,
+            CloneRecommendation {
+                shallow: false,
+                fetch_recurse: FetchRecurse::OnDemand,
+                branch: Branch::Name("HEAD".into()),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_fields_are_errors() {
+        let module = submodule("[submodule.a]\n shallow = maybe");
+        assert_eq!(
+            module.clone_recommendation("a".into()).unwrap_err().to_string(),
+            "The 'shallow' field of submodule 'a' could not be decoded"
+        );
+        let module = submodule("[submodule.a]\n fetchRecurseSubmodules = sometimes");
+        assert!(module.clone_recommendation("a".into()).is_err());
+    }
 }
 
 mod append_submodule_overrides {
@@ -380,4 +991,80 @@ mod append_submodule_overrides {
     }
 }
 
+mod overridden_fields {
+    use crate::file::submodule;
+    use std::str::FromStr;
+
+    #[test]
+    fn only_fields_from_overrides_are_reported() -> crate::Result {
+        let mut module = submodule("[submodule.a]\n url = a\n update = merge\n[submodule.b]\n url = b");
+        assert!(
+            module.overridden_fields("a".into()).is_empty(),
+            "nothing is overridden initially"
+        );
+
+        let repo_config = gix_config::File::from_str(
+            "[submodule.a]\n update = !dangerous\n ignore = all\n active = true\n[submodule.c]\n url = c",
+        )?;
+        module.append_submodule_overrides(&repo_config);
+        assert_eq!(
+            module.overridden_fields("a".into()),
+            ["ignore", "update"],
+            "unknown keys aren't taken from overrides, and the order is fixed"
+        );
+        assert!(module.overridden_fields("b".into()).is_empty());
+        assert_eq!(
+            module.overridden_fields("c".into()),
+            ["url"],
+            "submodules that only exist in overrides are reported as well"
+        );
+        Ok(())
+    }
+}
+
+mod names_with_mismatched_case {
+    use crate::file::submodule;
+    use std::str::FromStr;
+
+    #[test]
+    fn overrides_differing_only_in_case_are_detected() -> crate::Result {
+        let module = submodule("[submodule \"Name\"]\n url = a\n[submodule.other]\n url = b");
+        let repo_config =
+            gix_config::File::from_str("[submodule \"name\"]\n update = none\n[submodule \"other\"]\n url = c")?;
+        assert_eq!(module.names_with_mismatched_case(&repo_config), ["Name"]);
+
+        let repo_config = gix_config::File::from_str("[submodule \"Name\"]\n update = none")?;
+        assert!(
+            module.names_with_mismatched_case(&repo_config).is_empty(),
+            "exact matches are fine"
+        );
+        Ok(())
+    }
+}
+
+mod orphaned_overrides {
+    use crate::file::submodule;
+    use std::str::FromStr;
+
+    #[test]
+    fn overrides_without_submodule_are_detected() -> crate::Result {
+        let mut module = submodule("[submodule.a]\n url = a\n path = a");
+        let repo_config = gix_config::File::from_str(
+            "[submodule]\n active = a\n[submodule.a]\n update = none\n[submodule.removed]\n url = b\n[submodule.removed]\n update = none",
+        )?;
+        assert_eq!(module.orphaned_overrides(&repo_config), ["removed"]);
+
+        module.append_submodule_overrides(&repo_config);
+        assert_eq!(
+            module.orphaned_overrides(&repo_config),
+            ["removed"],
+            "appended overrides don't make the submodule known"
+        );
+
+        let repo_config = gix_config::File::from_str("[submodule.a]\n update = none")?;
+        assert!(module.orphaned_overrides(&repo_config).is_empty());
+        Ok(())
+    }
+}
+
 mod baseline;
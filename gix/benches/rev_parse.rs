@@ -0,0 +1,12 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn ambiguous_prefix(c: &mut Criterion) {
+    let dir = gix_testtools::scripted_fixture_read_only("make_rev_spec_parse_repos.sh").expect("fixture");
+    let repo = gix::open_opts(dir.join("ambiguous_commits"), gix::open::Options::isolated()).expect("valid repo");
+    for spec in ["0000000000f^{tree}", "000000000..000000000", "00000000^{commit}"] {
+        c.bench_function(spec, |b| b.iter(|| black_box(repo.rev_parse(spec)).ok()));
+    }
+}
+
+criterion_group!(benches, ambiguous_prefix);
+criterion_main!(benches);
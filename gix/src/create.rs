@@ -8,6 +8,8 @@ use std::{
 use gix_config::parse::section;
 use gix_discover::DOT_GIT_DIR;
 
+use crate::bstr::BString;
+
 /// The error used in [`into()`].
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
@@ -24,6 +26,8 @@ pub enum Error {
     DirectoryNotEmpty { path: PathBuf },
     #[error("Could not create directory at '{}'", .path.display())]
     CreateDirectory { source: std::io::Error, path: PathBuf },
+    #[error("A separate git directory can only be used for repositories with a worktree")]
+    SeparateGitDirInBareRepository,
 }
 
 /// The kind of repository to create.
@@ -35,6 +39,37 @@ pub enum Kind {
     Bare,
 }
 
+/// Determine the permissions of files and directories created in a new repository, similar to `core.sharedRepository`.
+///
+/// Note that permissions are only adjusted on Unix.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SharedRepository {
+    /// Use the permissions that the process umask allows, just like any other file or directory created by the process.
+    #[default]
+    Umask,
+    /// Make files and directories group-writable in addition to what the umask allows.
+    Group,
+    /// Like [`Group`](SharedRepository::Group), but also make files and directories readable by everyone.
+    All,
+    /// Set the permissions of files to exactly these bits, like `0o640`, regardless of the umask.
+    ///
+    /// Directories additionally receive execute bits for those who can read them.
+    Mode(u32),
+}
+
+impl SharedRepository {
+    /// The value to write into `core.sharedRepository`, or `None` if the default applies.
+    fn config_value(&self) -> Option<String> {
+        Some(match self {
+            SharedRepository::Umask => return None,
+            SharedRepository::Group => "1".into(),
+            SharedRepository::All => "2".into(),
+            SharedRepository::Mode(mode) => format!("{:04o}", mode & 0o777),
+        })
+    }
+}
+
+const TPL_PACKED_REFS: &[u8] = b"# pack-refs with: peeled fully-peeled sorted \n";
 const TPL_INFO_EXCLUDE: &[u8] = include_bytes!("assets/init/info/exclude");
 const TPL_HOOKS_APPLYPATCH_MSG: &[u8] = include_bytes!("assets/init/hooks/applypatch-msg.sample");
 const TPL_HOOKS_COMMIT_MSG: &[u8] = include_bytes!("assets/init/hooks/commit-msg.sample");
@@ -52,7 +87,7 @@ const TPL_HEAD: &[u8] = include_bytes!("assets/init/HEAD");
 
 struct PathCursor<'a>(&'a mut PathBuf);
 
-struct NewDir<'a>(&'a mut PathBuf);
+struct NewDir<'a>(&'a mut PathBuf, SharedRepository);
 
 impl<'a> PathCursor<'a> {
     fn at(&mut self, component: &str) -> &Path {
@@ -64,7 +99,7 @@ impl<'a> PathCursor<'a> {
 impl<'a> NewDir<'a> {
     fn at(self, component: &str) -> Result<Self, Error> {
         self.0.push(component);
-        create_dir(self.0)?;
+        create_dir(self.0, self.1)?;
         Ok(self)
     }
     fn as_mut(&mut self) -> &mut PathBuf {
@@ -84,39 +119,150 @@ impl<'a> Drop for PathCursor<'a> {
     }
 }
 
-fn write_file(data: &[u8], path: &Path) -> Result<(), Error> {
-    let mut file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .append(false)
-        .open(path)
-        .map_err(|e| Error::IoOpen {
-            source: e,
-            path: path.to_owned(),
-        })?;
+fn write_file(data: &[u8], path: &Path, shared: SharedRepository) -> Result<(), Error> {
+    write_file_with_mode(data, path, shared, false)
+}
+
+/// Like [`write_file()`], but if `executable` is true, create the file with mode `0o755` on Unix, subject to the umask.
+fn write_file_with_mode(data: &[u8], path: &Path, shared: SharedRepository, executable: bool) -> Result<(), Error> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).append(false);
+    #[cfg(unix)]
+    if executable {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o755);
+    }
+    #[cfg(not(unix))]
+    let _ = executable;
+    let mut file = options.open(path).map_err(|e| Error::IoOpen {
+        source: e,
+        path: path.to_owned(),
+    })?;
     file.write_all(data).map_err(|e| Error::IoWrite {
         source: e,
         path: path.to_owned(),
-    })
+    })?;
+    adjust_permissions(path, shared)
 }
 
-fn create_dir(p: &Path) -> Result<(), Error> {
+fn create_dir(p: &Path, shared: SharedRepository) -> Result<(), Error> {
     fs::create_dir_all(p).map_err(|e| Error::CreateDirectory {
         source: e,
         path: p.to_owned(),
-    })
+    })?;
+    adjust_permissions(p, shared)
+}
+
+/// Adjust the permissions of the existing file or directory at `path` according to `shared`, like `adjust_shared_perm()` in `git`.
+#[cfg(unix)]
+fn adjust_permissions(path: &Path, shared: SharedRepository) -> Result<(), Error> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut tweak = match shared {
+        SharedRepository::Umask => return Ok(()),
+        SharedRepository::Group => 0o660,
+        SharedRepository::All => 0o664,
+        SharedRepository::Mode(mode) => mode & 0o666,
+    };
+    let io_err = |err| Error::IoWrite {
+        source: err,
+        path: path.to_owned(),
+    };
+    let meta = fs::metadata(path).map_err(io_err)?;
+    let old_mode = meta.permissions().mode();
+    if old_mode & 0o200 == 0 {
+        tweak &= !0o222;
+    }
+    if meta.is_dir() || old_mode & 0o100 != 0 {
+        tweak |= (tweak & 0o444) >> 2;
+    }
+    let mut new_mode = match shared {
+        SharedRepository::Mode(_) => (old_mode & !0o777) | tweak,
+        _ => old_mode | tweak,
+    };
+    if meta.is_dir() {
+        // Let new files and directories inherit the group of their parent directory.
+        new_mode |= 0o2000;
+    }
+    if new_mode != old_mode {
+        fs::set_permissions(path, fs::Permissions::from_mode(new_mode)).map_err(io_err)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn adjust_permissions(_path: &Path, _shared: SharedRepository) -> Result<(), Error> {
+    Ok(())
 }
 
 /// Options for use in [`into()`];
-#[derive(Copy, Clone, Default)]
+#[derive(Clone, Default)]
 pub struct Options {
     /// If true, and the kind of repository to create has a worktree, then the destination directory must be empty.
     ///
     /// By default repos with worktree can be initialized into a non-empty repository as long as there is no `.git` directory.
     pub destination_must_be_empty: bool,
-    /// If set, use these filesystem capabilities to populate the respective gix-config fields.
-    /// If `None`, the directory will be probed.
+    /// If set, use these filesystem capabilities to populate `core.filemode`, `core.symlinks`, `core.ignorecase`
+    /// and `core.precomposeunicode`.
+    /// If `None`, the filesystem the repository is created on will be probed for them like `git init` does,
+    /// which involves creating and removing a few files. Setting it avoids this cost.
     pub fs_capabilities: Option<gix_fs::Capabilities>,
+    /// Determine the permissions of all created files and directories, and set `core.sharedRepository` accordingly.
+    ///
+    /// By default, the process umask is respected.
+    pub shared_repository: SharedRepository,
+    /// If set, create a commit with an empty tree and point the default branch to it once the repository is initialized.
+    ///
+    /// Note that this is only done by [`ThreadSafeRepository::init_opts()`][crate::ThreadSafeRepository::init_opts()],
+    /// as it needs the repository configuration to know the author and committer.
+    pub initial_commit: Option<InitialCommit>,
+    /// If set, write this into `HEAD` instead of pointing it to the default branch as configured by `init.defaultBranch`.
+    ///
+    /// Like the initial commit, this is only done by [`ThreadSafeRepository::init_opts()`][crate::ThreadSafeRepository::init_opts()].
+    pub head: Option<HeadTarget>,
+    /// If true, the sample hooks in `hooks/*.sample` are created executable on Unix, like `git` does when copying them
+    /// from its template directory. On other platforms, they are created just the same.
+    ///
+    /// By default, they are created like any other file.
+    pub executable_sample_hooks: bool,
+    /// If set, create the git directory at this location instead of within the worktree, and link to it from the worktree
+    /// with a `.git` file, like `git init --separate-git-dir` does.
+    ///
+    /// The git directory must not exist or be empty, and this is only possible with [`Kind::WithWorktree`].
+    pub separate_git_dir: Option<PathBuf>,
+    /// If true, create an empty `packed-refs` file so the reference store starts out with packed references,
+    /// which is useful if many references will be written right after, like when mirroring.
+    ///
+    /// The branch of the [initial commit](Options::initial_commit) is then written into it instead of as a loose reference.
+    pub packed_refs: bool,
+}
+
+/// What to point `HEAD` to in a new repository, see [`Options::head`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadTarget {
+    /// Point to the fully qualified reference with the given name, like `refs/heads/foo`, even though it doesn't exist yet.
+    ///
+    /// The name must be a valid reference name within `refs/`.
+    Symbolic(BString),
+    /// Detach `HEAD` at the given object, which doesn't have to exist yet.
+    ///
+    /// Note that no [initial commit](Options::initial_commit) can be created with a detached `HEAD`, as it already exists,
+    /// and asking for both is an error before anything is created.
+    Detached(gix_hash::ObjectId),
+}
+
+/// The initial commit to create in a new repository, see [`Options::initial_commit`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InitialCommit {
+    /// The message of the commit.
+    pub message: &'static str,
+}
+
+impl Default for InitialCommit {
+    fn default() -> Self {
+        InitialCommit {
+            message: "Initial commit",
+        }
+    }
 }
 
 /// Create a new `.git` repository of `kind` within the possibly non-existing `directory`
@@ -130,10 +276,19 @@ pub fn into(
     Options {
         fs_capabilities,
         destination_must_be_empty,
+        shared_repository: shared,
+        initial_commit: _,
+        head: _,
+        executable_sample_hooks,
+        separate_git_dir,
+        packed_refs,
     }: Options,
 ) -> Result<gix_discover::repository::Path, Error> {
     let mut dot_git = directory.into();
     let bare = matches!(kind, Kind::Bare);
+    if bare && separate_git_dir.is_some() {
+        return Err(Error::SeparateGitDirInBareRepository);
+    }
 
     if bare || destination_must_be_empty {
         let num_entries_in_dot_git = fs::read_dir(&dot_git)
@@ -161,15 +316,36 @@ pub fn into(
             return Err(Error::DirectoryExists { path: dot_git });
         }
     };
-    create_dir(&dot_git)?;
+    let git_file = match separate_git_dir {
+        Some(git_dir) => {
+            if dot_git.exists() {
+                return Err(Error::DirectoryExists { path: dot_git });
+            }
+            let git_dir = std::env::current_dir()?.join(git_dir);
+            match fs::read_dir(&git_dir).map(|mut entries| entries.next().is_some()) {
+                Ok(true) => return Err(Error::DirectoryNotEmpty { path: git_dir }),
+                Ok(false) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(Error::IoOpen {
+                        source: err,
+                        path: git_dir,
+                    })
+                }
+            }
+            Some(std::mem::replace(&mut dot_git, git_dir))
+        }
+        None => None,
+    };
+    create_dir(&dot_git, shared)?;
 
     {
-        let mut cursor = NewDir(&mut dot_git).at("info")?;
-        write_file(TPL_INFO_EXCLUDE, PathCursor(cursor.as_mut()).at("exclude"))?;
+        let mut cursor = NewDir(&mut dot_git, shared).at("info")?;
+        write_file(TPL_INFO_EXCLUDE, PathCursor(cursor.as_mut()).at("exclude"), shared)?;
     }
 
     {
-        let mut cursor = NewDir(&mut dot_git).at("hooks")?;
+        let mut cursor = NewDir(&mut dot_git, shared).at("hooks")?;
         for (tpl, filename) in &[
             (TPL_HOOKS_DOCS_URL, "docs.url"),
             (TPL_HOOKS_PREPARE_COMMIT_MSG, "prepare-commit-msg.sample"),
@@ -183,30 +359,41 @@ pub fn into(
             (TPL_HOOKS_COMMIT_MSG, "commit-msg.sample"),
             (TPL_HOOKS_APPLYPATCH_MSG, "applypatch-msg.sample"),
         ] {
-            write_file(tpl, PathCursor(cursor.as_mut()).at(filename))?;
+            let executable = executable_sample_hooks && filename.ends_with(".sample");
+            write_file_with_mode(tpl, PathCursor(cursor.as_mut()).at(filename), shared, executable)?;
         }
     }
 
     {
-        let mut cursor = NewDir(&mut dot_git).at("objects")?;
-        create_dir(PathCursor(cursor.as_mut()).at("info"))?;
-        create_dir(PathCursor(cursor.as_mut()).at("pack"))?;
+        let mut cursor = NewDir(&mut dot_git, shared).at("objects")?;
+        create_dir(PathCursor(cursor.as_mut()).at("info"), shared)?;
+        create_dir(PathCursor(cursor.as_mut()).at("pack"), shared)?;
     }
 
     {
-        let mut cursor = NewDir(&mut dot_git).at("refs")?;
-        create_dir(PathCursor(cursor.as_mut()).at("heads"))?;
-        create_dir(PathCursor(cursor.as_mut()).at("tags"))?;
+        let mut cursor = NewDir(&mut dot_git, shared).at("refs")?;
+        create_dir(PathCursor(cursor.as_mut()).at("heads"), shared)?;
+        create_dir(PathCursor(cursor.as_mut()).at("tags"), shared)?;
     }
 
     for (tpl, filename) in &[(TPL_HEAD, "HEAD"), (TPL_DESCRIPTION, "description")] {
-        write_file(tpl, PathCursor(&mut dot_git).at(filename))?;
+        write_file(tpl, PathCursor(&mut dot_git).at(filename), shared)?;
+    }
+    if packed_refs {
+        write_file(TPL_PACKED_REFS, PathCursor(&mut dot_git).at("packed-refs"), shared)?;
     }
 
     {
         let mut config = gix_config::File::default();
         {
-            let caps = fs_capabilities.unwrap_or_else(|| gix_fs::Capabilities::probe(&dot_git));
+            let caps = match fs_capabilities {
+                Some(caps) => caps,
+                None => {
+                    // Probing for case-insensitivity checks if `config` can be accessed by a different case, so it must exist.
+                    write_file(&[], PathCursor(&mut dot_git).at("config"), shared)?;
+                    gix_fs::Capabilities::probe(&dot_git)
+                }
+            };
             let mut core = config.new_section("core", None).expect("valid section name");
 
             core.push(key("repositoryformatversion"), Some("0".into()));
@@ -216,6 +403,9 @@ pub fn into(
             core.push(key("symlinks"), Some(bool(caps.symlink).into()));
             core.push(key("ignorecase"), Some(bool(caps.ignore_case).into()));
             core.push(key("precomposeunicode"), Some(bool(caps.precompose_unicode).into()));
+            if let Some(value) = shared.config_value() {
+                core.push(key("sharedrepository"), Some(value.as_str().into()));
+            }
         }
         let mut cursor = PathCursor(&mut dot_git);
         let config_path = cursor.at("config");
@@ -223,6 +413,23 @@ pub fn into(
             source: err,
             path: config_path.to_owned(),
         })?;
+        adjust_permissions(config_path, shared)?;
+    }
+
+    if let Some(git_file) = git_file {
+        let work_dir = git_file.parent().expect("`.git` was pushed").to_owned();
+        fs::create_dir_all(&work_dir).map_err(|err| Error::CreateDirectory {
+            source: err,
+            path: work_dir.clone(),
+        })?;
+        let mut content = BString::from("gitdir: ");
+        content.extend_from_slice(&gix_path::into_bstr(dot_git.as_path()));
+        content.push(b'\n');
+        write_file(&content, &git_file, SharedRepository::Umask)?;
+        return Ok(gix_discover::repository::Path::LinkedWorkTree {
+            work_dir,
+            git_dir: dot_git,
+        });
     }
 
     Ok(gix_discover::repository::Path::from_dot_git_dir(
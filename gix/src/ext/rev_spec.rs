@@ -14,6 +14,7 @@ impl RevSpecExt for gix_revision::Spec {
             inner: self,
             first_ref: None,
             second_ref: None,
+            prefix_interpretation: Default::default(),
             repo,
         }
     }
@@ -1,13 +1,24 @@
 #![allow(clippy::result_large_err)]
-use std::{borrow::Cow, convert::TryInto, path::Path};
+use std::{
+    borrow::Cow,
+    convert::TryInto,
+    path::{Path, PathBuf},
+};
 
+use gix_odb::Find;
 use gix_ref::{
+    file::transaction::PackedRefs,
     store::WriteReflog,
-    transaction::{PreviousValue, RefEdit},
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
     FullName, Target,
 };
 
-use crate::{bstr::BString, config::tree::Init, ThreadSafeRepository};
+use crate::{
+    bstr::{BString, ByteSlice},
+    config::tree::Init,
+    create::HeadTarget,
+    ThreadSafeRepository,
+};
 
 /// The name of the branch to use if non is configured via git configuration.
 ///
@@ -31,8 +42,23 @@ pub enum Error {
         name: BString,
         source: gix_validate::reference::name::Error,
     },
+    #[error("HEAD can only point to references within refs/, not to {name:?}")]
+    HeadTargetOutsideOfRefs { name: BString },
+    #[error("Invalid HEAD target: {name:?}")]
+    InvalidHeadTarget {
+        name: BString,
+        source: gix_validate::reference::name::Error,
+    },
     #[error("Could not edit HEAD reference with new default name")]
     EditHeadForDefaultBranch(#[from] crate::reference::edit::Error),
+    #[error("Could not restore the permissions of '{}' after changing the default branch", .path.display())]
+    RestoreHeadPermissions { source: std::io::Error, path: PathBuf },
+    #[error("Cannot create an initial commit if HEAD is detached")]
+    InitialCommitWithDetachedHead,
+    #[error("Could not create the initial commit")]
+    InitialCommit(#[source] crate::commit::Error),
+    #[error("Could not move the branch of the initial commit into the packed-refs file")]
+    PackInitialBranch(#[source] crate::reference::edit::Error),
 }
 
 impl ThreadSafeRepository {
@@ -50,52 +76,154 @@ impl ThreadSafeRepository {
         Self::init_opts(directory, kind, options, open_options)
     }
 
+    /// Create a bare repository within `directory`, creating intermediate directories as needed, which is typical for
+    /// repositories on servers.
+    ///
+    /// It's like [`init`][Self::init()] with [`Kind::Bare`](crate::create::Kind::Bare), so the default branch
+    /// is still configurable with `init.defaultBranch`.
+    pub fn init_bare(directory: impl AsRef<Path>, options: crate::create::Options) -> Result<Self, Error> {
+        use gix_sec::trust::DefaultForLevel;
+        let open_options = crate::open::Options::default_for_level(gix_sec::Trust::Full);
+        Self::init_opts(directory, crate::create::Kind::Bare, options, open_options)
+    }
+
     /// Similar to [`init`][Self::init()], but allows to determine how exactly to open the newly created repository.
     ///
     /// # Deviation
     ///
     /// Instead of naming the default branch `master`, we name it `main` unless configured explicitly using the `init.defaultBranch`
-    /// configuration key.
+    /// configuration key, or unless [`HEAD` is set explicitly](crate::create::Options::head).
     pub fn init_opts(
         directory: impl AsRef<Path>,
         kind: crate::create::Kind,
         create_options: crate::create::Options,
         mut open_options: crate::open::Options,
     ) -> Result<Self, Error> {
+        let head = create_options.head.clone();
+        let initial_commit = create_options.initial_commit;
+        if initial_commit.is_some() && matches!(head, Some(HeadTarget::Detached(_))) {
+            return Err(Error::InitialCommitWithDetachedHead);
+        }
+        let packed_refs = create_options.packed_refs;
         let path = crate::create::into(directory.as_ref(), kind, create_options)?;
         let (git_dir, worktree_dir) = path.into_repository_and_work_tree_directories();
         open_options.git_dir_trust = Some(gix_sec::Trust::Full);
         open_options.current_dir = std::env::current_dir()?.into();
         let repo = ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, open_options)?;
 
-        let branch_name = repo
-            .config
-            .resolved
-            .string("init", None, Init::DEFAULT_BRANCH.name)
-            .unwrap_or_else(|| Cow::Borrowed(DEFAULT_BRANCH_NAME.into()));
-        if branch_name.as_ref() != DEFAULT_BRANCH_NAME {
-            let sym_ref: FullName =
-                format!("refs/heads/{branch_name}")
+        let new_head = match head {
+            Some(HeadTarget::Detached(id)) => Some(Target::Peeled(id)),
+            Some(HeadTarget::Symbolic(name)) => {
+                if !name.starts_with(b"refs/") {
+                    return Err(Error::HeadTargetOutsideOfRefs { name });
+                }
+                let sym_ref: FullName = name
+                    .as_bstr()
                     .try_into()
-                    .map_err(|err| Error::InvalidBranchName {
-                        name: branch_name.into_owned(),
-                        source: err,
-                    })?;
+                    .map_err(|err| Error::InvalidHeadTarget { name, source: err })?;
+                Some(Target::Symbolic(sym_ref))
+            }
+            None => {
+                let branch_name = repo
+                    .config
+                    .resolved
+                    .string("init", None, Init::DEFAULT_BRANCH.name)
+                    .unwrap_or_else(|| Cow::Borrowed(DEFAULT_BRANCH_NAME.into()));
+                if branch_name.as_ref() != DEFAULT_BRANCH_NAME {
+                    let sym_ref: FullName =
+                        format!("refs/heads/{branch_name}")
+                            .try_into()
+                            .map_err(|err| Error::InvalidBranchName {
+                                name: branch_name.into_owned(),
+                                source: err,
+                            })?;
+                    Some(Target::Symbolic(sym_ref))
+                } else {
+                    None
+                }
+            }
+        };
+        let head_branch = match &new_head {
+            Some(Target::Symbolic(name)) => Some(name.clone()),
+            Some(Target::Peeled(_)) => None,
+            None => Some(format!("refs/heads/{DEFAULT_BRANCH_NAME}").try_into().expect("valid")),
+        };
+        if let Some(new_head) = new_head {
             let mut repo = repo.to_thread_local();
+            // Lock files are created with restrictive permissions, so keep the ones `HEAD` was created with.
+            let head_path = repo.git_dir().join("HEAD");
+            let head_permissions = std::fs::metadata(&head_path).map(|m| m.permissions()).ok();
             let prev_write_reflog = repo.refs.write_reflog;
             repo.refs.write_reflog = WriteReflog::Disable;
             repo.edit_reference(RefEdit {
                 change: gix_ref::transaction::Change::Update {
                     log: Default::default(),
                     expected: PreviousValue::Any,
-                    new: Target::Symbolic(sym_ref),
+                    new: new_head,
                 },
                 name: "HEAD".try_into().expect("valid"),
                 deref: false,
             })?;
             repo.refs.write_reflog = prev_write_reflog;
+            if let Some(permissions) = head_permissions {
+                std::fs::set_permissions(&head_path, permissions).map_err(|err| Error::RestoreHeadPermissions {
+                    source: err,
+                    path: head_path,
+                })?;
+            }
+        }
+
+        if let Some(initial_commit) = initial_commit {
+            let repo = repo.to_thread_local();
+            let tree = repo
+                .write_object(gix_object::Tree::empty())
+                .map_err(|err| Error::InitialCommit(err.into()))?;
+            let id = repo
+                .commit("HEAD", initial_commit.message, tree, crate::commit::NO_PARENT_IDS)
+                .map_err(Error::InitialCommit)?;
+            if let Some(branch) = head_branch.filter(|_| packed_refs) {
+                pack_branch(&repo, branch, id.detach()).map_err(Error::PackInitialBranch)?;
+            }
         }
 
         Ok(repo)
     }
 }
+
+/// Move the loose reference `name` pointing to `id` into the packed-refs file, without touching its reflog.
+fn pack_branch(
+    repo: &crate::Repository,
+    name: FullName,
+    id: gix_hash::ObjectId,
+) -> Result<(), crate::reference::edit::Error> {
+    let (file_lock_fail, packed_refs_lock_fail) = repo.config.lock_timeout()?;
+    repo.refs
+        .transaction()
+        .packed_refs(PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(
+            Box::new(|oid, buf| {
+                repo.objects
+                    .try_find(oid, buf)
+                    .map(|obj| obj.map(|obj| obj.kind))
+                    .map_err(|err| Box::new(err) as Box<dyn std::error::Error + Send + Sync + 'static>)
+            }),
+        ))
+        .prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: Default::default(),
+                    },
+                    expected: PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+                    new: Target::Peeled(id),
+                },
+                name,
+                deref: false,
+            }),
+            file_lock_fail,
+            packed_refs_lock_fail,
+        )?
+        .commit(None)?;
+    Ok(())
+}
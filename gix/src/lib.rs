@@ -132,8 +132,8 @@ pub(crate) type Config = OwnShared<gix_config::File<'static>>;
 
 mod types;
 pub use types::{
-    Commit, Head, Id, Object, ObjectDetached, Pathspec, Reference, Remote, Repository, Tag, ThreadSafeRepository, Tree,
-    Worktree,
+    Commit, Head, Id, Object, ObjectDetached, Pathspec, Reference, Remote, Repository, Submodule, Tag,
+    ThreadSafeRepository, Tree, Worktree,
 };
 
 ///
@@ -174,10 +174,10 @@ pub fn init(directory: impl AsRef<std::path::Path>) -> Result<Repository, init::
     ThreadSafeRepository::init(directory, create::Kind::WithWorktree, create::Options::default()).map(Into::into)
 }
 
-/// See [`ThreadSafeRepository::init()`], but returns a [`Repository`] instead.
+/// See [`ThreadSafeRepository::init_bare()`], but returns a [`Repository`] instead.
 #[allow(clippy::result_large_err)]
 pub fn init_bare(directory: impl AsRef<std::path::Path>) -> Result<Repository, init::Error> {
-    ThreadSafeRepository::init(directory, create::Kind::Bare, create::Options::default()).map(Into::into)
+    ThreadSafeRepository::init_bare(directory, create::Options::default()).map(Into::into)
 }
 
 /// Create a platform for configuring a bare clone from `url` to the local `path`, using default options for opening it (but
@@ -252,6 +252,9 @@ pub mod config;
 ///
 pub mod mailmap;
 
+///
+pub mod submodule;
+
 ///
 pub mod worktree;
 
@@ -34,6 +34,21 @@ pub mod peel {
     }
 }
 
+///
+pub mod reflog {
+    /// The error returned by [`Repository::reflog(…)`][crate::Repository::reflog()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindReference(#[from] crate::reference::find::existing::Error),
+        #[error("Could not read the reference log")]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Decode(#[from] gix_ref::file::log::iter::reverse::Error),
+    }
+}
+
 ///
 pub mod head_id {
     /// The error returned by [`Repository::head_id(…)`][crate::Repository::head_id()].
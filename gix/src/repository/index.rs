@@ -23,6 +23,7 @@ impl crate::Repository {
                 thread_limit,
                 min_extension_block_in_bytes_for_threading: 0,
                 expected_checksum: None,
+                track_source_offsets: false,
             },
         )
         .map_err(Into::into)
@@ -53,6 +53,7 @@ mod remote;
 mod revision;
 mod shallow;
 mod state;
+mod submodule;
 mod thread_safe;
 mod worktree;
 
@@ -66,6 +67,21 @@ pub enum IndexPersistedOrInMemory {
     InMemory(gix_index::File),
 }
 
+///
+pub mod submodules_from_tree {
+    /// The error returned by [`Repository::submodules_from_tree()`][crate::Repository::submodules_from_tree()].
+    #[derive(thiserror::Error, Debug)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        PeelToTree(#[from] crate::object::peel::to_kind::Error),
+        #[error("The .gitmodules file could not be parsed")]
+        Parse(#[from] gix_config::parse::Error),
+    }
+}
+
 ///
 pub mod index_or_load_from_head {
     /// The error returned by [`Repository::index_or_load_from_head()`][crate::Repository::index_or_load_from_head()].
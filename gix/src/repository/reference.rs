@@ -240,4 +240,25 @@ impl crate::Repository {
             Err(err) => Err(err.into()),
         }
     }
+
+    /// Return all entries of the reference log of the reference with the given partial or full `name`, like `HEAD` or `main`,
+    /// from the most recent to the oldest, so that the `n`th entry is the one `name@{n}` refers to.
+    ///
+    /// Return `None` if the reference exists but doesn't have a reference log.
+    pub fn reflog<'a, Name, E>(
+        &self,
+        name: Name,
+    ) -> Result<Option<impl Iterator<Item = gix_ref::log::Line>>, reference::reflog::Error>
+    where
+        Name: TryInto<&'a PartialNameRef, Error = E>,
+        gix_ref::file::find::Error: From<E>,
+    {
+        let r = self.find_reference(name)?;
+        let mut platform = r.log_iter();
+        let lines = match platform.rev()? {
+            Some(lines) => lines.collect::<Result<Vec<_>, _>>()?,
+            None => return Ok(None),
+        };
+        Ok(Some(lines.into_iter()))
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use crate::{bstr::BStr, revision, Id};
 
 /// Methods for resolving revisions by spec or working with the commit graph.
@@ -9,25 +11,45 @@ impl crate::Repository {
     /// - `@` actually stands for `HEAD`, whereas `git` resolves it to the object pointed to by `HEAD` without making the
     ///   `HEAD` ref available for lookups.
     pub fn rev_parse<'a>(&self, spec: impl Into<&'a BStr>) -> Result<revision::Spec<'_>, revision::spec::parse::Error> {
-        revision::Spec::from_bstr(
-            spec,
-            self,
-            revision::spec::parse::Options {
-                object_kind_hint: self.config.object_kind_hint,
-                ..Default::default()
-            },
-        )
+        revision::Spec::from_bstr(spec, self, revision::spec::parse::Options::from_repo(self))
     }
 
     /// Parse a revision specification and return single object id as represented by this instance.
+    ///
+    /// The error tells apart specs that are ranges, those naming an unborn branch,
+    /// and those that couldn't be resolved at all.
     pub fn rev_parse_single<'repo, 'a>(
         &'repo self,
         spec: impl Into<&'a BStr>,
     ) -> Result<Id<'repo>, revision::spec::parse::single::Error> {
+        use revision::spec::parse::{single, Error};
         let spec = spec.into();
-        self.rev_parse(spec)?
+        self.rev_parse(spec)
+            .map_err(|err| match err {
+                Error::UnbornBranch { branch, .. } => single::Error::UnbornBranch {
+                    spec: spec.into(),
+                    branch,
+                },
+                err => single::Error::Parse(err),
+            })?
             .single()
-            .ok_or(revision::spec::parse::single::Error::RangedRev { spec: spec.into() })
+            .map_err(|_| single::Error::RangedRev { spec: spec.into() })
+    }
+
+    /// Return all objects whose id starts with `prefix` along with information about them, sorted by kind and id,
+    /// similar to the candidates of an [ambiguous prefix](revision::spec::parse::Error::AmbiguousPrefix) error.
+    ///
+    /// This is useful to let users pick one of the candidates if [`rev_parse()`](Self::rev_parse()) found `prefix` to be ambiguous.
+    pub fn candidates_by_prefix(
+        &self,
+        prefix: gix_hash::Prefix,
+    ) -> Result<
+        Vec<(gix_hash::ObjectId, revision::spec::parse::error::CandidateInfo)>,
+        gix_odb::store::prefix::lookup::Error,
+    > {
+        let mut candidates = HashSet::default();
+        self.objects.lookup_prefix(prefix, Some(&mut candidates))?;
+        Ok(revision::spec::parse::error::candidates_info(candidates, self))
     }
 
     /// Create the baseline for a revision walk by initializing it with the `tips` to start iterating on.
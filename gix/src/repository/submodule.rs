@@ -0,0 +1,92 @@
+use gix_features::threading::OwnShared;
+
+use crate::{bstr::BString, repository::submodules_from_tree, submodule, Submodule};
+
+impl crate::Repository {
+    /// Read the `.gitmodules` file from the tree (or the tree of the commit or tag) with `tree_id` through the object database,
+    /// which is useful in bare repositories that have no worktree to read it from.
+    ///
+    /// Return `None` if there is no `.gitmodules` file at the root of the tree, or if it isn't a regular file.
+    /// Like in `git`, a `.gitmodules` symlink is never followed.
+    pub fn submodules_from_tree(
+        &self,
+        tree_id: impl Into<gix_hash::ObjectId>,
+    ) -> Result<Option<gix_submodule::File>, submodules_from_tree::Error> {
+        let tree = self.find_object(tree_id)?.peel_to_tree()?;
+        let blob_id = match tree.find_entry(".gitmodules") {
+            Some(entry) if entry.mode().is_blob() => entry.object_id(),
+            _ => return Ok(None),
+        };
+        let blob = self.find_object(blob_id)?;
+        Ok(Some(gix_submodule::File::from_bytes(&blob.data, None)?))
+    }
+
+    /// Load the `.gitmodules` file and merge the submodule configuration of this repository into it, or return `None`
+    /// if there is no `.gitmodules` file.
+    ///
+    /// Like `git`, the file is read from the worktree, or from the index if it's not present there,
+    /// or from the tree of `HEAD` if there is no index either, as is typical for bare repositories.
+    pub fn modules(&self) -> Result<Option<gix_submodule::File>, submodule::modules::Error> {
+        let mut modules = match self.work_dir() {
+            Some(root) => {
+                let path = root.join(".gitmodules");
+                match std::fs::read(&path) {
+                    Ok(buf) => Some(gix_submodule::File::from_bytes(&buf, path)?),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => self.modules_from_index_or_head()?,
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            None => self.modules_from_index_or_head()?,
+        };
+        if let Some(modules) = modules.as_mut() {
+            modules.append_submodule_overrides(&self.config.resolved);
+        }
+        Ok(modules)
+    }
+
+    /// Return an iterator over all submodules configured in the `.gitmodules` file as obtained by [`modules()`](Self::modules()),
+    /// or `None` if there is no such file or if it doesn't configure any submodule.
+    ///
+    /// Submodules which are only mentioned in the repository configuration aren't returned, and neither are those with names
+    /// that aren't safe to use as directory in `.git/modules/`, just like in `git`.
+    pub fn submodules(&self) -> Result<Option<impl Iterator<Item = Submodule<'_>>>, submodule::modules::Error> {
+        let modules = match self.modules()? {
+            Some(modules) => OwnShared::new(modules),
+            None => return Ok(None),
+        };
+        let orphaned = modules.orphaned_overrides(&self.config.resolved);
+        let mut names = Vec::<BString>::new();
+        for name in modules.names() {
+            if !names.iter().any(|n| n == name) && !orphaned.iter().any(|n| n == name) {
+                names.push(name.to_owned());
+            }
+        }
+        if names.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(names.into_iter().map(move |name| Submodule {
+            modules: modules.clone(),
+            name,
+            repo: self,
+        })))
+    }
+
+    fn modules_from_index_or_head(&self) -> Result<Option<gix_submodule::File>, submodule::modules::Error> {
+        match self.index() {
+            Ok(index) => {
+                if let Some(entry) = index.entry_by_path_and_stage(".gitmodules".into(), 0) {
+                    let blob = self.find_object(entry.id)?;
+                    return Ok(Some(gix_submodule::File::from_bytes(&blob.data, None)?));
+                }
+                return Ok(None);
+            }
+            Err(crate::worktree::open_index::Error::IndexFile(gix_index::file::init::Error::Io(err)))
+                if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        match self.head()?.into_fully_peeled_id().transpose()? {
+            Some(id) => Ok(self.submodules_from_tree(id)?),
+            None => Ok(None),
+        }
+    }
+}
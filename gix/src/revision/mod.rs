@@ -23,5 +23,7 @@ pub struct Spec<'repo> {
     pub(crate) first_ref: Option<gix_ref::Reference>,
     /// The second name of a reference as seen while parsing a `RevSpec`, for completeness.
     pub(crate) second_ref: Option<gix_ref::Reference>,
+    /// How the first and second ambiguous name were interpreted, if they were valid object prefixes.
+    pub(crate) prefix_interpretation: [Option<spec::parse::PrefixInterpretation>; 2],
     pub(crate) repo: &'repo crate::Repository,
 }
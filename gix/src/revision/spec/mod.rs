@@ -3,6 +3,20 @@ use crate::{ext::ReferenceExt, revision::Spec, Id, Reference};
 ///
 pub mod parse;
 
+///
+pub mod single {
+    /// The error returned by [`Spec::single()`](crate::revision::Spec::single()).
+    ///
+    /// Specs naming unborn branches or objects that don't exist can't be parsed in the first place, see
+    /// [`Repository::rev_parse_single()`](crate::Repository::rev_parse_single()) for an error telling all of these apart.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The rev-spec is of kind {kind:?} and doesn't resolve to a single object")]
+        Range { kind: gix_revision::spec::Kind },
+    }
+}
+
 mod impls {
     use std::ops::{Deref, DerefMut};
 
@@ -40,6 +54,7 @@ impl<'repo> Spec<'repo> {
             repo: id.repo,
             first_ref: None,
             second_ref: None,
+            prefix_interpretation: Default::default(),
         }
     }
 }
@@ -75,16 +90,82 @@ impl<'repo> Spec<'repo> {
         self.second_ref.as_ref()
     }
 
-    /// Return the single included object represented by this instance, or `None` if it is a range of any kind.
-    pub fn single(&self) -> Option<Id<'repo>> {
+    /// Return how the first name in the rev-spec was interpreted if it was both a prefix of existing objects and a possible
+    /// reference name, or `None` if it wasn't a prefix of existing objects.
+    /// For example, with a branch named `cafe` and objects starting with `cafe`, `cafe` is interpreted as
+    /// [reference](crate::revision::spec::parse::PrefixInterpretation::Reference) by default.
+    ///
+    /// This is useful to let users know which of the possible interpretations was used.
+    pub fn first_prefix_interpretation(&self) -> Option<crate::revision::spec::parse::PrefixInterpretation> {
+        self.prefix_interpretation[0]
+    }
+
+    /// Return how the second name in a range was interpreted, like [`first_prefix_interpretation()`](Self::first_prefix_interpretation()),
+    /// or `None` if it wasn't a prefix of existing objects or if there was no second name.
+    pub fn second_prefix_interpretation(&self) -> Option<crate::revision::spec::parse::PrefixInterpretation> {
+        self.prefix_interpretation[1]
+    }
+
+    /// Return the object the first part of the rev-spec resolved to along with the reference that led to it, if any.
+    /// For example, `main..abcd` yields `(Some(refs/heads/main), <id of main>)`, whereas `abcd` yields `(None, abcd)`.
+    ///
+    /// This is useful to show where an object came from, like `HEAD (main) -> abcd`.
+    pub fn first_endpoint(&self) -> (Option<&gix_ref::Reference>, Id<'repo>) {
+        let id = match self.inner {
+            gix_revision::Spec::Include(id)
+            | gix_revision::Spec::Exclude(id)
+            | gix_revision::Spec::ExcludeParents(id)
+            | gix_revision::Spec::IncludeOnlyParents(id)
+            | gix_revision::Spec::Range { from: id, .. }
+            | gix_revision::Spec::Merge { theirs: id, .. } => id,
+        };
+        (self.first_reference(), Id::from_id(id, self.repo))
+    }
+
+    /// Return the object the second part of a range resolved to along with the reference that led to it, if any,
+    /// or `None` if this isn't a range. For example, `abcd..main` yields `Some((Some(refs/heads/main), <id of main>))`.
+    pub fn second_endpoint(&self) -> Option<(Option<&gix_ref::Reference>, Id<'repo>)> {
         match self.inner {
-            gix_revision::Spec::Include(id) | gix_revision::Spec::ExcludeParents(id) => {
-                Id::from_id(id, self.repo).into()
+            gix_revision::Spec::Range { to: id, .. } | gix_revision::Spec::Merge { ours: id, .. } => {
+                Some((self.second_reference(), Id::from_id(id, self.repo)))
             }
+            gix_revision::Spec::Include(_)
+            | gix_revision::Spec::Exclude(_)
+            | gix_revision::Spec::ExcludeParents(_)
+            | gix_revision::Spec::IncludeOnlyParents(_) => None,
+        }
+    }
+
+    /// Return the single included object represented by this instance, or an error with the kind of this
+    /// specification if it is a range of any kind.
+    pub fn single(&self) -> Result<Id<'repo>, single::Error> {
+        match self.inner {
+            gix_revision::Spec::Include(id) | gix_revision::Spec::ExcludeParents(id) => Ok(Id::from_id(id, self.repo)),
             gix_revision::Spec::Exclude(_)
             | gix_revision::Spec::Range { .. }
             | gix_revision::Spec::Merge { .. }
-            | gix_revision::Spec::IncludeOnlyParents { .. } => None,
+            | gix_revision::Spec::IncludeOnlyParents { .. } => Err(single::Error::Range {
+                kind: self.inner.kind(),
+            }),
         }
     }
+
+    /// If this is an `a^!` specification, return the commit `a` to include along with all of its parents to exclude,
+    /// which makes it the equivalent of `a ^a^1 ^a^2` and so forth. Return `None` for any other kind of specification.
+    ///
+    /// This is useful to turn `a^!` into the tips of a traversal, like `git log a^!` does to show only `a` itself.
+    pub fn commit_and_excluded_parents(
+        &self,
+    ) -> Result<Option<(Id<'repo>, Vec<Id<'repo>>)>, crate::object::peel::to_kind::Error> {
+        let id = match self.inner {
+            gix_revision::Spec::ExcludeParents(id) => id,
+            _ => return Ok(None),
+        };
+        let commit = Id::from_id(id, self.repo)
+            .object()?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit();
+        let parents = commit.parent_ids().collect();
+        Ok(Some((commit.id(), parents)))
+    }
 }
@@ -1,11 +1,15 @@
-use std::collections::HashSet;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
 
 use gix_hash::ObjectId;
 use gix_revision::spec::{parse, parse::delegate};
 use smallvec::SmallVec;
 
-use super::{Delegate, Error, ObjectKindHint};
+use super::{Delegate, Error, ObjectKindHint, RefsHint};
 use crate::{
+    bstr::{BStr, ByteSlice},
     ext::{ObjectIdExt, ReferenceExt},
     Repository,
 };
@@ -22,7 +26,10 @@ impl<'repo> Delegate<'repo> {
             kind: None,
             err: Vec::new(),
             prefix: Default::default(),
+            prefix_interpretation: Default::default(),
             last_call_was_disambiguate_prefix: Default::default(),
+            peeled: Default::default(),
+            kinds: Default::default(),
             opts,
             repo,
         }
@@ -30,16 +37,37 @@ impl<'repo> Delegate<'repo> {
 
     pub fn into_err(mut self) -> Error {
         let repo = self.repo;
+        let hex_len = self.opts.candidate_hex_len;
         for err in self
             .ambiguous_objects
             .iter_mut()
             .zip(self.prefix)
             .filter_map(|(a, b)| a.take().filter(|candidates| candidates.len() > 1).zip(b))
-            .map(|(candidates, prefix)| Error::ambiguous(candidates, prefix, repo))
+            .map(|(candidates, prefix)| Error::ambiguous(candidates, prefix, hex_len, repo))
             .rev()
         {
             self.err.insert(0, err);
         }
+        // Suggestions need all references, which must not be read if only objects are allowed.
+        if self.opts.refs_hint != RefsHint::ObjectsOnly {
+            for err in &mut self.err {
+                match err {
+                    Error::PrefixNotFound { prefix, suggestions } => {
+                        *suggestions = super::error::similar_reference_names(prefix.to_string().as_str().into(), repo);
+                    }
+                    Error::RefNotFound { name, suggestions } => {
+                        *suggestions = super::error::similar_reference_names(name.as_ref(), repo);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if self.err.is_empty() {
+            // Navigation on a reference without object stops silently, like `HEAD^{commit}` on an unborn branch.
+            if let Some(r) = self.refs[self.idx].as_ref().filter(|_| self.objs[self.idx].is_none()) {
+                self.err.push(unborn_branch(r));
+            }
+        }
         Error::from_errors(self.err)
     }
 
@@ -48,6 +76,7 @@ impl<'repo> Delegate<'repo> {
             mut candidates: [Option<HashSet<ObjectId>>; 2],
             prefix: [Option<gix_hash::Prefix>; 2],
             mut errors: Vec<Error>,
+            hex_len: Option<usize>,
             repo: &Repository,
         ) -> Result<[Option<ObjectId>; 2], Error> {
             let mut out = [None, None];
@@ -66,7 +95,12 @@ impl<'repo> Delegate<'repo> {
                             _ => {
                                 errors.insert(
                                     0,
-                                    Error::ambiguous(candidates, prefix.expect("set when obtaining candidates"), repo),
+                                    Error::ambiguous(
+                                        candidates,
+                                        prefix.expect("set when obtaining candidates"),
+                                        hex_len,
+                                        repo,
+                                    ),
                                 );
                                 return Err(Error::from_errors(errors));
                             }
@@ -80,29 +114,42 @@ impl<'repo> Delegate<'repo> {
         fn kind_to_spec(
             kind: Option<gix_revision::spec::Kind>,
             [first, second]: [Option<ObjectId>; 2],
+            refs: &[Option<gix_ref::Reference>; 2],
         ) -> Result<gix_revision::Spec, Error> {
             use gix_revision::spec::Kind::*;
+            // A reference without object points to an unborn branch, otherwise there was nothing at all.
+            let missing = |idx: usize| refs[idx].as_ref().map_or(Error::Malformed, unborn_branch);
+            let first = || first.ok_or_else(|| missing(0));
+            let second = || second.ok_or_else(|| missing(1));
             Ok(match kind.unwrap_or_default() {
-                IncludeReachable => gix_revision::Spec::Include(first.ok_or(Error::Malformed)?),
-                ExcludeReachable => gix_revision::Spec::Exclude(first.ok_or(Error::Malformed)?),
+                IncludeReachable => gix_revision::Spec::Include(first()?),
+                ExcludeReachable => gix_revision::Spec::Exclude(first()?),
                 RangeBetween => gix_revision::Spec::Range {
-                    from: first.ok_or(Error::Malformed)?,
-                    to: second.ok_or(Error::Malformed)?,
+                    from: first()?,
+                    to: second()?,
                 },
                 ReachableToMergeBase => gix_revision::Spec::Merge {
-                    theirs: first.ok_or(Error::Malformed)?,
-                    ours: second.ok_or(Error::Malformed)?,
+                    theirs: first()?,
+                    ours: second()?,
                 },
-                IncludeReachableFromParents => gix_revision::Spec::IncludeOnlyParents(first.ok_or(Error::Malformed)?),
-                ExcludeReachableFromParents => gix_revision::Spec::ExcludeParents(first.ok_or(Error::Malformed)?),
+                IncludeReachableFromParents => gix_revision::Spec::IncludeOnlyParents(first()?),
+                ExcludeReachableFromParents => gix_revision::Spec::ExcludeParents(first()?),
             })
         }
 
-        let range = zero_or_one_objects_or_ambiguity_err(self.objs, self.prefix, self.err, self.repo)?;
+        let range = zero_or_one_objects_or_ambiguity_err(
+            self.objs,
+            self.prefix,
+            self.err,
+            self.opts.candidate_hex_len,
+            self.repo,
+        )?;
+        let inner = kind_to_spec(self.kind, range, &self.refs)?;
         Ok(crate::revision::Spec {
             first_ref: self.refs[0].take(),
             second_ref: self.refs[1].take(),
-            inner: kind_to_spec(self.kind, range)?,
+            prefix_interpretation: self.prefix_interpretation,
+            inner,
             repo: self.repo,
         })
     }
@@ -140,15 +187,20 @@ impl<'repo> Delegate<'repo> {
         self.kind.unwrap_or(gix_revision::spec::Kind::IncludeReachable) != gix_revision::spec::Kind::IncludeReachable
     }
     fn disambiguate_objects_by_fallback_hint(&mut self, hint: Option<ObjectKindHint>) {
-        fn require_object_kind(repo: &Repository, obj: &gix_hash::oid, kind: gix_object::Kind) -> Result<(), Error> {
-            let obj = repo.find_object(obj)?;
-            if obj.kind == kind {
+        fn require_object_kind(
+            repo: &Repository,
+            kinds: &mut HashMap<ObjectId, gix_object::Kind>,
+            obj: &gix_hash::oid,
+            kind: gix_object::Kind,
+        ) -> Result<(), Error> {
+            let actual = object_kind(repo, kinds, obj)?;
+            if actual == kind {
                 Ok(())
             } else {
                 Err(Error::ObjectKind {
-                    actual: obj.kind,
+                    actual,
                     expected: kind,
-                    oid: obj.id.attach(repo).shorten_or_id(),
+                    oid: obj.to_owned().attach(repo).shorten_or_id(),
                 })
             }
         }
@@ -158,6 +210,8 @@ impl<'repo> Delegate<'repo> {
 
             if let Some(objs) = self.objs[self.idx].as_mut() {
                 let repo = self.repo;
+                let peeled = &mut self.peeled;
+                let kinds = &mut self.kinds;
                 let errors: Vec<_> = match hint {
                     Some(kind_hint) => match kind_hint {
                         ObjectKindHint::Treeish | ObjectKindHint::Committish => {
@@ -167,7 +221,7 @@ impl<'repo> Delegate<'repo> {
                                 _ => unreachable!("BUG: we narrow possibilities above"),
                             };
                             objs.iter()
-                                .filter_map(|obj| peel(repo, obj, kind).err().map(|err| (*obj, err)))
+                                .filter_map(|obj| peel(repo, peeled, obj, kind).err().map(|err| (*obj, err)))
                                 .collect()
                         }
                         ObjectKindHint::Tree | ObjectKindHint::Commit | ObjectKindHint::Blob => {
@@ -178,11 +232,13 @@ impl<'repo> Delegate<'repo> {
                                 _ => unreachable!("BUG: we narrow possibilities above"),
                             };
                             objs.iter()
-                                .filter_map(|obj| require_object_kind(repo, obj, kind).err().map(|err| (*obj, err)))
+                                .filter_map(|obj| {
+                                    require_object_kind(repo, kinds, obj, kind).err().map(|err| (*obj, err))
+                                })
                                 .collect()
                         }
                     },
-                    None => return,
+                    None => Vec::new(),
                 };
 
                 if errors.len() == objs.len() {
@@ -192,6 +248,9 @@ impl<'repo> Delegate<'repo> {
                         objs.remove(&obj);
                         self.err.push(err);
                     }
+                    if self.opts.prefer_reachable_from_head {
+                        retain_only_commit_reachable_from_head(repo, kinds, objs);
+                    }
                 }
             }
         }
@@ -218,13 +277,142 @@ impl<'repo> Delegate<'repo> {
     fn unset_disambiguate_call(&mut self) {
         self.last_call_was_disambiguate_prefix[self.idx] = false;
     }
+
+    /// Return `true` and record an error if references must not be looked up, which would be needed to find `name`.
+    fn ref_lookup_disabled(&mut self, name: &str) -> bool {
+        let disabled = self.opts.refs_hint == RefsHint::ObjectsOnly;
+        if disabled {
+            self.err.push(Error::RefLookupDisabled { name: name.into() });
+        }
+        disabled
+    }
+
+    /// Like git, resolve `path` relative to the configured prefix if it starts with `./` or `../`, or return it unchanged.
+    fn resolve_relative_path<'a>(&self, path: &'a BStr) -> Result<Cow<'a, BStr>, Error> {
+        if !(path.starts_with(b"./") || path.starts_with(b"../")) {
+            return Ok(Cow::Borrowed(path));
+        }
+        let prefix = self
+            .opts
+            .prefix
+            .as_ref()
+            .ok_or_else(|| Error::RelativePathWithoutPrefix { path: path.into() })?;
+        let mut components: Vec<&[u8]> = prefix.split_str("/").filter(|c| !c.is_empty()).collect();
+        for component in path.split_str("/") {
+            match component {
+                b"" | b"." => {}
+                b".." => {
+                    if components.pop().is_none() {
+                        return Err(Error::RelativePathOutsideOfWorktree { path: path.into() });
+                    }
+                }
+                component => components.push(component),
+            }
+        }
+        Ok(Cow::Owned(components.join(&b'/').into()))
+    }
+}
+
+/// A cache for the results of peeling objects, keyed by the object and the kind it was peeled to,
+/// or `None` if tags were peeled until reaching a non-tag object.
+#[derive(Default)]
+pub(crate) struct Peeled {
+    ids: HashMap<(ObjectId, Option<gix_object::Kind>), ObjectId>,
+    /// The amount of times we had to read objects to peel them.
+    #[cfg(test)]
+    misses: usize,
+}
+
+impl Peeled {
+    fn get_or_peel(
+        &mut self,
+        obj: &gix_hash::oid,
+        kind: Option<gix_object::Kind>,
+        peel: impl FnOnce() -> Result<ObjectId, Error>,
+    ) -> Result<ObjectId, Error> {
+        let key = (obj.to_owned(), kind);
+        if let Some(id) = self.ids.get(&key) {
+            return Ok(*id);
+        }
+        #[cfg(test)]
+        {
+            self.misses += 1;
+        }
+        let id = peel()?;
+        self.ids.insert(key, id);
+        Ok(id)
+    }
+}
+
+/// Return the kind of `obj`, reading it only if it wasn't classified before and remembering it in `kinds`.
+fn object_kind(
+    repo: &Repository,
+    kinds: &mut HashMap<ObjectId, gix_object::Kind>,
+    obj: &gix_hash::oid,
+) -> Result<gix_object::Kind, Error> {
+    if let Some(kind) = kinds.get(obj) {
+        return Ok(*kind);
+    }
+    let kind = repo.find_object(obj)?.kind;
+    kinds.insert(obj.to_owned(), kind);
+    Ok(kind)
+}
+
+fn peel(
+    repo: &Repository,
+    peeled: &mut Peeled,
+    obj: &gix_hash::oid,
+    kind: gix_object::Kind,
+) -> Result<ObjectId, Error> {
+    peeled.get_or_peel(obj, Some(kind), || {
+        let mut obj = repo.find_object(obj)?;
+        if kind == gix_object::Kind::Tag && obj.kind != kind {
+            // Nothing peels to a tag, so like `git`, require the object to be an annotated tag already.
+            return Err(Error::ObjectKind {
+                oid: obj.id().shorten_or_id(),
+                actual: obj.kind,
+                expected: kind,
+            });
+        }
+        obj = obj.peel_to_kind(kind)?;
+        debug_assert_eq!(obj.kind, kind, "bug in Object::peel_to_kind() which didn't deliver");
+        Ok(obj.id)
+    })
 }
 
-fn peel(repo: &Repository, obj: &gix_hash::oid, kind: gix_object::Kind) -> Result<ObjectId, Error> {
-    let mut obj = repo.find_object(obj)?;
-    obj = obj.peel_to_kind(kind)?;
-    debug_assert_eq!(obj.kind, kind, "bug in Object::peel_to_kind() which didn't deliver");
-    Ok(obj.id)
+/// If more than one of `objs` is a commit, but only one of these is reachable from `HEAD`, remove all other commits.
+///
+/// Candidates that aren't commits are left untouched, and nothing is done if `HEAD` can't be traversed.
+fn retain_only_commit_reachable_from_head(
+    repo: &Repository,
+    kinds: &mut HashMap<ObjectId, gix_object::Kind>,
+    objs: &mut HashSet<ObjectId>,
+) {
+    let commits: HashSet<_> = objs
+        .iter()
+        .filter(|obj| object_kind(repo, kinds, obj).ok() == Some(gix_object::Kind::Commit))
+        .copied()
+        .collect();
+    if commits.len() < 2 {
+        return;
+    }
+    let head = match repo.head_id() {
+        Ok(id) => id,
+        Err(_) => return,
+    };
+    let mut reachable = None;
+    for info in head.ancestors().all().expect("cannot fail without sorting") {
+        let Ok(info) = info else { return };
+        if commits.contains(&info.id) {
+            if reachable.is_some() {
+                return;
+            }
+            reachable = Some(info.id);
+        }
+    }
+    if let Some(reachable) = reachable {
+        objs.retain(|obj| *obj == reachable || !commits.contains(obj));
+    }
 }
 
 fn handle_errors_and_replacements(
@@ -249,5 +437,15 @@ fn handle_errors_and_replacements(
     }
 }
 
+fn unborn_branch(r: &gix_ref::Reference) -> Error {
+    Error::UnbornBranch {
+        reference: r.name.clone(),
+        branch: r.target.try_name().unwrap_or(r.name.as_ref()).to_owned(),
+    }
+}
+
 mod navigate;
 mod revision;
+
+#[cfg(test)]
+mod tests;
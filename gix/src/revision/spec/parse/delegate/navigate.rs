@@ -12,7 +12,7 @@ use crate::{
     ext::ObjectIdExt,
     object,
     revision::spec::parse::{
-        delegate::{handle_errors_and_replacements, peel, Replacements},
+        delegate::{handle_errors_and_replacements, object_kind, peel, Replacements},
         Delegate, Error,
     },
     Object,
@@ -91,35 +91,47 @@ impl<'repo> delegate::Navigate for Delegate<'repo> {
     fn peel_until(&mut self, kind: PeelTo<'_>) -> Option<()> {
         self.unset_disambiguate_call();
         self.follow_refs_to_objects_if_needed()?;
+        let resolved_path;
+        let kind = match kind {
+            PeelTo::Path(path) => match self.resolve_relative_path(path) {
+                Ok(path) => {
+                    resolved_path = path;
+                    PeelTo::Path(resolved_path.as_ref())
+                }
+                Err(err) => {
+                    self.err.push(err);
+                    return None;
+                }
+            },
+            kind => kind,
+        };
 
         let mut replacements = Replacements::default();
         let mut errors = Vec::new();
         let objs = self.objs[self.idx].as_mut()?;
         let repo = self.repo;
+        let peeled = &mut self.peeled;
 
         match kind {
             PeelTo::ValidObject => {
+                // Nothing is replaced, but invalid candidates are pruned along with those of the other branches.
                 for obj in objs.iter() {
-                    match repo.find_object(*obj) {
-                        Ok(_) => {}
-                        Err(err) => {
-                            errors.push((*obj, err.into()));
-                        }
-                    };
+                    if let Err(err) = object_kind(repo, &mut self.kinds, obj) {
+                        errors.push((*obj, err));
+                    }
                 }
             }
             PeelTo::ObjectKind(kind) => {
-                let peel = |obj| peel(repo, obj, kind);
                 for obj in objs.iter() {
-                    match peel(obj) {
+                    match peel(repo, peeled, obj, kind) {
                         Ok(replace) => replacements.push((*obj, replace)),
                         Err(err) => errors.push((*obj, err)),
                     }
                 }
             }
             PeelTo::Path(path) => {
-                let lookup_path = |obj: &ObjectId| {
-                    let tree_id = peel(repo, obj, gix_object::Kind::Tree)?;
+                let mut lookup_path = |obj: &ObjectId| {
+                    let tree_id = peel(repo, peeled, obj, gix_object::Kind::Tree)?;
                     if path.is_empty() {
                         return Ok(tree_id);
                     }
@@ -142,9 +154,11 @@ impl<'repo> delegate::Navigate for Delegate<'repo> {
             }
             PeelTo::RecursiveTagObject => {
                 for oid in objs.iter() {
-                    match oid.attach(repo).object().and_then(Object::peel_tags_to_end) {
-                        Ok(obj) => replacements.push((*oid, obj.id)),
-                        Err(err) => errors.push((*oid, err.into())),
+                    match peeled.get_or_peel(oid, None, || {
+                        Ok(oid.attach(repo).object().and_then(Object::peel_tags_to_end)?.id)
+                    }) {
+                        Ok(id) => replacements.push((*oid, id)),
+                        Err(err) => errors.push((*oid, err)),
                     }
                 }
             }
@@ -177,6 +191,9 @@ impl<'repo> delegate::Navigate for Delegate<'repo> {
             }
         };
 
+        if self.objs[self.idx].is_none() && self.ref_lookup_disabled("refs/*") {
+            return None;
+        }
         match self.objs[self.idx].as_mut() {
             Some(objs) => {
                 let repo = self.repo;
@@ -300,6 +317,14 @@ impl<'repo> delegate::Navigate for Delegate<'repo> {
 
     fn index_lookup(&mut self, path: &BStr, stage: u8) -> Option<()> {
         self.unset_disambiguate_call();
+        let path = match self.resolve_relative_path(path) {
+            Ok(path) => path,
+            Err(err) => {
+                self.err.push(err);
+                return None;
+            }
+        };
+        let path = path.as_ref();
         match self.repo.index() {
             Ok(index) => match index.entry_by_path_and_stage(path, stage.into()) {
                 Some(entry) => {
@@ -309,7 +334,7 @@ impl<'repo> delegate::Navigate for Delegate<'repo> {
                     Some(())
                 }
                 None => {
-                    let stage_hint = [0, 1, 2]
+                    let stage_hint = [0, 1, 2, 3]
                         .iter()
                         .filter(|our_stage| **our_stage != stage)
                         .find_map(|stage| {
@@ -9,7 +9,8 @@ use gix_revision::spec::parse::{
 use crate::{
     bstr::{BStr, BString, ByteSlice},
     ext::ReferenceExt,
-    revision::spec::parse::{Delegate, Error, RefsHint},
+    remote,
+    revision::spec::parse::{Delegate, Error, PrefixInterpretation, RefsHint},
 };
 
 impl<'repo> delegate::Revision for Delegate<'repo> {
@@ -18,12 +19,22 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
         if !self.err.is_empty() && self.refs[self.idx].is_some() {
             return None;
         }
+        if self.ref_lookup_disabled(&name.to_str_lossy()) {
+            return None;
+        }
         match self.repo.refs.find(name) {
             Ok(r) => {
                 assert!(self.refs[self.idx].is_none(), "BUG: cannot set the same ref twice");
                 self.refs[self.idx] = Some(r);
                 Some(())
             }
+            Err(gix_ref::file::find::existing::Error::NotFound { .. }) => {
+                self.err.push(Error::RefNotFound {
+                    name: name.into(),
+                    suggestions: Vec::new(),
+                });
+                None
+            }
             Err(err) => {
                 self.err.push(err.into());
                 None
@@ -37,6 +48,10 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
         _must_be_commit: Option<delegate::PrefixHint<'_>>,
     ) -> Option<()> {
         self.last_call_was_disambiguate_prefix[self.idx] = true;
+        if let Some(min_len) = self.opts.min_prefix_len.filter(|min_len| prefix.hex_len() < *min_len) {
+            self.err.push(Error::PrefixTooShort { prefix, min_len });
+            return None;
+        }
         let mut candidates = Some(HashSet::default());
         self.prefix[self.idx] = Some(prefix);
 
@@ -54,7 +69,10 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
                 None
             }
             Ok(None) => {
-                self.err.push(Error::PrefixNotFound { prefix });
+                self.err.push(Error::PrefixNotFound {
+                    prefix,
+                    suggestions: Vec::new(),
+                });
                 None
             }
             Ok(Some(Ok(_) | Err(()))) => {
@@ -66,11 +84,13 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
                     {
                         self.ambiguous_objects[self.idx] = Some(candidates.clone());
                         self.objs[self.idx] = Some(candidates);
+                        self.prefix_interpretation[self.idx] = Some(PrefixInterpretation::Object);
                         Some(())
                     }
-                    RefsHint::PreferObject => {
+                    RefsHint::PreferObject | RefsHint::ObjectsOnly => {
                         self.ambiguous_objects[self.idx] = Some(candidates.clone());
                         self.objs[self.idx] = Some(candidates);
+                        self.prefix_interpretation[self.idx] = Some(PrefixInterpretation::Object);
                         Some(())
                     }
                     RefsHint::PreferRef | RefsHint::PreferObjectOnFullLengthHexShaUseRefOtherwise | RefsHint::Fail => {
@@ -83,16 +103,23 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
                                         prefix,
                                         reference: ref_,
                                     });
-                                    self.err.push(Error::ambiguous(candidates, prefix, self.repo));
+                                    self.err.push(Error::ambiguous(
+                                        candidates,
+                                        prefix,
+                                        self.opts.candidate_hex_len,
+                                        self.repo,
+                                    ));
                                     None
                                 } else {
                                     self.refs[self.idx] = Some(ref_);
+                                    self.prefix_interpretation[self.idx] = Some(PrefixInterpretation::Reference);
                                     Some(())
                                 }
                             }
                             Err(_) => {
                                 self.ambiguous_objects[self.idx] = Some(candidates.clone());
                                 self.objs[self.idx] = Some(candidates);
+                                self.prefix_interpretation[self.idx] = Some(PrefixInterpretation::Object);
                                 Some(())
                             }
                         }
@@ -104,59 +131,86 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
 
     fn reflog(&mut self, query: ReflogLookup) -> Option<()> {
         self.unset_disambiguate_call();
+        if self.refs[self.idx].is_none() && self.ref_lookup_disabled("HEAD") {
+            return None;
+        }
+        let r = match &mut self.refs[self.idx] {
+            Some(r) => r.clone().attach(self.repo),
+            val @ None => match self.repo.head().map(crate::Head::try_into_referent) {
+                Ok(Some(r)) => {
+                    *val = Some(r.clone().detach());
+                    r
+                }
+                Ok(None) => {
+                    self.err.push(Error::UnbornHeadsHaveNoRefLog);
+                    return None;
+                }
+                Err(err) => {
+                    self.err.push(err.into());
+                    return None;
+                }
+            },
+        };
+        let mut platform = r.log_iter();
         match query {
-            ReflogLookup::Date(_date) => {
-                self.err.push(Error::Planned {
-                    dependency: "remote handling and ref-specs are fleshed out more",
-                });
-                None
-            }
-            ReflogLookup::Entry(no) => {
-                let r = match &mut self.refs[self.idx] {
-                    Some(r) => r.clone().attach(self.repo),
-                    val @ None => match self.repo.head().map(crate::Head::try_into_referent) {
-                        Ok(Some(r)) => {
-                            *val = Some(r.clone().detach());
-                            r
-                        }
-                        Ok(None) => {
-                            self.err.push(Error::UnbornHeadsHaveNoRefLog);
-                            return None;
-                        }
-                        Err(err) => {
-                            self.err.push(err.into());
-                            return None;
-                        }
-                    },
-                };
-                let mut platform = r.log_iter();
-                match platform.rev().ok().flatten() {
-                    Some(mut it) => match it.nth(no).and_then(Result::ok) {
-                        Some(line) => {
-                            self.objs[self.idx]
-                                .get_or_insert_with(HashSet::default)
-                                .insert(line.new_oid);
-                            Some(())
+            ReflogLookup::Date(date) => {
+                // Like git, use the most recent entry at `date`, or the state before the oldest entry if `date` predates it.
+                let mut oldest = None;
+                let id = platform.rev().ok().flatten().and_then(|it| {
+                    for line in it.filter_map(Result::ok) {
+                        if line.signature.time.seconds <= date.seconds {
+                            return Some(line.new_oid);
                         }
-                        None => {
-                            let available = platform.rev().ok().flatten().map_or(0, Iterator::count);
-                            self.err.push(Error::RefLogEntryOutOfRange {
-                                reference: r.detach(),
-                                desired: no,
-                                available,
-                            });
-                            None
+                        oldest = Some(line);
+                    }
+                    oldest.map(|line| {
+                        if line.previous_oid.is_null() {
+                            line.new_oid
+                        } else {
+                            line.previous_oid
                         }
-                    },
+                    })
+                });
+                match id {
+                    Some(id) => {
+                        self.objs[self.idx].get_or_insert_with(HashSet::default).insert(id);
+                        Some(())
+                    }
                     None => {
                         self.err.push(Error::MissingRefLog {
                             reference: r.name().as_bstr().into(),
-                            action: "lookup entry",
+                            action: "lookup entry by date",
                         });
                         None
                     }
                 }
             }
+            ReflogLookup::Entry(no) => match platform.rev().ok().flatten() {
+                Some(mut it) => match it.nth(no).and_then(Result::ok) {
+                    Some(line) => {
+                        self.objs[self.idx]
+                            .get_or_insert_with(HashSet::default)
+                            .insert(line.new_oid);
+                        Some(())
+                    }
+                    None => {
+                        let available = platform.rev().ok().flatten().map_or(0, Iterator::count);
+                        self.err.push(Error::RefLogEntryOutOfRange {
+                            reference: r.detach(),
+                            desired: no,
+                            available,
+                        });
+                        None
+                    }
+                },
+                None => {
+                    self.err.push(Error::MissingRefLog {
+                        reference: r.name().as_bstr().into(),
+                        action: "lookup entry",
+                    });
+                    None
+                }
+            },
         }
     }
 
@@ -179,6 +233,9 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
             }
         }
 
+        if self.ref_lookup_disabled("HEAD") {
+            return None;
+        }
         let head = match self.repo.head() {
             Ok(head) => head,
             Err(err) => {
@@ -205,6 +262,7 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
                     available: prior_checkouts_iter(&mut head.log_iter())
                         .map(Iterator::count)
                         .unwrap_or(0),
+                    right_side_of_range: self.idx == 1,
                 });
                 None
             }
@@ -215,11 +273,152 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
         }
     }
 
-    fn sibling_branch(&mut self, _kind: SiblingBranch) -> Option<()> {
+    fn sibling_branch(&mut self, kind: SiblingBranch) -> Option<()> {
         self.unset_disambiguate_call();
-        self.err.push(Error::Planned {
-            dependency: "remote handling and ref-specs are fleshed out more",
-        });
-        None
+        if self.refs[self.idx].is_none() && self.ref_lookup_disabled("HEAD") {
+            return None;
+        }
+        let branch = match &self.refs[self.idx] {
+            Some(r) => r.clone().attach(self.repo),
+            None => match self.repo.head().map(crate::Head::try_into_referent) {
+                Ok(Some(r)) => r,
+                Ok(None) => {
+                    self.err.push(Error::NoLocalBranch { name: "HEAD".into() });
+                    return None;
+                }
+                Err(err) => {
+                    self.err.push(err.into());
+                    return None;
+                }
+            },
+        };
+        let name = match sibling_branch_name(&branch, kind) {
+            Ok(name) => name,
+            Err(err) => {
+                self.err.push(err);
+                return None;
+            }
+        };
+        match self.repo.refs.find(name.as_bstr()) {
+            Ok(r) => {
+                self.refs[self.idx] = Some(r);
+                Some(())
+            }
+            Err(err) => {
+                self.err.push(err.into());
+                None
+            }
+        }
     }
 }
+
+/// Return the name of the remote-tracking branch that `kind` refers to for the local `branch`, similar to how git does it.
+fn sibling_branch_name(branch: &crate::Reference<'_>, kind: SiblingBranch) -> Result<gix_ref::FullName, Error> {
+    let repo = branch.repo;
+    let short_name = match branch.name().category_and_short_name() {
+        Some((gix_ref::Category::LocalBranch, short_name)) => short_name,
+        _ => {
+            return Err(Error::NoLocalBranch {
+                name: branch.name().as_bstr().into(),
+            })
+        }
+    };
+    let no_upstream = || Error::NoUpstream {
+        branch: short_name.into(),
+    };
+    let upstream_ref = || -> Result<gix_ref::FullName, Error> {
+        Ok(repo
+            .branch_remote_ref(short_name)
+            .ok_or_else(no_upstream)?
+            .map_err(|err| Error::InvalidUpstreamName {
+                branch: short_name.into(),
+                source: err,
+            })?
+            .into_owned())
+    };
+    let not_tracked = |remote_ref: &gix_ref::FullNameRef, remote: &remote::Name<'_>| Error::NotTracked {
+        branch: short_name.into(),
+        remote_ref: remote_ref.as_bstr().into(),
+        remote: remote.as_bstr().into(),
+    };
+    match kind {
+        SiblingBranch::Upstream => {
+            let remote_name = branch.remote_name(remote::Direction::Fetch).ok_or_else(no_upstream)?;
+            let upstream = upstream_ref()?;
+            if remote_name.as_bstr() == "." {
+                return Ok(upstream);
+            }
+            let remote = repo.find_remote(remote_name.as_bstr())?;
+            tracking_ref(&remote, upstream.as_ref()).ok_or_else(|| not_tracked(upstream.as_ref(), &remote_name))
+        }
+        SiblingBranch::Push => {
+            let remote_name = branch
+                .remote_name(remote::Direction::Push)
+                .or_else(|| {
+                    repo.remote_default_name(remote::Direction::Push)
+                        .map(remote::Name::Symbol)
+                })
+                .ok_or_else(|| Error::NoPushRemote {
+                    branch: short_name.into(),
+                })?;
+            let remote = repo.find_remote(remote_name.as_bstr())?;
+            let no_destination = |reason| Error::NoPushDestination {
+                branch: short_name.into(),
+                reason,
+            };
+            let destination = if remote.refspecs(remote::Direction::Push).is_empty() {
+                let push_default = repo
+                    .config
+                    .resolved
+                    .string_filter("push", None, "default", &mut repo.filter_config_section())
+                    .map(|value| value.to_ascii_lowercase());
+                match push_default.as_deref().unwrap_or(&b"simple"[..]) {
+                    b"nothing" => return Err(no_destination("push.default is 'nothing'")),
+                    b"current" | b"matching" => branch.name().to_owned(),
+                    b"upstream" | b"tracking" => return sibling_branch_name(branch, SiblingBranch::Upstream),
+                    _simple => {
+                        let upstream = sibling_branch_name(branch, SiblingBranch::Upstream)?;
+                        let current = tracking_ref(&remote, branch.name())
+                            .ok_or_else(|| not_tracked(branch.name(), &remote_name))?;
+                        if current != upstream {
+                            return Err(no_destination(
+                                "push.default is 'simple' but the upstream branch isn't the one we would push to",
+                            ));
+                        }
+                        return Ok(current);
+                    }
+                }
+            } else {
+                map_through(remote.refspecs(remote::Direction::Push), branch.name(), repo)
+                    .ok_or_else(|| no_destination("the push refspecs of the remote don't include it"))?
+            };
+            tracking_ref(&remote, destination.as_ref()).ok_or_else(|| not_tracked(destination.as_ref(), &remote_name))
+        }
+    }
+}
+
+/// Return the remote-tracking branch that the `remote_ref` of `remote` is fetched into.
+fn tracking_ref(remote: &crate::Remote<'_>, remote_ref: &gix_ref::FullNameRef) -> Option<gix_ref::FullName> {
+    map_through(remote.refspecs(remote::Direction::Fetch), remote_ref, remote.repo())
+}
+
+/// Return the destination that the first of `specs` matching `name` maps it to.
+fn map_through(
+    specs: &[gix_refspec::RefSpec],
+    name: &gix_ref::FullNameRef,
+    repo: &crate::Repository,
+) -> Option<gix_ref::FullName> {
+    let null = gix_hash::ObjectId::null(repo.object_hash());
+    let group = gix_refspec::MatchGroup {
+        specs: specs.iter().map(gix_refspec::RefSpec::to_ref).collect(),
+    };
+    group
+        .match_remotes(std::iter::once(gix_refspec::match_group::Item {
+            full_ref_name: name.as_bstr(),
+            target: &null,
+            object: None,
+        }))
+        .mappings
+        .into_iter()
+        .find_map(|mapping| mapping.rhs.and_then(|rhs| rhs.into_owned().try_into().ok()))
+}
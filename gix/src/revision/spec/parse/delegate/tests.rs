@@ -0,0 +1,39 @@
+use gix_revision::spec::parse;
+
+use super::Delegate;
+
+fn repo() -> crate::Repository {
+    let dir = gix_testtools::scripted_fixture_read_only("make_rev_spec_parse_repos.sh").unwrap();
+    crate::open_opts(dir.join("complex_graph"), crate::open::Options::isolated()).unwrap()
+}
+
+#[test]
+fn tags_are_peeled_only_once_per_spec() {
+    let repo = repo();
+    let tag = repo
+        .find_reference("b-tag")
+        .unwrap()
+        .target()
+        .try_id()
+        .expect("not symbolic")
+        .to_owned();
+    let commit = repo.find_reference("b").unwrap().id().detach();
+
+    let mut delegate = Delegate::new(&repo, Default::default());
+    parse(
+        format!("{tag}^{{commit}}...{tag}^{{commit}}").as_str().into(),
+        &mut delegate,
+    )
+    .unwrap();
+    assert_eq!(
+        delegate.peeled.misses, 1,
+        "the tag is read once, even though it's peeled on both sides and to disambiguate it"
+    );
+    assert_eq!(
+        delegate.into_rev_spec().unwrap().detach(),
+        gix_revision::Spec::Merge {
+            theirs: commit,
+            ours: commit
+        }
+    );
+}
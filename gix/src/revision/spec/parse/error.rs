@@ -3,7 +3,12 @@ use std::collections::HashSet;
 use gix_hash::ObjectId;
 
 use super::Error;
-use crate::{bstr, bstr::BString, ext::ObjectIdExt, Repository};
+use crate::{
+    bstr,
+    bstr::{BStr, BString},
+    ext::ObjectIdExt,
+    Repository,
+};
 
 /// Additional information about candidates that caused ambiguity.
 #[derive(Debug)]
@@ -45,61 +50,164 @@ impl std::fmt::Display for CandidateInfo {
     }
 }
 
-impl Error {
-    pub(crate) fn ambiguous(candidates: HashSet<ObjectId>, prefix: gix_hash::Prefix, repo: &Repository) -> Self {
-        #[derive(PartialOrd, Ord, Eq, PartialEq, Copy, Clone)]
-        enum Order {
-            Tag,
-            Commit,
-            Tree,
-            Blob,
-            Invalid,
+/// Return information about all `candidates`, sorted by kind (tags, commits, trees, blobs and objects that couldn't be read)
+/// and id.
+pub(crate) fn candidates_info(
+    candidates: impl IntoIterator<Item = ObjectId>,
+    repo: &Repository,
+) -> Vec<(ObjectId, CandidateInfo)> {
+    #[derive(PartialOrd, Ord, Eq, PartialEq, Copy, Clone)]
+    enum Order {
+        Tag,
+        Commit,
+        Tree,
+        Blob,
+        Invalid,
+    }
+    let candidates = {
+        let mut c: Vec<_> = candidates
+            .into_iter()
+            .map(|oid| {
+                let obj = repo.find_object(oid);
+                let order = match &obj {
+                    Err(_) => Order::Invalid,
+                    Ok(obj) => match obj.kind {
+                        gix_object::Kind::Tag => Order::Tag,
+                        gix_object::Kind::Commit => Order::Commit,
+                        gix_object::Kind::Tree => Order::Tree,
+                        gix_object::Kind::Blob => Order::Blob,
+                    },
+                };
+                (oid, obj, order)
+            })
+            .collect();
+        c.sort_by(|lhs, rhs| lhs.2.cmp(&rhs.2).then_with(|| lhs.0.cmp(&rhs.0)));
+        c
+    };
+    candidates
+        .into_iter()
+        .map(|(oid, find_result, _)| {
+            let info = match find_result {
+                Ok(obj) => match obj.kind {
+                    gix_object::Kind::Tree | gix_object::Kind::Blob => CandidateInfo::Object { kind: obj.kind },
+                    gix_object::Kind::Tag => {
+                        let tag = obj.to_tag_ref();
+                        CandidateInfo::Tag { name: tag.name.into() }
+                    }
+                    gix_object::Kind::Commit => {
+                        use bstr::ByteSlice;
+                        let commit = obj.to_commit_ref();
+                        CandidateInfo::Commit {
+                            date: commit.committer().time,
+                            title: commit.message().title.trim().into(),
+                        }
+                    }
+                },
+                Err(err) => CandidateInfo::FindError { source: err },
+            };
+            (oid, info)
+        })
+        .collect()
+}
+
+/// The maximum amount of similar reference names to suggest.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Return the short names of up to [`MAX_SUGGESTIONS`] references in `repo` whose names are similar to `name`, sorted by
+/// similarity and name, or nothing if there are none or if the references can't be read.
+///
+/// Names are similar if only about a third of their characters have to be changed to turn one into the other,
+/// while at least one of them stays the same.
+pub(crate) fn similar_reference_names(name: &BStr, repo: &Repository) -> Vec<BString> {
+    let max_distance = (name.len() / 3).max(1).min(name.len().saturating_sub(1));
+    let Ok(refs) = repo.references() else {
+        return Vec::new();
+    };
+    let Ok(refs) = refs.all() else {
+        return Vec::new();
+    };
+    let mut candidates: Vec<_> = refs
+        .filter_map(Result::ok)
+        .filter_map(|r| {
+            let short_name = r.name().shorten();
+            let distance = edit_distance(name, short_name);
+            (distance <= max_distance).then(|| (distance, short_name.to_owned()))
+        })
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// Return the amount of single-byte insertions, deletions, substitutions or swaps of adjacent bytes needed to turn `a` into `b`,
+/// so that typos like `mian` are only one edit away from `main`.
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut row = vec![0; b.len() + 1];
+    for (i, a_byte) in a.iter().enumerate() {
+        row[0] = i + 1;
+        for (j, b_byte) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_byte != b_byte);
+            row[j + 1] = substitution.min(row[j] + 1).min(previous[j + 1] + 1);
+            if i > 0 && j > 0 && *a_byte == b[j - 1] && a[i - 1] == *b_byte {
+                row[j + 1] = row[j + 1].min(before_previous[j - 1] + 1);
+            }
         }
-        let candidates = {
-            let mut c: Vec<_> = candidates
-                .into_iter()
-                .map(|oid| {
-                    let obj = repo.find_object(oid);
-                    let order = match &obj {
-                        Err(_) => Order::Invalid,
-                        Ok(obj) => match obj.kind {
-                            gix_object::Kind::Tag => Order::Tag,
-                            gix_object::Kind::Commit => Order::Commit,
-                            gix_object::Kind::Tree => Order::Tree,
-                            gix_object::Kind::Blob => Order::Blob,
-                        },
-                    };
-                    (oid, obj, order)
-                })
-                .collect();
-            c.sort_by(|lhs, rhs| lhs.2.cmp(&rhs.2).then_with(|| lhs.0.cmp(&rhs.0)));
-            c
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut row);
+    }
+    previous[b.len()]
+}
+
+/// Format `suggestions` for display at the end of an error message, or return an empty string if there are none.
+pub(crate) fn fmt_suggestions(suggestions: &[BString]) -> String {
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    format!(
+        ". The most similar references are:\n{}",
+        suggestions
+            .iter()
+            .map(|name| format!("\t{name}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    )
+}
+
+impl From<crate::remote::find::existing::Error> for Error {
+    fn from(err: crate::remote::find::existing::Error) -> Self {
+        Error::FindRemote(Box::new(err))
+    }
+}
+
+impl Error {
+    /// Create an error listing all `candidates` for `prefix`, each abbreviated to `hex_len` hex characters or to `core.abbrev`
+    /// if `None`, but always long enough to be unambiguous.
+    pub(crate) fn ambiguous(
+        candidates: HashSet<ObjectId>,
+        prefix: gix_hash::Prefix,
+        hex_len: Option<usize>,
+        repo: &Repository,
+    ) -> Self {
+        let shorten = |oid: ObjectId| match hex_len {
+            None => oid.attach(repo).shorten().ok(),
+            Some(hex_len) => gix_odb::store::prefix::disambiguate::Candidate::new(
+                oid,
+                hex_len.clamp(gix_hash::Prefix::MIN_HEX_LEN, oid.kind().len_in_hex()),
+            )
+            .ok()
+            .and_then(|candidate| repo.objects.disambiguate_prefix(candidate).ok().flatten()),
         };
         Error::AmbiguousPrefix {
             prefix,
-            info: candidates
+            info: candidates_info(candidates, repo)
                 .into_iter()
-                .map(|(oid, find_result, _)| {
-                    let info = match find_result {
-                        Ok(obj) => match obj.kind {
-                            gix_object::Kind::Tree | gix_object::Kind::Blob => CandidateInfo::Object { kind: obj.kind },
-                            gix_object::Kind::Tag => {
-                                let tag = obj.to_tag_ref();
-                                CandidateInfo::Tag { name: tag.name.into() }
-                            }
-                            gix_object::Kind::Commit => {
-                                use bstr::ByteSlice;
-                                let commit = obj.to_commit_ref();
-                                CandidateInfo::Commit {
-                                    date: commit.committer().time,
-                                    title: commit.message().title.trim().into(),
-                                }
-                            }
-                        },
-                        Err(err) => CandidateInfo::FindError { source: err },
-                    };
-                    (oid.attach(repo).shorten().unwrap_or_else(|_| oid.into()), info)
-                })
+                .map(|(oid, info)| (shorten(oid).unwrap_or_else(|| oid.into()), info))
                 .collect(),
         }
     }
@@ -1,18 +1,25 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use gix_hash::ObjectId;
 use gix_revision::spec::parse;
 
-use crate::{bstr::BStr, revision::Spec, Repository};
+use crate::{
+    bstr::{BStr, BString},
+    revision::Spec,
+    Repository,
+};
 
 mod types;
-pub use types::{Error, ObjectKindHint, Options, RefsHint};
+pub use types::{Error, ObjectKindHint, Options, PrefixInterpretation, RefsHint};
 
 ///
 pub mod single {
     use crate::bstr::BString;
 
     /// The error returned by [`crate::Repository::rev_parse_single()`].
+    ///
+    /// It distinguishes specs that resolved to more than one object, like ranges, from those resolving to unborn
+    /// branches, and from those which couldn't be resolved at all.
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
     pub enum Error {
@@ -20,6 +27,8 @@ pub mod single {
         Parse(#[from] super::Error),
         #[error("revspec {spec:?} did not resolve to a single object")]
         RangedRev { spec: BString },
+        #[error("revspec {spec:?} resolved to the unborn branch '{branch}' which doesn't point to an object yet")]
+        UnbornBranch { spec: BString, branch: gix_ref::FullName },
     }
 }
 
@@ -40,6 +49,66 @@ impl<'repo> Spec<'repo> {
     }
 }
 
+/// Initialization
+impl Options {
+    /// Create options which use the configuration of `repo` to learn how to deal with ambiguity,
+    /// like `core.disambiguate`, which is what [`repo.rev_parse()`][Repository::rev_parse()] uses as well.
+    ///
+    /// The [prefix](Options::prefix) is set to the location of the current working directory within the worktree, if it's inside of it.
+    pub fn from_repo(repo: &Repository) -> Self {
+        Options {
+            object_kind_hint: repo.config.object_kind_hint,
+            prefix: repo
+                .prefix()
+                .ok()
+                .flatten()
+                .map(|prefix| gix_path::to_unix_separators_on_windows(gix_path::into_bstr(prefix)).into_owned()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Builder
+impl Options {
+    /// Set the hint to know what to do if refs and object names are equal.
+    pub fn refs_hint(mut self, hint: RefsHint) -> Self {
+        self.refs_hint = hint;
+        self
+    }
+
+    /// Set the `hint` to use when encountering multiple objects matching a prefix, or `None` to require
+    /// the rev-spec to disambiguate them.
+    pub fn object_kind_hint(mut self, hint: Option<ObjectKindHint>) -> Self {
+        self.object_kind_hint = hint;
+        self
+    }
+
+    /// Set the minimum amount of hex characters object prefixes must have to be resolved, or `None` to allow all.
+    pub fn min_prefix_len(mut self, len: Option<usize>) -> Self {
+        self.min_prefix_len = len;
+        self
+    }
+
+    /// Set the amount of hex characters to abbreviate ambiguous candidates to, or `None` to use `core.abbrev`.
+    pub fn candidate_hex_len(mut self, len: Option<usize>) -> Self {
+        self.candidate_hex_len = len;
+        self
+    }
+
+    /// Set the path of the current working directory relative to the worktree root to resolve relative paths with,
+    /// or `None` to not allow relative paths.
+    pub fn prefix(mut self, prefix: Option<BString>) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// If `toggle` is true, resolve ambiguous commit prefixes to the only candidate reachable from `HEAD`.
+    pub fn prefer_reachable_from_head(mut self, toggle: bool) -> Self {
+        self.prefer_reachable_from_head = toggle;
+        self
+    }
+}
+
 struct Delegate<'repo> {
     refs: [Option<gix_ref::Reference>; 2],
     objs: [Option<HashSet<ObjectId>>; 2],
@@ -52,8 +121,14 @@ struct Delegate<'repo> {
     err: Vec<Error>,
     /// The ambiguous prefix obtained during a call to `disambiguate_prefix()`.
     prefix: [Option<gix_hash::Prefix>; 2],
+    /// How the prefix obtained during a call to `disambiguate_prefix()` was interpreted.
+    prefix_interpretation: [Option<PrefixInterpretation>; 2],
     /// If true, we didn't try to do any other transformation which might have helped with disambiguation.
     last_call_was_disambiguate_prefix: [bool; 2],
+    /// Objects we already peeled, to avoid reading the same tags multiple times.
+    peeled: delegate::Peeled,
+    /// The kinds of objects we already read, to classify each candidate only once.
+    kinds: HashMap<ObjectId, gix_object::Kind>,
 
     repo: &'repo Repository,
 }
@@ -19,6 +19,22 @@ pub enum RefsHint {
     PreferRef,
     /// If there is an ambiguous situation, instead of silently choosing one over the other, fail instead.
     Fail,
+    /// Never look up references, and treat all names as objects which makes it suitable to resolve many hashes in bulk.
+    /// Specs that need a reference, like `HEAD`, `main` or `@{1}`, fail with [`Error::RefLookupDisabled`].
+    ObjectsOnly,
+}
+
+/// How a name that is both a valid object prefix with matching objects and a possible reference name was interpreted,
+/// as determined by the [`RefsHint`] in effect.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PrefixInterpretation {
+    /// The name was used as object prefix.
+    ///
+    /// Note that with [`RefsHint::PreferObject`] and [`RefsHint::ObjectsOnly`] no reference is looked up,
+    /// so it's unknown if a reference of the same name exists.
+    Object,
+    /// A reference with the same name existed and was used instead of the objects matching the prefix.
+    Reference,
 }
 
 /// A hint to know which object kind to prefer if multiple objects match a prefix.
@@ -40,7 +56,7 @@ pub enum ObjectKindHint {
 }
 
 /// Options for use in [`revision::Spec::from_bstr()`][crate::revision::Spec::from_bstr()].
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct Options {
     /// What to do if both refs and object names match the same input.
     pub refs_hint: RefsHint,
@@ -49,6 +65,27 @@ pub struct Options {
     /// If `None`, the rev-spec itself must disambiguate the object by drilling down to desired kinds or applying
     /// other disambiguating transformations.
     pub object_kind_hint: Option<ObjectKindHint>,
+    /// If set, object prefixes with fewer hex characters than this fail with [`Error::PrefixTooShort`] instead of being
+    /// resolved, which protects against accidentally matching an unrelated object.
+    ///
+    /// Names that are too short are still looked up as references.
+    pub min_prefix_len: Option<usize>,
+    /// The amount of hex characters to abbreviate the ids of candidates to in [`Error::AmbiguousPrefix`], or `None` to use
+    /// the length configured in `core.abbrev`.
+    ///
+    /// Ids are always made long enough to be unambiguous.
+    pub candidate_hex_len: Option<usize>,
+    /// The path of the current working directory relative to the root of the worktree, with `/` as separator,
+    /// or `None` if it's not inside of the worktree.
+    ///
+    /// It's used to resolve paths starting with `./` or `../` in specs like `:./file` or `HEAD:../file`, which fail with
+    /// [`Error::RelativePathWithoutPrefix`] if it's `None`. An empty prefix is the root of the worktree.
+    pub prefix: Option<BString>,
+    /// If true, and multiple commits match a prefix after applying the [object kind hint](Options::object_kind_hint),
+    /// prefer the one that is reachable from `HEAD` if it's the only one.
+    ///
+    /// This needs to traverse the commit graph from `HEAD`, which is why it's off by default.
+    pub prefer_reachable_from_head: bool,
 }
 
 /// The error returned by [`crate::Repository::rev_parse()`].
@@ -59,12 +96,27 @@ pub enum Error {
     Malformed,
     #[error("Unborn heads do not have a reflog yet")]
     UnbornHeadsHaveNoRefLog,
+    #[error("Reference '{reference}' points to the unborn branch '{branch}' which doesn't exist yet")]
+    UnbornBranch {
+        /// The reference that was used, like `HEAD`.
+        reference: gix_ref::FullName,
+        /// The branch that `reference` points to, like `refs/heads/main` in a new repository.
+        branch: gix_ref::FullName,
+    },
     #[error("This feature will be implemented once {dependency}")]
     Planned { dependency: &'static str },
     #[error("Reference {reference:?} does not have a reference log, cannot {action}")]
     MissingRefLog { reference: BString, action: &'static str },
-    #[error("HEAD has {available} prior checkouts and checkout number {desired} is out of range")]
-    PriorCheckoutOutOfRange { desired: usize, available: usize },
+    #[error(
+        "HEAD has {available} prior checkouts and checkout number {desired}{} is out of range",
+        if *right_side_of_range { " on the right side of the range" } else { "" }
+    )]
+    PriorCheckoutOutOfRange {
+        desired: usize,
+        available: usize,
+        /// If `true`, the checkout was requested in the second revision of a range, like `@{-1}..@{-5}`.
+        right_side_of_range: bool,
+    },
     #[error("Reference {:?} has {available} ref-log entries and entry number {desired} is out of range", reference.name.as_bstr())]
     RefLogEntryOutOfRange {
         reference: gix_ref::Reference,
@@ -92,6 +144,10 @@ pub enum Error {
         stage_hint: Option<gix_index::entry::Stage>,
         exists: bool,
     },
+    #[error("Relative path {path:?} can only be used if the current directory is inside of the worktree")]
+    RelativePathWithoutPrefix { path: BString },
+    #[error("Relative path {path:?} points outside of the worktree")]
+    RelativePathOutsideOfWorktree { path: BString },
     #[error(transparent)]
     FindHead(#[from] reference::find::existing::Error),
     #[error(transparent)]
@@ -133,10 +189,49 @@ pub enum Error {
         /// The reference matching the prefix.
         reference: gix_ref::Reference,
     },
+    #[error("Reference {name:?} would have to be looked up, but only objects are allowed")]
+    RefLookupDisabled {
+        /// The name of the reference that would have been looked up.
+        name: BString,
+    },
+    #[error("Only local branches have an upstream or push destination, but {name:?} is not one")]
+    NoLocalBranch { name: BString },
+    #[error("Branch {branch:?} has no upstream configured")]
+    NoUpstream { branch: BString },
+    #[error("The upstream of branch {branch:?} is not a valid reference name")]
+    InvalidUpstreamName {
+        branch: BString,
+        source: gix_validate::reference::name::Error,
+    },
+    #[error("Branch {branch:?} has no remote for pushing")]
+    NoPushRemote { branch: BString },
+    #[error("Branch {branch:?} has no push destination as {reason}")]
+    NoPushDestination { branch: BString, reason: &'static str },
+    #[error(
+        "Branch {branch:?} maps to {remote_ref:?} which is not stored as remote-tracking branch of remote {remote:?}"
+    )]
+    NotTracked {
+        branch: BString,
+        remote_ref: BString,
+        remote: BString,
+    },
+    #[error(transparent)]
+    FindRemote(Box<crate::remote::find::existing::Error>),
     #[error(transparent)]
     IdFromHex(#[from] gix_hash::decode::Error),
     #[error(transparent)]
     FindReference(#[from] gix_ref::file::find::existing::Error),
+    #[error(
+        "The ref partially named {name:?} could not be found{}",
+        super::error::fmt_suggestions(suggestions)
+    )]
+    RefNotFound {
+        /// The name of the reference as given in the rev-spec.
+        name: BString,
+        /// The short names of the references whose names are most similar to `name`, most similar first,
+        /// which is empty if there is no such reference.
+        suggestions: Vec<BString>,
+    },
     #[error(transparent)]
     FindObject(#[from] object::find::existing::Error),
     #[error(transparent)]
@@ -151,8 +246,18 @@ pub enum Error {
     },
     #[error(transparent)]
     Parse(#[from] gix_revision::spec::parse::Error),
-    #[error("An object prefixed {prefix} could not be found")]
-    PrefixNotFound { prefix: gix_hash::Prefix },
+    #[error(
+        "An object prefixed {prefix} could not be found{}",
+        super::error::fmt_suggestions(suggestions)
+    )]
+    PrefixNotFound {
+        prefix: gix_hash::Prefix,
+        /// The short names of the references whose names are most similar to `prefix`, most similar first,
+        /// which is empty if there is no such reference.
+        suggestions: Vec<BString>,
+    },
+    #[error("The short hash {prefix} must have at least {min_len} hex characters to be used")]
+    PrefixTooShort { prefix: gix_hash::Prefix, min_len: usize },
     #[error("Short id {prefix} is ambiguous. Candidates are:\n{}", info.iter().map(|(oid, info)| format!("\t{oid} {info}")).collect::<Vec<_>>().join("\n"))]
     AmbiguousPrefix {
         prefix: gix_hash::Prefix,
@@ -0,0 +1,96 @@
+//! Submodule plumbing and abstractions
+use std::borrow::Cow;
+
+use crate::bstr::BStr;
+pub use gix_submodule::*;
+
+///
+pub mod modules {
+    /// The error returned by [`Repository::modules()`](crate::Repository::modules()) and
+    /// [`Repository::submodules()`](crate::Repository::submodules()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read the .gitmodules file from the worktree")]
+        Io(#[from] std::io::Error),
+        #[error("The .gitmodules file could not be parsed")]
+        Parse(#[from] gix_config::parse::Error),
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        PeelHead(#[from] crate::head::peel::Error),
+        #[error(transparent)]
+        FromTree(#[from] crate::repository::submodules_from_tree::Error),
+    }
+}
+
+///
+pub mod is_active {
+    /// The error returned by [`Submodule::is_active()`](crate::Submodule::is_active()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        PathspecDefaults(#[from] gix_pathspec::defaults::from_environment::Error),
+        #[error(transparent)]
+        ActiveState(#[from] gix_submodule::config::names_and_active_state::Error),
+        #[error(transparent)]
+        ActiveField(#[from] gix_submodule::config::names_and_active_state::iter::Error),
+    }
+}
+
+/// Access
+impl<'repo> crate::Submodule<'repo> {
+    /// Return the name of the submodule, as used in `submodule.<name>` configuration sections.
+    pub fn name(&self) -> &BStr {
+        self.name.as_ref()
+    }
+
+    /// Return the path relative to the root of the worktree at which the submodule is checked out.
+    pub fn path(&self) -> Result<Cow<'_, BStr>, config::path::Error> {
+        self.modules.path(self.name())
+    }
+
+    /// Return the url to fetch the submodule from, taking overrides from the repository configuration into account.
+    pub fn url(&self) -> Result<gix_url::Url, config::url::Error> {
+        self.modules.url(self.name())
+    }
+
+    /// Return the branch to track on the remote, if set.
+    pub fn branch(&self) -> Result<Option<config::Branch>, config::branch::Error> {
+        self.modules.branch(self.name())
+    }
+
+    /// Return how `git submodule update` should update the submodule, if set.
+    pub fn update(&self) -> Result<Option<config::Update>, config::update::Error> {
+        self.modules.update(self.name())
+    }
+
+    /// Return how the submodule participates in `git status`, if set.
+    pub fn ignore(&self) -> Result<Option<config::Ignore>, config::Error> {
+        self.modules.ignore(self.name())
+    }
+
+    /// Return how to recurse into the submodule when fetching, if set.
+    pub fn fetch_recurse(&self) -> Result<Option<config::FetchRecurse>, config::Error> {
+        self.modules.fetch_recurse(self.name())
+    }
+
+    /// Return `true` if the submodule is considered active according to the configuration of the repository,
+    /// which typically means it's meant to be checked out.
+    ///
+    /// ### Deviation
+    ///
+    /// Pathspecs in `submodule.active` with attribute matching, like `:(attr:a)`, never match.
+    pub fn is_active(&self) -> Result<bool, is_active::Error> {
+        let defaults = self.repo.pathspec_defaults()?;
+        Ok(self
+            .modules
+            .is_active_platform(&self.repo.config.resolved, defaults)?
+            .is_active(self.name(), |_, _, _, _| false)?)
+    }
+}
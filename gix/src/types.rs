@@ -1,6 +1,7 @@
 use std::{cell::RefCell, path::PathBuf};
 
 use gix_hash::ObjectId;
+use gix_object::bstr::BString;
 
 use crate::{head, remote};
 
@@ -206,3 +207,14 @@ pub struct Pathspec<'repo> {
     /// The prepared search to use for checking matches.
     pub(crate) search: gix_pathspec::Search,
 }
+
+/// A stand-in for the submodule of a particular name, with access to its configuration as merged from the `.gitmodules` file
+/// and the repository configuration.
+#[derive(Clone)]
+pub struct Submodule<'repo> {
+    /// The `.gitmodules` file along with all overrides, shared among all submodules obtained from it.
+    pub(crate) modules: gix_features::threading::OwnShared<gix_submodule::File>,
+    /// The name of the submodule, which is the key into `modules`.
+    pub(crate) name: BString,
+    pub(crate) repo: &'repo Repository,
+}
@@ -240,6 +240,15 @@ git clone ambiguous_commits duplicate_ambiguous_objects
   diff actual expect # git deduplicates the same objects even though they are in the loose and packed odb
 )
 
+git clone ambiguous_commits ambiguous_commits_partially_reachable
+(
+  cd ambiguous_commits_partially_reachable
+  # only the root commit 0000000000e4f remains reachable from HEAD, the other commits with the same prefix don't
+  git reset --hard 0000000000e4f
+
+  baseline "0000000000"  # ambiguous as git doesn't consider reachability here
+)
+
 git clone ambiguous_blob_tree_commit ambiguous_refs
 (
     cd ambiguous_refs
@@ -377,6 +386,11 @@ git init complex_graph
   baseline "@{-4}"
   baseline "@{-5}"
   baseline "@{-6}"
+  baseline "@{-1}..@{-2}"
+  baseline "@{-1}..HEAD"
+  baseline "@{-2}...@{-1}"
+  baseline "@{-6}..@{-1}"
+  baseline "@{-1}..@{-6}"
 
   baseline "@{0}"
   baseline "@{3}"
@@ -402,3 +416,151 @@ git init new
 (cd new
   baseline '@{1}'
 )
+
+git init pseudo_refs
+(
+  cd pseudo_refs
+  tick
+  git commit --allow-empty -m first
+  first=$(git rev-parse HEAD)
+
+  tick
+  git commit --allow-empty -m second
+  second=$(git rev-parse HEAD)
+
+  echo $first > .git/ORIG_HEAD
+  echo $second > .git/MERGE_HEAD
+  echo $first > .git/CHERRY_PICK_HEAD
+  printf "%s\t\tbranch 'main' of ../upstream\n" $second > .git/FETCH_HEAD
+
+  baseline "ORIG_HEAD"
+  baseline "MERGE_HEAD"
+  baseline "CHERRY_PICK_HEAD"
+  baseline "FETCH_HEAD"
+  baseline "ORIG_HEAD..MERGE_HEAD"
+)
+
+git init sibling_branches
+(
+  cd sibling_branches
+  tick
+  git commit --allow-empty -m local
+  tick
+  git commit --allow-empty -m upstream
+  git update-ref refs/remotes/origin/main HEAD
+  tick
+  git commit --allow-empty -m other-name
+  git update-ref refs/remotes/origin/other-name HEAD
+  tick
+  git commit --allow-empty -m fork
+  git update-ref refs/remotes/fork/main HEAD
+  git reset --hard HEAD~3
+  git branch feature
+
+  git config remote.origin.url ../origin
+  git config remote.origin.fetch '+refs/heads/*:refs/remotes/origin/*'
+  git config remote.fork.url ../fork
+  git config remote.fork.fetch '+refs/heads/*:refs/remotes/fork/*'
+  git config remote.pushDefault fork
+  git config push.default current
+
+  git config branch.main.remote origin
+  git config branch.main.merge refs/heads/main
+  git config branch.feature.remote origin
+  git config branch.feature.merge refs/heads/other-name
+  git config branch.feature.pushRemote origin
+
+  baseline "main@{upstream}"
+  baseline "main@{u}"
+  baseline "@{u}"
+  baseline "main@{push}"
+  baseline "@{push}"
+  baseline "feature@{u}"
+  baseline "feature@{push}"
+)
+
+git init relative_paths
+(
+  cd relative_paths
+  mkdir -p dir/sub
+  echo top > top
+  echo dir > dir/file
+  echo sub > dir/sub/file
+  tick
+  git add . && git commit -m initial
+
+  baseline ":top"
+  baseline ":dir/file"
+  baseline ":dir/sub/file"
+  baseline "HEAD:dir"
+  baseline "HEAD:dir/sub/file"
+)
+
+git init tag_kinds
+(
+  cd tag_kinds
+  echo content > file
+  tick
+  git add file && git commit -m initial
+  git tag -a -m annotated v1.0
+  git tag -a -m nested v1.0-nested v1.0
+  git tag lightweight
+
+  baseline "v1.0"
+  baseline "v1.0-nested"
+  baseline "v1.0^{tag}"
+  baseline "v1.0-nested^{tag}"
+  baseline "lightweight^{tag}"
+  baseline "v1.0^{tree}^{tag}"
+)
+
+git init reflog_dates
+(
+  cd reflog_dates
+  echo a > file
+  git add file
+  GIT_COMMITTER_DATE="2022-12-15 12:00:00 +0000" git commit -m A
+  echo b > file
+  git add file
+  GIT_COMMITTER_DATE="2023-01-15 12:00:00 +0000" git commit -m B
+
+  baseline "HEAD@{2023-01-01}"
+  baseline "main@{2023-01-01}"
+  baseline "main@{2023-02-01}"
+  baseline "HEAD@{yesterday}"
+  baseline "HEAD@{2.days.ago}"
+  baseline "@{now}"
+  baseline "main@{2022-01-01}"  # before the oldest entry, which is the one that created the branch
+)
+
+git init regex_anchoring
+(
+  cd regex_anchoring
+  tick
+  echo a > file
+  git add file && git commit -m "fix: the first problem"
+  tick
+  echo b > file
+  git add file && git commit -m "feature"
+
+  git checkout -b other
+  tick
+  echo c > file
+  git add file && git commit -m "fix: a problem on another branch, the youngest"
+  git checkout main
+
+  baseline ":/fix"           # the youngest match reachable from any reference
+  baseline "HEAD^{/fix}"     # the youngest match reachable from HEAD
+  baseline "other^{/fix}"
+  baseline "HEAD^{/!-fix}"
+)
+
+git init ref_named_like_object_prefix
+(
+  cd ref_named_like_object_prefix
+  echo bnkxmdwz | git hash-object -w --stdin # has the prefix "cafe"
+  git commit --allow-empty -m "first"
+  git branch cafe
+
+  baseline "cafe" # git warns that the ref name is ambiguous and uses the ref
+)
@@ -24,3 +24,5 @@ git init with-submodules
 
   git submodule add ../module1 dir/m1
 )
+
+git clone --bare with-submodules with-submodules-bare
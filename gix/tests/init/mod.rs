@@ -38,6 +38,26 @@ mod bare {
         Ok(())
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn init_bare_honors_the_configured_default_branch() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let _env = gix_testtools::Env::new()
+            .set("GIT_CONFIG_COUNT", "1")
+            .set("GIT_CONFIG_KEY_0", "init.defaultBranch")
+            .set("GIT_CONFIG_VALUE_0", "trunk");
+        let repo: gix::Repository =
+            gix::ThreadSafeRepository::init_bare(tmp.path().join("server.git"), Default::default())?.into();
+        assert!(repo.is_bare());
+        assert!(repo.work_dir().is_none());
+        assert_eq!(
+            repo.head_name()?.expect("symbolic").as_bstr(),
+            "refs/heads/trunk",
+            "the default branch is taken from the configuration"
+        );
+        Ok(())
+    }
+
     #[test]
     fn init_into_non_empty_directory_is_not_allowed() -> crate::Result {
         let tmp = tempfile::tempdir()?;
@@ -74,6 +94,273 @@ mod non_bare {
         );
         Ok(())
     }
+    #[allow(clippy::result_large_err)]
+    fn init_with_head(
+        dir: &std::path::Path,
+        head: gix::create::HeadTarget,
+    ) -> Result<gix::Repository, gix::init::Error> {
+        Ok(gix::ThreadSafeRepository::init_opts(
+            dir,
+            gix::create::Kind::Bare,
+            gix::create::Options {
+                head: Some(head),
+                ..Default::default()
+            },
+            gix::open::Options::isolated().config_overrides(["init.defaultBranch=ignored"]),
+        )?
+        .into())
+    }
+
+    #[test]
+    fn init_with_explicit_head_target() -> crate::Result {
+        use gix::create::HeadTarget;
+        let tmp = tempfile::tempdir()?;
+
+        let repo = init_with_head(&tmp.path().join("0"), HeadTarget::Symbolic("refs/heads/foo".into()))?;
+        assert_eq!(
+            repo.head()?.referent_name().expect("name").as_bstr(),
+            "refs/heads/foo",
+            "the configured default branch is ignored if HEAD is set explicitly"
+        );
+
+        let repo = init_with_head(
+            &tmp.path().join("1"),
+            HeadTarget::Symbolic("refs/remotes/origin/main".into()),
+        )?;
+        assert_eq!(
+            repo.head()?.referent_name().expect("name").as_bstr(),
+            "refs/remotes/origin/main",
+            "any reference within refs/ is allowed"
+        );
+
+        let id = gix::ObjectId::empty_tree(gix::hash::Kind::Sha1);
+        let repo = init_with_head(&tmp.path().join("2"), HeadTarget::Detached(id))?;
+        assert!(repo.head()?.is_detached());
+        assert_eq!(repo.head_id()?, id);
+
+        assert!(matches!(
+            init_with_head(&tmp.path().join("3"), HeadTarget::Symbolic("refs/heads/a..b".into())).unwrap_err(),
+            gix::init::Error::InvalidHeadTarget { .. }
+        ));
+        assert_eq!(
+            init_with_head(&tmp.path().join("4"), HeadTarget::Symbolic("FETCH_HEAD".into()))
+                .unwrap_err()
+                .to_string(),
+            "HEAD can only point to references within refs/, not to \"FETCH_HEAD\""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn description_and_info_exclude_are_created_like_git_does() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        for (kind, dir) in [
+            (gix::create::Kind::WithWorktree, "worktree"),
+            (gix::create::Kind::Bare, "bare.git"),
+        ] {
+            let repo: gix::Repository =
+                gix::ThreadSafeRepository::init(tmp.path().join(dir), kind, Default::default())?.into();
+            let description = std::fs::read_to_string(repo.git_dir().join("description"))?;
+            assert!(
+                description.starts_with("Unnamed repository;"),
+                "{dir}: hosting tools pick up the name from here"
+            );
+            let exclude = std::fs::read_to_string(repo.git_dir().join("info").join("exclude"))?;
+            assert!(
+                exclude.lines().all(|line| line.is_empty() || line.starts_with('#')),
+                "{dir}: it only contains comments and doesn't exclude anything"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn init_with_initial_commit() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                initial_commit: Some(Default::default()),
+                ..Default::default()
+            },
+            gix::open::Options::isolated().config_overrides(["user.name=a", "user.email=b"]),
+        )?
+        .into();
+
+        let mut head = repo.head()?;
+        assert_eq!(head.referent_name().expect("name").as_bstr(), "refs/heads/main");
+        let commit = head.peel_to_commit_in_place()?;
+        assert_eq!(commit.message_raw()?, "Initial commit");
+        assert_eq!(commit.parent_ids().count(), 0, "it's the only commit");
+        assert_eq!(commit.tree_id()?, gix::ObjectId::empty_tree(repo.object_hash()));
+        assert_eq!(commit.author()?.name, "a");
+        assert_eq!(
+            repo.rev_parse_single("main")?,
+            commit.id,
+            "the default branch points to it"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn init_with_packed_refs() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo: gix::Repository = gix::ThreadSafeRepository::init(
+            tmp.path().join("empty"),
+            gix::create::Kind::Bare,
+            gix::create::Options {
+                packed_refs: true,
+                ..Default::default()
+            },
+        )?
+        .into();
+        assert_eq!(
+            std::fs::read(repo.git_dir().join("packed-refs"))?,
+            b"# pack-refs with: peeled fully-peeled sorted \n",
+            "the file only has a header"
+        );
+        assert_eq!(repo.references()?.all()?.count(), 0);
+
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path().join("with-commit"),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                packed_refs: true,
+                initial_commit: Some(Default::default()),
+                ..Default::default()
+            },
+            gix::open::Options::isolated().config_overrides(["user.name=a", "user.email=b"]),
+        )?
+        .into();
+        assert!(
+            !repo.git_dir().join("refs/heads/main").exists(),
+            "the branch of the initial commit isn't loose"
+        );
+        let head_id = repo.head_id()?;
+        let packed = repo.refs.cached_packed_buffer()?.expect("packed-refs file exists");
+        assert_eq!(packed.find("refs/heads/main")?.target(), head_id, "but packed instead");
+        assert_eq!(
+            repo.find_reference("main")?.log_iter().all()?.expect("present").count(),
+            1,
+            "the reflog of the initial commit is kept as is"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn init_with_initial_commit_fails_without_author() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let err = gix::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            gix::create::Kind::Bare,
+            gix::create::Options {
+                initial_commit: Some(gix::create::InitialCommit { message: "first" }),
+                ..Default::default()
+            },
+            gix::open::Options::isolated(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            gix::init::Error::InitialCommit(gix::commit::Error::AuthorMissing)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn init_with_initial_commit_fails_with_detached_head() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let dir = tmp.path().join("repo");
+        let err = gix::ThreadSafeRepository::init_opts(
+            &dir,
+            gix::create::Kind::Bare,
+            gix::create::Options {
+                initial_commit: Some(Default::default()),
+                head: Some(gix::create::HeadTarget::Detached(gix::ObjectId::empty_tree(
+                    gix::hash::Kind::Sha1,
+                ))),
+                ..Default::default()
+            },
+            gix::open::Options::isolated().config_overrides(["user.name=a", "user.email=b"]),
+        )
+        .unwrap_err();
+        assert!(matches!(err, gix::init::Error::InitialCommitWithDetachedHead));
+        assert!(!dir.exists(), "the options are checked before anything is created");
+        Ok(())
+    }
+
+    #[test]
+    fn executable_sample_hooks_are_created_on_all_platforms() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = gix::ThreadSafeRepository::init(
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                executable_sample_hooks: true,
+                ..Default::default()
+            },
+        )?
+        .to_thread_local();
+        let hook = repo.git_dir().join("hooks").join("pre-commit.sample");
+        assert!(std::fs::read(hook)?.starts_with(b"#!/bin/sh"));
+        Ok(())
+    }
+
+    #[test]
+    fn init_with_separate_git_dir_links_to_it_with_a_git_file() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let work_dir = tmp.path().join("worktree");
+        let git_dir = tmp.path().join("repo.git");
+        let repo = gix::ThreadSafeRepository::init_opts(
+            &work_dir,
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                separate_git_dir: Some(git_dir.clone()),
+                ..Default::default()
+            },
+            gix::open::Options::isolated(),
+        )?
+        .to_thread_local();
+        assert_eq!(repo.kind(), gix::repository::Kind::WorkTree { is_linked: false });
+        assert_eq!(repo.work_dir(), Some(work_dir.as_path()));
+        assert_eq!(repo.git_dir(), git_dir);
+        assert!(git_dir.join("HEAD").is_file(), "the git dir is created elsewhere");
+
+        let git_file = work_dir.join(".git");
+        assert!(git_file.is_file(), "the worktree only has a `.git` file");
+        assert_eq!(
+            std::fs::read_to_string(&git_file)?,
+            format!("gitdir: {}\n", git_dir.display())
+        );
+
+        let opened = gix::open_opts(&work_dir, gix::open::Options::isolated())?;
+        assert_eq!(opened.git_dir(), git_dir, "the git file is followed when opening");
+        assert_eq!(opened.work_dir(), Some(work_dir.as_path()));
+        assert_eq!(opened.kind(), gix::repository::Kind::WorkTree { is_linked: false });
+        Ok(())
+    }
+
+    #[test]
+    fn init_with_separate_git_dir_is_not_allowed_if_it_is_not_empty_or_bare() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let git_dir = tmp.path().join("repo.git");
+        std::fs::create_dir(&git_dir)?;
+        std::fs::write(git_dir.join("existing"), b"I was here before you")?;
+        let options = || gix::create::Options {
+            separate_git_dir: Some(git_dir.clone()),
+            ..Default::default()
+        };
+
+        let err =
+            gix::create::into(tmp.path().join("worktree"), gix::create::Kind::WithWorktree, options()).unwrap_err();
+        assert!(matches!(err, gix::create::Error::DirectoryNotEmpty { path } if path == git_dir));
+
+        let err = gix::create::into(tmp.path().join("bare"), gix::create::Kind::Bare, options()).unwrap_err();
+        assert!(matches!(err, gix::create::Error::SeparateGitDirInBareRepository));
+        Ok(())
+    }
+
     #[test]
     fn init_into_empty_directory_creates_a_dot_git_dir() -> crate::Result {
         let tmp = tempfile::tempdir()?;
@@ -126,3 +413,200 @@ mod non_bare {
         Ok(())
     }
 }
+
+mod fs_capabilities {
+    use gix_testtools::tempfile;
+
+    fn written_capabilities(repo: &gix::Repository) -> gix::fs::Capabilities {
+        let config = repo.config_snapshot();
+        let value = |key: &str| config.boolean(key).unwrap_or_else(|| panic!("{key} is written"));
+        gix::fs::Capabilities {
+            precompose_unicode: value("core.precomposeUnicode"),
+            ignore_case: value("core.ignoreCase"),
+            executable_bit: value("core.fileMode"),
+            symlink: value("core.symlinks"),
+        }
+    }
+
+    #[test]
+    fn are_probed_on_the_filesystem_by_default() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = gix::init(tmp.path().join("repo"))?;
+
+        let probe_dir = tmp.path().join("probe");
+        std::fs::create_dir(&probe_dir)?;
+        std::fs::write(probe_dir.join("config"), b"")?;
+        assert_eq!(
+            written_capabilities(&repo),
+            gix::fs::Capabilities::probe(&probe_dir),
+            "the same filesystem has the same capabilities, which includes case-insensitivity where available"
+        );
+        assert!(
+            !repo.git_dir().join("_test_executable_bit").exists(),
+            "probes clean up after themselves"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn are_written_as_given_without_probing() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let default = gix::fs::Capabilities::default();
+        let caps = gix::fs::Capabilities {
+            precompose_unicode: !default.precompose_unicode,
+            ignore_case: !default.ignore_case,
+            executable_bit: !default.executable_bit,
+            symlink: !default.symlink,
+        };
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            gix::create::Kind::Bare,
+            gix::create::Options {
+                fs_capabilities: Some(caps),
+                ..Default::default()
+            },
+            gix::open::Options::isolated(),
+        )?
+        .into();
+        assert_eq!(written_capabilities(&repo), caps);
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+mod permissions {
+    use std::{os::unix::fs::PermissionsExt, path::Path};
+
+    use gix_testtools::tempfile;
+
+    fn mode(path: impl AsRef<Path>) -> u32 {
+        std::fs::metadata(path).expect("exists").permissions().mode() & 0o7777
+    }
+
+    #[test]
+    fn created_files_and_directories_respect_the_umask() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let probe_file = tmp.path().join("probe");
+        std::fs::write(&probe_file, b"")?;
+        let probe_dir = tmp.path().join("probe-dir");
+        std::fs::create_dir(&probe_dir)?;
+        let (file_mode, dir_mode) = (mode(probe_file), mode(probe_dir));
+
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path().join("repo"),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options::default(),
+            gix::open::Options::isolated().config_overrides(["init.defaultBranch=special"]),
+        )?
+        .into();
+        let git_dir = repo.git_dir();
+        for file in [
+            "config",
+            "HEAD",
+            "description",
+            "info/exclude",
+            "hooks/pre-commit.sample",
+        ] {
+            assert_eq!(
+                mode(git_dir.join(file)),
+                file_mode,
+                "{file} is created like any other file, which is subject to the umask"
+            );
+        }
+        for dir in [".", "objects", "objects/pack", "refs/heads", "hooks"] {
+            assert_eq!(
+                mode(git_dir.join(dir)),
+                dir_mode,
+                "{dir} is subject to the umask as well"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sample_hooks_can_be_made_executable() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let probe_dir = tmp.path().join("probe-dir");
+        std::fs::create_dir(&probe_dir)?;
+        let executable_mode = mode(probe_dir) & 0o755;
+
+        let repo = gix::ThreadSafeRepository::init(
+            tmp.path().join("repo"),
+            gix::create::Kind::Bare,
+            gix::create::Options {
+                executable_sample_hooks: true,
+                ..Default::default()
+            },
+        )?
+        .to_thread_local();
+        let hooks = repo.git_dir().join("hooks");
+        for hook in ["pre-commit.sample", "commit-msg.sample", "fsmonitor-watchman.sample"] {
+            assert_eq!(
+                mode(hooks.join(hook)),
+                executable_mode,
+                "{hook} is created with 0o755, subject to the umask"
+            );
+        }
+        assert_eq!(
+            mode(hooks.join("docs.url")) & 0o111,
+            0,
+            "only sample hooks are made executable"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn shared_repository_mode_is_applied_exactly() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = gix::ThreadSafeRepository::init(
+            tmp.path(),
+            gix::create::Kind::Bare,
+            gix::create::Options {
+                shared_repository: gix::create::SharedRepository::Mode(0o640),
+                ..Default::default()
+            },
+        )?
+        .to_thread_local();
+        let git_dir = repo.git_dir();
+        assert_eq!(mode(git_dir.join("config")), 0o640);
+        assert_eq!(mode(git_dir.join("HEAD")), 0o640);
+        assert_eq!(
+            mode(git_dir.join("objects")),
+            0o2750,
+            "directories are executable where readable, and pass on their group"
+        );
+        assert_eq!(
+            repo.config_snapshot()
+                .string("core.sharedRepository")
+                .expect("set")
+                .as_ref(),
+            "0640"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn shared_repository_group_adds_group_permissions() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = gix::ThreadSafeRepository::init(
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                shared_repository: gix::create::SharedRepository::Group,
+                ..Default::default()
+            },
+        )?
+        .to_thread_local();
+        let git_dir = repo.git_dir();
+        assert_eq!(mode(git_dir.join("config")) & 0o660, 0o660);
+        assert_eq!(mode(git_dir.join("refs")) & 0o2770, 0o2770);
+        assert_eq!(
+            repo.config_snapshot()
+                .string("core.sharedRepository")
+                .expect("set")
+                .as_ref(),
+            "1"
+        );
+        Ok(())
+    }
+}
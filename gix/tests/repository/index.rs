@@ -0,0 +1,27 @@
+use gix::bstr::BString;
+
+use crate::util::restricted;
+
+#[test]
+fn from_tree_has_a_stage_0_entry_for_each_path_in_the_tree() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_worktree_repo.sh")?;
+    let repo = gix::open_opts(dir.join("repo"), restricted())?;
+    let tree_id = repo.head_commit()?.tree_id()?;
+
+    let index = repo.index_from_tree(&tree_id)?;
+    let paths = |index: &gix::index::State| -> Vec<BString> {
+        index.entries().iter().map(|e| e.path(index).to_owned()).collect()
+    };
+    assert_eq!(paths(&index), ["a", "b", "dir/c"]);
+    let expected = repo.index()?;
+    assert_eq!(
+        paths(&index),
+        paths(&expected),
+        "it matches the index that git created from the same tree"
+    );
+    assert!(index
+        .entries()
+        .iter()
+        .all(|e| e.stage() == 0 && e.stat == gix::index::entry::Stat::default()));
+    Ok(())
+}
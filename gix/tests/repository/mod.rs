@@ -2,6 +2,7 @@ use gix::Repository;
 
 mod config;
 mod filter;
+mod index;
 mod object;
 mod open;
 mod pathspec;
@@ -9,6 +10,7 @@ mod reference;
 mod remote;
 mod shallow;
 mod state;
+mod submodule;
 mod worktree;
 
 #[test]
@@ -224,3 +224,59 @@ mod head {
         Ok(())
     }
 }
+
+mod reflog {
+    fn repo() -> crate::Result<gix::Repository> {
+        crate::named_subrepo_opts(
+            "make_rev_spec_parse_repos.sh",
+            "reflog_dates",
+            gix::open::Options::isolated(),
+        )
+    }
+
+    #[test]
+    fn lists_entries_from_most_recent_to_oldest() -> crate::Result {
+        let repo = repo()?;
+        let lines: Vec<_> = repo.reflog("HEAD")?.expect("HEAD has a reflog").collect();
+        assert_eq!(lines.len(), 2);
+
+        let (recent, oldest) = (&lines[0], &lines[1]);
+        assert_eq!(recent.message, "commit: B");
+        assert_eq!(oldest.message, "commit (initial): A");
+        assert!(oldest.previous_oid.is_null(), "the first entry created the reference");
+        assert_eq!(recent.previous_oid, oldest.new_oid, "entries form a chain");
+        assert_eq!(
+            recent.new_oid,
+            repo.head_id()?,
+            "the most recent entry is the current state"
+        );
+        assert_eq!(oldest.signature.name, "C O Mitter");
+        assert_eq!(oldest.signature.time.seconds, 1671105600, "2022-12-15 12:00:00 +0000");
+        assert_eq!(recent.signature.time.seconds, 1673784000, "2023-01-15 12:00:00 +0000");
+
+        assert_eq!(
+            repo.reflog("main")?
+                .expect("present")
+                .map(|line| line.new_oid)
+                .collect::<Vec<_>>(),
+            lines.iter().map(|line| line.new_oid).collect::<Vec<_>>(),
+            "the branch was changed along with HEAD"
+        );
+        assert_eq!(
+            repo.reflog("HEAD")?.expect("present").nth(1).map(|line| line.new_oid),
+            Some(repo.rev_parse_single("HEAD@{1}")?.detach()),
+            "the nth entry is what @{{n}} refers to"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn missing_reflogs_and_references() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_rev_spec_parse_repos.sh")?;
+        let repo = gix::open_opts(tmp.path().join("reflog_dates"), gix::open::Options::isolated())?;
+        std::fs::write(repo.git_dir().join("refs/heads/new"), format!("{}\n", repo.head_id()?))?;
+        assert!(repo.reflog("new")?.is_none(), "references don't need to have a reflog");
+        assert!(repo.reflog("does-not-exist").is_err());
+        Ok(())
+    }
+}
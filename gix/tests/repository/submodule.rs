@@ -0,0 +1,100 @@
+use crate::util::named_subrepo_opts;
+
+#[test]
+fn submodules_from_tree() -> crate::Result {
+    let repo = named_subrepo_opts("make_submodules.sh", "with-submodules", gix::open::Options::isolated())?;
+    let head_tree = repo.head_commit()?.tree_id()?;
+    let modules = repo
+        .submodules_from_tree(head_tree)?
+        .expect(".gitmodules was committed in HEAD");
+    assert_eq!(
+        modules.names().map(ToString::to_string).collect::<Vec<_>>(),
+        ["m1"],
+        "the submodule that was added but not committed isn't visible"
+    );
+    assert_eq!(modules.path("m1".into())?.as_ref(), "m1");
+    assert_eq!(modules.url("m1".into())?.to_bstring(), "../module1");
+    assert_eq!(
+        modules.config_path(),
+        None,
+        "there is no path as it wasn't read from a file"
+    );
+
+    let head_commit_id = repo.head_id()?;
+    assert!(
+        repo.submodules_from_tree(head_commit_id)?.is_some(),
+        "commits are peeled to their tree"
+    );
+
+    let parent_tree = repo.rev_parse_single("HEAD~1^{tree}")?;
+    assert!(
+        repo.submodules_from_tree(parent_tree)?.is_none(),
+        "the first commit doesn't have a .gitmodules file"
+    );
+    Ok(())
+}
+
+mod submodules {
+    use crate::util::named_subrepo_opts;
+
+    #[test]
+    fn from_worktree_with_overrides_from_configuration() -> crate::Result {
+        let repo = named_subrepo_opts("make_submodules.sh", "with-submodules", gix::open::Options::isolated())?;
+        let submodules: Vec<_> = repo.submodules()?.expect("modules present").collect();
+        assert_eq!(
+            submodules.iter().map(|sm| sm.name().to_string()).collect::<Vec<_>>(),
+            ["m1", "dir/m1"],
+            "the worktree file has all submodules, even the uncommitted one"
+        );
+        for sm in &submodules {
+            assert_eq!(sm.path()?.as_ref(), sm.name(), "paths are the same as the name here");
+            let url = sm.url()?;
+            assert!(
+                url.path.ends_with(b"module1") && url.path != "../module1",
+                "the absolute url from the repository configuration overrides the relative one: {url:?}"
+            );
+            assert_eq!(sm.branch()?, None);
+            assert!(sm.is_active()?, "submodules are active after being added");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn from_head_tree_in_bare_repositories() -> crate::Result {
+        let repo = named_subrepo_opts(
+            "make_submodules.sh",
+            "with-submodules-bare",
+            gix::open::Options::isolated(),
+        )?;
+        let submodules: Vec<_> = repo.submodules()?.expect("modules present").collect();
+        assert_eq!(
+            submodules.iter().map(|sm| sm.name().to_string()).collect::<Vec<_>>(),
+            ["m1"],
+            "only the committed submodule is visible"
+        );
+        assert_eq!(submodules[0].url()?.to_bstring(), "../module1");
+        assert!(submodules[0].is_active()?, "submodules with url are active");
+        Ok(())
+    }
+
+    #[test]
+    fn a_corrupt_index_is_an_error() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_submodules.sh")?;
+        let repo = gix::open_opts(tmp.path().join("with-submodules"), gix::open::Options::isolated())?;
+        std::fs::remove_file(repo.work_dir().expect("non-bare").join(".gitmodules"))?;
+        std::fs::write(repo.index_path(), "corrupt")?;
+        assert!(
+            repo.submodules().is_err(),
+            "only a missing index makes us fall back to the tree of HEAD"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn none_without_gitmodules() -> crate::Result {
+        let repo = named_subrepo_opts("make_submodules.sh", "module1", gix::open::Options::isolated())?;
+        assert!(repo.modules()?.is_none());
+        assert!(repo.submodules()?.is_none());
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use gix::{
     prelude::{ObjectIdExt, RevSpecExt},
     revision::{
-        spec::parse::{Options, RefsHint},
+        spec::parse::{ObjectKindHint, Options, RefsHint},
         Spec,
     },
 };
@@ -49,6 +49,60 @@ fn fully_failed_disambiguation_still_yields_an_ambiguity_error() {
     );
 }
 
+#[test]
+fn candidates_are_available_as_structured_data() -> crate::Result {
+    use gix::revision::spec::parse::{error::CandidateInfo, Error};
+    let repo = repo("ambiguous_blob_tree_commit").unwrap();
+    let prefix = match parse_spec("0000000000", &repo).unwrap_err() {
+        Error::AmbiguousPrefix { prefix, .. } => prefix,
+        err => unreachable!("unexpected error: {err}"),
+    };
+
+    let candidates = repo.candidates_by_prefix(prefix)?;
+    assert_eq!(
+        candidates.iter().map(|(id, _)| id.to_string()).collect::<Vec<_>>(),
+        [
+            "0000000000e4f9fbd19cf1e932319e5ad0d1d00b",
+            "0000000000cdcf04beb2fab69e65622616294984",
+            "0000000000b36b6aa7ea4b75318ed078f55505c3"
+        ],
+        "full ids are sorted like in the error message"
+    );
+    assert!(matches!(&candidates[0].1, CandidateInfo::Commit { title, .. } if title == "a2onsxbvj"));
+    assert!(matches!(
+        candidates[1].1,
+        CandidateInfo::Object {
+            kind: gix::object::Kind::Tree
+        }
+    ));
+    assert!(matches!(
+        candidates[2].1,
+        CandidateInfo::Object {
+            kind: gix::object::Kind::Blob
+        }
+    ));
+    Ok(())
+}
+
+#[test]
+fn candidates_can_be_abbreviated_to_a_given_length() {
+    let repo = repo("ambiguous_blob_tree_commit").unwrap();
+    assert_eq!(
+        parse_spec_no_baseline_opts("0000000000", &repo, Options::default().candidate_hex_len(Some(13)))
+            .unwrap_err()
+            .to_string(),
+        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000e4f commit 2005-04-07 \"a2onsxbvj\"\n\t0000000000cdc tree\n\t0000000000b36 blob",
+        "ids are abbreviated to the desired length"
+    );
+    assert_eq!(
+        parse_spec_no_baseline_opts("0000000000", &repo, Options::default().candidate_hex_len(Some(4)))
+            .unwrap_err()
+            .to_string(),
+        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000e commit 2005-04-07 \"a2onsxbvj\"\n\t0000000000c tree\n\t0000000000b blob",
+        "but they are always long enough to be unambiguous"
+    );
+}
+
 #[test]
 fn ranges_are_auto_disambiguated_by_committish() {
     let repo = repo("ambiguous_blob_tree_commit").unwrap();
@@ -148,6 +202,7 @@ fn opts_ref_hint(hint: RefsHint) -> Options {
     Options {
         refs_hint: hint,
         object_kind_hint: None,
+        ..Default::default()
     }
 }
 
@@ -216,6 +271,161 @@ fn ambiguous_short_refs_are_dereferenced() {
     );
 }
 
+#[test]
+fn the_interpretation_of_names_that_are_refs_and_object_prefixes_is_recorded() -> crate::Result {
+    use gix::revision::spec::parse::PrefixInterpretation;
+    let repo = repo("ref_named_like_object_prefix")?;
+    let branch_target = repo.find_reference("cafe")?.id().detach();
+
+    let spec = parse_spec("cafe", &repo)?;
+    assert_eq!(spec, Spec::from_id(branch_target.attach(&repo)));
+    assert_eq!(
+        spec.first_prefix_interpretation(),
+        Some(PrefixInterpretation::Reference),
+        "short names are interpreted as branch by default"
+    );
+    assert_eq!(spec.second_prefix_interpretation(), None);
+    assert_eq!(
+        spec.first_reference().expect("present").name.as_bstr(),
+        "refs/heads/cafe"
+    );
+
+    let spec = parse_spec_no_baseline_opts("cafe", &repo, opts_ref_hint(RefsHint::PreferObject))?;
+    assert_eq!(
+        spec,
+        Spec::from_id(hex_to_id("cafe3f2e27174acdf9aa3a394d73714cdcf533d9").attach(&repo))
+    );
+    assert_eq!(spec.first_prefix_interpretation(), Some(PrefixInterpretation::Object));
+
+    let spec = parse_spec_no_baseline("main..cafe", &repo)?;
+    assert_eq!(
+        spec.first_prefix_interpretation(),
+        None,
+        "'main' can't be an object prefix"
+    );
+    assert_eq!(
+        spec.second_prefix_interpretation(),
+        Some(PrefixInterpretation::Reference)
+    );
+
+    let spec = parse_spec_no_baseline("cafe3f2e27174acdf9aa3a394d73714cdcf533d9", &repo)?;
+    assert_eq!(
+        spec.first_prefix_interpretation(),
+        Some(PrefixInterpretation::Object),
+        "full hashes are objects by default"
+    );
+    Ok(())
+}
+
+#[test]
+fn prefixes_that_are_not_found_suggest_similar_reference_names() -> crate::Result {
+    use gix::revision::spec::parse::Error;
+    let repo = repo("ref_named_like_object_prefix")?;
+    assert_eq!(
+        parse_spec_no_baseline("cafd", &repo).unwrap_err().to_string(),
+        "An object prefixed cafd could not be found. The most similar references are:\n\tcafe",
+        "a near-miss of a reference name lists the reference"
+    );
+
+    let err = parse_spec_no_baseline("cafe0", &repo).unwrap_err();
+    let Error::Multi { current, .. } = err else {
+        unreachable!("the prefix and the reference lookup both fail")
+    };
+    match current.downcast_ref::<Error>() {
+        Some(Error::PrefixNotFound { suggestions, .. }) => assert_eq!(suggestions, &["cafe"]),
+        _ => unreachable!("the first error is about the prefix"),
+    }
+
+    assert_eq!(
+        parse_spec_no_baseline("0123", &repo).unwrap_err().to_string(),
+        "An object prefixed 0123 could not be found",
+        "nothing is suggested if no reference is similar enough"
+    );
+
+    assert_eq!(
+        parse_spec_no_baseline("mian", &repo).unwrap_err().to_string(),
+        "The ref partially named \"mian\" could not be found. The most similar references are:\n\tmain",
+        "references that aren't found get suggestions as well, and swapped characters count as one change"
+    );
+
+    assert_eq!(
+        parse_spec_no_baseline_opts("cafd", &repo, opts_ref_hint(RefsHint::ObjectsOnly))
+            .unwrap_err()
+            .to_string(),
+        "An object prefixed cafd could not be found",
+        "references aren't read for suggestions if only objects are allowed"
+    );
+    Ok(())
+}
+
+#[test]
+fn objects_only_never_looks_up_refs() -> crate::Result {
+    use gix::revision::spec::parse::Error;
+    let tmp = gix_testtools::scripted_fixture_writable("make_rev_spec_parse_repos.sh")?;
+    let repo = gix::open(tmp.path().join("ambiguous_refs"))?;
+    std::fs::remove_dir_all(repo.git_dir().join("refs"))?;
+    std::fs::write(repo.git_dir().join("packed-refs"), "corrupt")?;
+    std::fs::write(repo.git_dir().join("HEAD"), "corrupt")?;
+
+    let opts = opts_ref_hint(RefsHint::ObjectsOnly);
+    for (spec, expected) in [
+        ("0000000000e", "0000000000e4f9fbd19cf1e932319e5ad0d1d00b"),
+        (
+            "0000000000e4f9fbd19cf1e932319e5ad0d1d00b",
+            "0000000000e4f9fbd19cf1e932319e5ad0d1d00b",
+        ),
+        ("0000000000e^{tree}", "0000000000cdcf04beb2fab69e65622616294984"),
+    ] {
+        assert_eq!(
+            parse_spec_no_baseline_opts(spec, &repo, opts.clone())?,
+            Spec::from_id(hex_to_id(expected).attach(&repo)),
+            "the broken ref store isn't touched, and objects win even if a ref of the same name exists"
+        );
+    }
+
+    for (spec, name) in [
+        ("HEAD", "HEAD"),
+        ("main~1", "main"),
+        ("@{1}", "HEAD"),
+        ("@{-1}", "HEAD"),
+    ] {
+        match parse_spec_no_baseline_opts(spec, &repo, opts.clone()).unwrap_err() {
+            Error::RefLookupDisabled { name: actual } => assert_eq!(actual, name, "{spec}"),
+            err => panic!("{spec}: unexpected error: {err}"),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn options_can_be_derived_from_repository_configuration() {
+    let r = repo("ambiguous_objects_disambiguation_config_committish").unwrap();
+    let opts = Options::from_repo(&r);
+    assert_eq!(opts.object_kind_hint, Some(ObjectKindHint::Committish));
+    assert_eq!(
+        opts.refs_hint,
+        RefsHint::default(),
+        "everything else is left at its default"
+    );
+    assert_eq!(
+        parse_spec_no_baseline_opts("0000000000f", &r, opts.clone()).unwrap(),
+        rev_parse("0000000000f", &r).unwrap(),
+        "it's what `rev_parse()` uses"
+    );
+
+    let opts = opts
+        .object_kind_hint(None)
+        .refs_hint(RefsHint::Fail)
+        .min_prefix_len(Some(4));
+    assert_eq!(opts.object_kind_hint, None);
+    assert_eq!(opts.refs_hint, RefsHint::Fail);
+    assert_eq!(opts.min_prefix_len, Some(4));
+    assert!(
+        parse_spec_no_baseline_opts("0000000000f", &r, opts).is_err(),
+        "without the configured hint the prefix is ambiguous"
+    );
+}
+
 #[test]
 fn repository_local_disambiguation_hints_disambiguate() {
     let r = repo("ambiguous_objects_disambiguation_config_committish").unwrap();
@@ -279,3 +489,31 @@ fn repository_local_disambiguation_hints_are_overridden_by_specific_ones() {
         "spec overrides overrule the configuration value, which makes this particular object ambiguous between tree and tag"
     );
 }
+
+#[test]
+fn commits_reachable_from_head_can_be_preferred() {
+    let repo = repo("ambiguous_commits_partially_reachable").unwrap();
+    assert!(
+        parse_spec("0000000000", &repo).is_err(),
+        "the prefix is ambiguous by default"
+    );
+    let opts = Options::default().object_kind_hint(Some(ObjectKindHint::Commit));
+    assert!(
+        parse_spec_no_baseline_opts("0000000000", &repo, opts.clone()).is_err(),
+        "there are multiple commits with this prefix"
+    );
+
+    let opts = opts.prefer_reachable_from_head(true);
+    assert!(opts.prefer_reachable_from_head);
+    assert_eq!(
+        parse_spec_no_baseline_opts("0000000000", &repo, opts.clone()).unwrap(),
+        Spec::from_id(hex_to_id("0000000000e4f9fbd19cf1e932319e5ad0d1d00b").attach(&repo)),
+        "only the root commit is reachable from HEAD"
+    );
+
+    let repo = super::repo("ambiguous_commits").unwrap();
+    assert!(
+        parse_spec_no_baseline_opts("0000000000", &repo, opts).is_err(),
+        "if multiple commits are reachable, the prefix stays ambiguous"
+    );
+}
@@ -15,19 +15,119 @@ mod peel;
 mod sibling_branch {
     use gix::revision::spec::parse::Error;
 
-    use crate::revision::spec::from_bytes::{parse_spec_no_baseline, repo};
+    use crate::revision::spec::from_bytes::{parse_spec, parse_spec_no_baseline, repo};
+
+    fn resolved_ref(spec: &str, repo: &gix::Repository) -> String {
+        parse_spec(spec, repo)
+            .unwrap()
+            .first_reference()
+            .expect("sibling branches are references")
+            .name
+            .as_bstr()
+            .to_string()
+    }
+
+    #[test]
+    fn upstream_is_the_tracking_branch_of_the_merge_ref() {
+        let repo = repo("sibling_branches").unwrap();
+        for spec in ["main@{upstream}", "main@{u}", "@{u}"] {
+            assert_eq!(resolved_ref(spec, &repo), "refs/remotes/origin/main", "{spec}");
+        }
+        assert_eq!(
+            resolved_ref("feature@{u}", &repo),
+            "refs/remotes/origin/other-name",
+            "the name of the upstream branch can differ"
+        );
+    }
+
+    #[test]
+    fn push_destination_differs_from_upstream_in_triangular_workflows() {
+        let repo = repo("sibling_branches").unwrap();
+        for spec in ["main@{push}", "@{push}"] {
+            assert_eq!(
+                resolved_ref(spec, &repo),
+                "refs/remotes/fork/main",
+                "remote.pushDefault makes us push to the fork while fetching from origin"
+            );
+        }
+        assert_ne!(
+            parse_spec("main@{push}", &repo).unwrap(),
+            parse_spec("main@{u}", &repo).unwrap(),
+        );
+    }
+
+    #[test]
+    fn push_destination_follows_push_default() {
+        let repo = repo("sibling_branches").unwrap();
+        assert!(
+            matches!(
+                parse_spec("feature@{push}", &repo).unwrap_err(),
+                Error::FindReference(_)
+            ),
+            "with push.default=current, there is no remote-tracking branch for 'feature'"
+        );
+
+        let mut repo = repo;
+        repo.config_snapshot_mut()
+            .set_raw_value("push", None, "default", "upstream")
+            .unwrap();
+        assert_eq!(
+            resolved_ref_no_baseline("feature@{push}", &repo),
+            "refs/remotes/origin/other-name",
+            "pushing to the upstream branch, whatever its name"
+        );
+
+        repo.config_snapshot_mut()
+            .set_raw_value("push", None, "default", "simple")
+            .unwrap();
+        for spec in ["main@{push}", "feature@{push}"] {
+            assert!(
+                matches!(
+                    parse_spec_no_baseline(spec, &repo).unwrap_err(),
+                    Error::NoPushDestination { .. }
+                ),
+                "{spec}: the push destination must be the upstream branch, which isn't the case with a different name or remote"
+            );
+        }
+
+        repo.config_snapshot_mut()
+            .set_raw_value("remote", None, "pushDefault", "origin")
+            .unwrap();
+        assert_eq!(
+            resolved_ref_no_baseline("main@{push}", &repo),
+            "refs/remotes/origin/main",
+            "without a triangular workflow, the upstream branch is the push destination"
+        );
+
+        repo.config_snapshot_mut()
+            .set_raw_value("push", None, "default", "nothing")
+            .unwrap();
+        assert_eq!(
+            parse_spec_no_baseline("main@{push}", &repo).unwrap_err().to_string(),
+            "Branch \"main\" has no push destination as push.default is 'nothing'"
+        );
+    }
+
+    fn resolved_ref_no_baseline(spec: &str, repo: &gix::Repository) -> String {
+        parse_spec_no_baseline(spec, repo)
+            .unwrap()
+            .first_reference()
+            .expect("sibling branches are references")
+            .name
+            .as_bstr()
+            .to_string()
+    }
 
     #[test]
-    fn is_planned_and_delayed_until_remotes_are_sorted() {
+    fn branches_without_configuration_have_no_upstream() {
         let repo = repo("complex_graph").unwrap();
         assert!(matches!(
-            parse_spec_no_baseline("main@{push}", &repo).unwrap_err(),
-            Error::Planned { .. }
+            parse_spec_no_baseline("main@{upstream}", &repo).unwrap_err(),
+            Error::NoUpstream { .. }
         ));
-
         assert!(matches!(
-            parse_spec_no_baseline("main@{upstream}", &repo).unwrap_err(),
-            Error::Planned { .. }
+            parse_spec_no_baseline("main@{push}", &repo).unwrap_err(),
+            Error::NoPushRemote { .. }
         ));
     }
 }
@@ -36,7 +136,7 @@ mod index {
     use gix::{prelude::ObjectIdExt, revision::Spec};
 
     use crate::{
-        revision::spec::from_bytes::{parse_spec, repo},
+        revision::spec::from_bytes::{parse_spec, parse_spec_no_baseline, repo},
         util::hex_to_id,
     };
 
@@ -58,6 +158,174 @@ mod index {
             "Path \"foo\" did not exist in index at stage 0. It does not exist on disk",
         );
     }
+
+    #[test]
+    fn paths_without_stage_are_looked_up_at_stage_0_even_during_conflicts() -> crate::Result {
+        use gix::index::entry::{Flags, Mode};
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let repo = gix::init(tmp.path())?;
+        let blobs = ["clean", "base", "ours", "theirs"]
+            .into_iter()
+            .map(|content| repo.write_blob(content).map(gix::Id::detach))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut state = gix::index::State::new(repo.object_hash());
+        state.dangerously_push_entry(Default::default(), blobs[0], Flags::empty(), Mode::FILE, "clean".into());
+        for stage in 1..=3u32 {
+            let flags = Flags::from_bits_retain(stage << 12);
+            state.dangerously_push_entry(
+                Default::default(),
+                blobs[stage as usize],
+                flags,
+                Mode::FILE,
+                "conflict".into(),
+            );
+        }
+        gix::index::File::from_state(state, repo.index_path()).write(Default::default())?;
+
+        for spec in [":clean", ":0:clean"] {
+            assert_eq!(
+                parse_spec_no_baseline(spec, &repo)?,
+                Spec::from_id(blobs[0].attach(&repo))
+            );
+        }
+        for (spec, expected) in [
+            (":1:conflict", blobs[1]),
+            (":2:conflict", blobs[2]),
+            (":3:conflict", blobs[3]),
+        ] {
+            assert_eq!(
+                parse_spec_no_baseline(spec, &repo)?,
+                Spec::from_id(expected.attach(&repo)),
+                "{spec}"
+            );
+        }
+        for spec in [":conflict", ":0:conflict"] {
+            assert_eq!(
+                parse_spec_no_baseline(spec, &repo).unwrap_err().to_string(),
+                "Path \"conflict\" did not exist in index at stage 0. It does exist at stage 1. It does not exist on disk",
+                "without a stage 0, none of the conflicting stages is picked"
+            );
+        }
+        Ok(())
+    }
+}
+
+mod relative_paths {
+    use gix::revision::spec::parse::{Error, Options};
+
+    use crate::revision::spec::from_bytes::{parse_spec, parse_spec_no_baseline, parse_spec_no_baseline_opts, repo};
+
+    fn with_prefix(prefix: &str) -> Options {
+        Options::default().prefix(Some(prefix.into()))
+    }
+
+    #[test]
+    fn without_prefix_relative_paths_are_an_error() {
+        let repo = repo("relative_paths").unwrap();
+        for spec in [":./top", ":../top", "HEAD:./top", "@:../dir"] {
+            let err = parse_spec_no_baseline(spec, &repo).unwrap_err();
+            assert!(
+                matches!(err, Error::RelativePathWithoutPrefix { .. }),
+                "{spec}: the current directory within the worktree isn't known: {err:?}"
+            );
+        }
+        assert_eq!(
+            parse_spec_no_baseline(":./top", &repo).unwrap_err().to_string(),
+            "Relative path \"./top\" can only be used if the current directory is inside of the worktree"
+        );
+        assert!(
+            parse_spec_no_baseline(":top", &repo).is_ok(),
+            "paths that aren't relative don't need a prefix"
+        );
+    }
+
+    #[test]
+    fn at_the_worktree_root() {
+        let repo = repo("relative_paths").unwrap();
+        for (relative, absolute) in [
+            (":./top", ":top"),
+            (":./dir/./sub/../file", ":dir/file"),
+            ("HEAD:./dir", "HEAD:dir"),
+            ("HEAD:./dir/sub/file", "HEAD:dir/sub/file"),
+        ] {
+            assert_eq!(
+                parse_spec_no_baseline_opts(relative, &repo, with_prefix("")).unwrap(),
+                parse_spec(absolute, &repo).unwrap(),
+                "{relative}"
+            );
+        }
+        assert_eq!(
+            parse_spec_no_baseline_opts("HEAD:./", &repo, with_prefix("")).unwrap(),
+            parse_spec_no_baseline("HEAD^{tree}", &repo).unwrap(),
+            "the prefix itself is the root tree"
+        );
+    }
+
+    #[test]
+    fn in_a_subdirectory() {
+        let repo = repo("relative_paths").unwrap();
+        for prefix in ["dir", "dir/"] {
+            for (relative, absolute) in [
+                (":./file", ":dir/file"),
+                (":../top", ":top"),
+                (":./sub/file", ":dir/sub/file"),
+                ("HEAD:./", "HEAD:dir"),
+                ("HEAD:./sub/file", "HEAD:dir/sub/file"),
+                ("HEAD:../top", ":top"),
+                ("HEAD:top", ":top"),
+            ] {
+                assert_eq!(
+                    parse_spec_no_baseline_opts(relative, &repo, with_prefix(prefix)).unwrap(),
+                    parse_spec(absolute, &repo).unwrap(),
+                    "{relative} in {prefix}, paths not starting with ./ or ../ are never relative"
+                );
+            }
+        }
+        assert_eq!(
+            parse_spec_no_baseline_opts(":./file", &repo, with_prefix("dir/sub")).unwrap(),
+            parse_spec(":dir/sub/file", &repo).unwrap()
+        );
+    }
+
+    #[test]
+    fn paths_outside_of_the_worktree_are_an_error() {
+        let repo = repo("relative_paths").unwrap();
+        let err = parse_spec_no_baseline_opts(":../../top", &repo, with_prefix("dir")).unwrap_err();
+        assert!(matches!(err, Error::RelativePathOutsideOfWorktree { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Relative path \"../../top\" points outside of the worktree"
+        );
+    }
+}
+
+mod pseudo_refs {
+    use gix::{bstr::ByteSlice, prelude::ObjectIdExt, revision::Spec};
+
+    use crate::revision::spec::from_bytes::{parse_spec, repo};
+
+    #[test]
+    fn orig_head_resolves_to_the_stored_object() {
+        let repo = repo("pseudo_refs").unwrap();
+        let stored = std::fs::read(repo.git_dir().join("ORIG_HEAD")).unwrap();
+        let expected = gix::ObjectId::from_hex(stored.trim()).unwrap();
+
+        let spec = parse_spec("ORIG_HEAD", &repo).unwrap();
+        assert_eq!(spec, Spec::from_id(expected.attach(&repo)));
+        assert_eq!(
+            spec.first_reference().map(|r| r.name.as_bstr().to_string()),
+            Some("ORIG_HEAD".into()),
+            "pseudo-refs are references in their own right"
+        );
+    }
+
+    #[test]
+    fn other_pseudo_refs_resolve_when_present() {
+        let repo = repo("pseudo_refs").unwrap();
+        for name in ["MERGE_HEAD", "CHERRY_PICK_HEAD", "FETCH_HEAD", "ORIG_HEAD..MERGE_HEAD"] {
+            parse_spec(name, &repo).unwrap_or_else(|err| panic!("{name} should resolve: {err}"));
+        }
+    }
 }
 
 #[test]
@@ -84,6 +352,56 @@ fn names_are_made_available_via_references() {
     );
 }
 
+#[test]
+fn endpoints_pair_references_with_the_objects_they_resolved_to() {
+    let repo = repo("complex_graph").unwrap();
+    let main = repo.find_reference("main").unwrap().id().detach();
+    let g = repo.find_reference("g").unwrap().id().detach();
+
+    let spec = parse_spec_no_baseline("main..g", &repo).unwrap();
+    let (reference, id) = spec.first_endpoint();
+    assert_eq!(
+        reference.map(|r| r.name.as_bstr().to_string()),
+        Some("refs/heads/main".into())
+    );
+    assert_eq!(id, main);
+    let (reference, id) = spec.second_endpoint().expect("a range has two endpoints");
+    assert_eq!(
+        reference.map(|r| r.name.as_bstr().to_string()),
+        Some("refs/heads/g".into())
+    );
+    assert_eq!(id, g);
+
+    let spec = parse_spec_no_baseline(&main.to_string(), &repo).unwrap();
+    assert_eq!(
+        spec.first_endpoint(),
+        (None, main.attach(&repo)),
+        "no reference was involved"
+    );
+    assert!(spec.second_endpoint().is_none());
+}
+
+#[test]
+fn at_is_shorthand_for_head() {
+    let repo = repo("complex_graph").unwrap();
+    assert_eq!(
+        parse_spec_no_baseline("@", &repo).unwrap(),
+        parse_spec_no_baseline("HEAD", &repo).unwrap()
+    );
+    assert_eq!(
+        parse_spec_no_baseline("@~2", &repo).unwrap(),
+        parse_spec_no_baseline("HEAD~2", &repo).unwrap(),
+        "navigation works as well"
+    );
+    assert_eq!(
+        parse_spec_no_baseline("@~2", &repo)
+            .unwrap()
+            .first_reference()
+            .map(|r| r.name.as_bstr().to_string()),
+        Some("HEAD".into())
+    );
+}
+
 #[test]
 fn bad_objects_are_valid_until_they_are_actually_read_from_the_odb() {
     {
@@ -113,6 +431,65 @@ fn bad_objects_are_valid_until_they_are_actually_read_from_the_odb() {
     }
 }
 
+#[test]
+fn invalid_candidates_are_pruned_when_validating_objects() {
+    let repo = repo("blob.corrupt").unwrap();
+    assert!(
+        parse_spec_no_baseline("cafe", &repo).is_err(),
+        "both the valid and the corrupt blob match the prefix"
+    );
+    assert_eq!(
+        parse_spec_no_baseline("cafe^{object}", &repo).unwrap(),
+        Spec::from_id(hex_to_id("cafe3f2e27174acdf9aa3a394d73714cdcf533d9").attach(&repo)),
+        "the corrupt object is removed from the candidates, just like when peeling to a kind"
+    );
+    assert_eq!(
+        parse_spec_no_baseline("cafe^{blob}", &repo).unwrap(),
+        parse_spec_no_baseline("cafe^{object}", &repo).unwrap()
+    );
+}
+
+#[test]
+fn short_prefixes_can_be_rejected() {
+    use gix::revision::spec::parse::Options;
+    let repo = repo("blob.bad").unwrap();
+    let expected = Spec::from_id(hex_to_id("e32851d29feb48953c6f40b2e06d630a3c49608a").attach(&repo));
+    assert_eq!(
+        parse_spec_no_baseline_opts("e328", &repo, Options::default()).unwrap(),
+        expected,
+        "there is no minimum by default"
+    );
+
+    let opts = Options {
+        min_prefix_len: Some(7),
+        ..Default::default()
+    };
+    assert_eq!(
+        parse_spec_no_baseline_opts("e328", &repo, opts.clone())
+            .unwrap_err()
+            .to_string(),
+        "The short hash e328 must have at least 7 hex characters to be used",
+        "it's also tried as reference, which fails as well"
+    );
+    assert_eq!(
+        parse_spec_no_baseline_opts("e32851d", &repo, opts).unwrap(),
+        expected,
+        "prefixes of the minimum length are fine"
+    );
+    assert_eq!(
+        parse_spec_no_baseline_opts(
+            "e328",
+            &repo,
+            Options {
+                min_prefix_len: Some(4),
+                ..Default::default()
+            }
+        )
+        .unwrap(),
+        expected
+    );
+}
+
 #[test]
 fn access_blob_through_tree() {
     let repo = repo("ambiguous_blob_tree_commit").unwrap();
@@ -135,3 +512,82 @@ fn empty_tree_as_full_name() {
         Spec::from_id(hex_to_id("4b825dc642cb6eb9a060e54bf8d69288fbee4904").attach(&repo))
     );
 }
+
+#[test]
+fn rev_parse_single_tells_ranges_and_unborn_references_apart() -> crate::Result {
+    use gix::revision::spec::parse::single::Error;
+    let unborn = repo("new")?;
+    let repo = repo("complex_graph")?;
+    assert_eq!(
+        repo.rev_parse_single("@")?,
+        hex_to_id("55e825ebe8fd2ff78cad3826afb696b96b576a7e"),
+        "a single commit"
+    );
+    assert!(matches!(
+        repo.rev_parse_single("@~1..@").unwrap_err(),
+        Error::RangedRev { spec } if spec == "@~1..@"
+    ));
+    assert!(matches!(
+        repo.rev_parse_single("does-not-exist").unwrap_err(),
+        Error::Parse(_)
+    ));
+
+    match unborn.rev_parse_single("HEAD").unwrap_err() {
+        Error::UnbornBranch { spec, branch } => {
+            assert_eq!(spec, "HEAD");
+            assert_eq!(branch.as_bstr(), "refs/heads/main", "the branch HEAD points to");
+        }
+        err => unreachable!("unexpected error: {err}"),
+    }
+    Ok(())
+}
+
+#[test]
+fn single_tells_single_objects_from_ranges() -> crate::Result {
+    use gix::revision::spec::single::Error;
+    use gix_revision::spec::Kind;
+    let repo = repo("complex_graph")?;
+    assert_eq!(
+        parse_spec_no_baseline("@", &repo)?.single()?,
+        hex_to_id("55e825ebe8fd2ff78cad3826afb696b96b576a7e"),
+        "a single commit"
+    );
+    for (spec, kind) in [
+        ("@~1..@", Kind::RangeBetween),
+        ("@~1...@", Kind::ReachableToMergeBase),
+        ("^@", Kind::ExcludeReachable),
+        ("@^@", Kind::IncludeReachableFromParents),
+    ] {
+        assert_eq!(
+            parse_spec_no_baseline(spec, &repo)?.single(),
+            Err(Error::Range { kind }),
+            "{spec}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn unborn_head_of_new_repository() -> crate::Result {
+    use gix::revision::spec::parse::Error;
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let repo = gix::init(tmp.path())?;
+    let branch = repo.head_name()?.expect("HEAD is symbolic");
+    for spec in ["HEAD", "@", "HEAD^{commit}"] {
+        match parse_spec_no_baseline(spec, &repo).unwrap_err() {
+            Error::UnbornBranch {
+                reference,
+                branch: unborn,
+            } => {
+                assert_eq!(reference.as_bstr(), "HEAD");
+                assert_eq!(unborn, branch, "the branch that doesn't exist yet is named");
+            }
+            err => unreachable!("{spec}: unexpected error: {err}"),
+        }
+    }
+    assert_eq!(
+        parse_spec_no_baseline("HEAD", &repo).unwrap_err().to_string(),
+        format!("Reference 'HEAD' points to the unborn branch '{branch}' which doesn't exist yet")
+    );
+    Ok(())
+}
@@ -23,3 +23,39 @@ fn trailing_colon_is_equivalent_to_peel_to_tree() {
     let repo = &repo("complex_graph").unwrap();
     assert_eq!(parse_spec("@^{tree}", repo).unwrap(), parse_spec("@:", repo).unwrap());
 }
+
+mod to_tag {
+    use gix::revision::spec::parse::Error;
+
+    use crate::revision::spec::from_bytes::{parse_spec, repo};
+
+    #[test]
+    fn annotated_tags_are_returned_as_is() {
+        let repo = &repo("tag_kinds").unwrap();
+        for spec in ["v1.0", "v1.0-nested"] {
+            let tag = parse_spec(spec, repo).unwrap();
+            assert_eq!(
+                parse_spec(&format!("{spec}^{{tag}}"), repo).unwrap().single(),
+                tag.single(),
+                "{spec}: the tag object itself, without peeling nested tags"
+            );
+        }
+    }
+
+    #[test]
+    fn objects_that_are_not_annotated_tags_are_an_error() {
+        let repo = &repo("tag_kinds").unwrap();
+        for (spec, expected_actual) in [
+            ("lightweight^{tag}", gix::object::Kind::Commit),
+            ("v1.0^{tree}^{tag}", gix::object::Kind::Tree),
+        ] {
+            match parse_spec(spec, repo).unwrap_err() {
+                Error::ObjectKind { actual, expected, .. } => {
+                    assert_eq!(actual, expected_actual, "{spec}");
+                    assert_eq!(expected, gix::object::Kind::Tag, "{spec}");
+                }
+                err => panic!("{spec}: unexpected error: {err:?}"),
+            }
+        }
+    }
+}
@@ -1,10 +1,7 @@
-use gix::{
-    prelude::ObjectIdExt,
-    revision::{spec::parse::Error, Spec},
-};
+use gix::{prelude::ObjectIdExt, revision::Spec};
 
 use crate::{
-    revision::spec::from_bytes::{parse_spec, parse_spec_no_baseline, repo},
+    revision::spec::from_bytes::{parse_spec, repo},
     util::hex_to_id,
 };
 
@@ -30,6 +27,51 @@ fn nth_prior_checkout() {
     );
 }
 
+#[test]
+fn by_checked_out_branch_in_ranges() {
+    let repo = repo("complex_graph").unwrap();
+    let id_of = |name: &str| repo.find_reference(name).unwrap().id().detach();
+
+    let spec = parse_spec("@{-1}..@{-2}", &repo).unwrap();
+    assert_eq!(spec.first_reference().expect("present").name.as_bstr(), "refs/heads/i");
+    assert_eq!(
+        spec.second_reference().expect("present").name.as_bstr(),
+        "refs/heads/main"
+    );
+    assert_eq!(
+        spec.detach(),
+        gix_revision::Spec::Range {
+            from: id_of("i"),
+            to: id_of("main")
+        },
+        "each side of the range resolves its own prior checkout"
+    );
+
+    assert_eq!(
+        parse_spec("@{-1}..HEAD", &repo).unwrap().detach(),
+        gix_revision::Spec::Range {
+            from: id_of("i"),
+            to: repo.head_id().unwrap().detach()
+        }
+    );
+    assert_eq!(
+        parse_spec("@{-2}...@{-1}", &repo).unwrap().detach(),
+        gix_revision::Spec::Merge {
+            theirs: id_of("main"),
+            ours: id_of("i")
+        }
+    );
+
+    assert_eq!(
+        parse_spec("@{-6}..@{-1}", &repo).unwrap_err().to_string(),
+        "HEAD has 5 prior checkouts and checkout number 6 is out of range"
+    );
+    assert_eq!(
+        parse_spec("@{-1}..@{-6}", &repo).unwrap_err().to_string(),
+        "HEAD has 5 prior checkouts and checkout number 6 on the right side of the range is out of range"
+    );
+}
+
 #[test]
 fn by_index_unborn_head() {
     let repo = &repo("new").unwrap();
@@ -77,11 +119,53 @@ fn by_index() {
     );
 }
 
-#[test]
-fn by_date_is_planned_until_git_date_crate_is_implements_parsing() {
-    let repo = repo("complex_graph").unwrap();
-    assert!(matches!(
-        parse_spec_no_baseline("main@{1979-02-26 18:30:00}", &repo).unwrap_err(),
-        Error::Planned { .. }
-    ));
+mod by_date {
+    use gix::revision::spec::parse::Error;
+
+    use crate::revision::spec::from_bytes::{parse_spec, parse_spec_no_baseline, repo};
+
+    fn commit_message(spec: &str, repo: &gix::Repository) -> String {
+        parse_spec(spec, repo)
+            .unwrap_or_else(|err| panic!("{spec}: {err}"))
+            .single()
+            .expect("single object")
+            .object()
+            .unwrap()
+            .into_commit()
+            .message_raw()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn picks_the_entry_that_was_current_at_the_given_date() {
+        let repo = repo("reflog_dates").unwrap();
+        for (spec, expected) in [
+            ("HEAD@{2023-01-01}", "A\n"),
+            ("main@{2023-01-01}", "A\n"),
+            ("main@{2023-02-01}", "B\n"),
+            ("HEAD@{yesterday}", "B\n"),
+            ("HEAD@{2.days.ago}", "B\n"),
+            ("@{now}", "B\n"),
+        ] {
+            assert_eq!(commit_message(spec, &repo), expected, "{spec}");
+        }
+    }
+
+    #[test]
+    fn dates_before_the_oldest_entry_use_the_oldest_known_state() {
+        let repo = repo("reflog_dates").unwrap();
+        assert_eq!(commit_message("main@{2022-01-01}", &repo), "A\n");
+    }
+
+    #[test]
+    fn unparseable_dates_are_an_error() {
+        let repo = repo("reflog_dates").unwrap();
+        let err = parse_spec_no_baseline("main@{not a date}", &repo).unwrap_err();
+        assert!(matches!(err, Error::Parse(_)), "{err:?}");
+        assert!(
+            err.to_string().contains("not a date"),
+            "the offending input is mentioned: {err}"
+        );
+    }
 }
@@ -54,6 +54,34 @@ mod with_known_revision {
             "None of 1 commits from 0000000000e matched regex \"^x\"",
         );
     }
+
+    #[test]
+    fn search_starts_at_the_known_revision_instead_of_all_references() {
+        let repo = repo("regex_anchoring").unwrap();
+        let head = repo.head_commit().unwrap();
+        let first_fix = head.parent_ids().next().expect("one parent").detach();
+        let youngest_fix = repo.find_reference("other").unwrap().id().detach();
+
+        assert_eq!(
+            parse_spec(":/fix", &repo).unwrap(),
+            Spec::from_id(youngest_fix.attach(&repo)),
+            "without a known revision, the youngest commit of all references matches"
+        );
+        assert_eq!(
+            parse_spec("HEAD^{/fix}", &repo).unwrap(),
+            Spec::from_id(first_fix.attach(&repo)),
+            "the nearest matching ancestor of HEAD is found, even though a younger commit matches elsewhere"
+        );
+        assert_eq!(
+            parse_spec("other^{/fix}", &repo).unwrap(),
+            Spec::from_id(youngest_fix.attach(&repo))
+        );
+        assert_eq!(
+            parse_spec("HEAD^{/!-fix}", &repo).unwrap(),
+            Spec::from_id(head.id.attach(&repo)),
+            "the known revision itself is the first candidate"
+        );
+    }
 }
 
 mod find_youngest_matching_commit {
@@ -62,6 +90,35 @@ mod find_youngest_matching_commit {
     use super::*;
     use crate::revision::spec::from_bytes::parse_spec;
 
+    #[test]
+    fn negation_finds_the_youngest_commit_not_matching() {
+        let repo = repo("regex_anchoring").unwrap();
+        let head = repo.head_commit().unwrap();
+        let youngest_fix = repo.find_reference("other").unwrap().id().detach();
+
+        assert_eq!(
+            parse_spec(":/fix", &repo).unwrap(),
+            Spec::from_id(youngest_fix.attach(&repo))
+        );
+        assert_eq!(
+            parse_spec_no_baseline(":/!-fix", &repo).unwrap(),
+            Spec::from_id(head.id.attach(&repo)),
+            "the youngest commit is skipped as it mentions 'fix', the next one doesn't"
+        );
+        assert_eq!(
+            parse_spec_no_baseline(":/!-problem", &repo).unwrap(),
+            Spec::from_id(head.id.attach(&repo)),
+            "the same commit is found with anything both fixes mention"
+        );
+        assert!(
+            parse_spec_no_baseline(":/!-e", &repo)
+                .unwrap_err()
+                .to_string()
+                .starts_with("None of 3 commits reached from all references matched"),
+            "all messages contain 'e', so no commit lacks it"
+        );
+    }
+
     #[test]
     #[cfg(not(feature = "regex"))]
     fn contained_string_matches() {
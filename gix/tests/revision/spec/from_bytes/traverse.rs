@@ -74,3 +74,35 @@ fn ancestors() {
         "Commit 55e825e has 3 ancestors along the first parent and ancestor number 42 is out of range"
     );
 }
+
+#[test]
+fn exclude_parents_yields_the_commit_and_all_of_its_parents() -> crate::Result {
+    let repo = repo("complex_graph")?;
+    let id = |spec: &str| {
+        parse_spec_no_baseline(spec, &repo)
+            .expect("valid")
+            .single()
+            .expect("single")
+    };
+
+    let spec = parse_spec_no_baseline("a^!", &repo)?;
+    let (commit, parents) = spec.commit_and_excluded_parents()?.expect("a^! was used");
+    assert_eq!(commit, id("a"));
+    assert_eq!(parents, [id("a^1"), id("a^2")], "all parents are excluded, in order");
+
+    let (commit, parents) = parse_spec_no_baseline("e^!", &repo)?
+        .commit_and_excluded_parents()?
+        .expect("a^! was used");
+    assert_eq!(commit, id("e"));
+    assert!(parents.is_empty(), "root commits have no parents to exclude");
+
+    for spec in ["a", "a..b", "a^@"] {
+        assert!(
+            parse_spec_no_baseline(spec, &repo)?
+                .commit_and_excluded_parents()?
+                .is_none(),
+            "{spec}: only a^! is supported"
+        );
+    }
+    Ok(())
+}
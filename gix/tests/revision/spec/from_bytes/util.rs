@@ -79,9 +79,10 @@ static BASELINE: Lazy<HashMap<PathBuf, HashMap<BString, Option<gix_revision::Spe
                             }
                         } else {
                             lines.next().expect("merge-base to consume");
+                            // git prints `A...B` as `B`, `A` and the negated merge-base.
                             gix_revision::Spec::Merge {
-                                theirs: first_hash,
-                                ours: second_hash,
+                                theirs: second_hash,
+                                ours: first_hash,
                             }
                         }
                     }